@@ -1,8 +1,33 @@
 //! 主应用模块
 //! 定义应用状态和核心逻辑
 
+use crate::chart::ChartState;
+use crate::config::{AppConfig, ConfigLoadError, ConfigReload, ConfigWatcher};
+use crate::database::{
+    DatabaseManager, DbObject, QueryParams, QueryResult, TableName, format_value,
+};
+use crate::ddl::SqlTemplate;
+use crate::designer::TableDesigner;
+use crate::diff::ResultDiff;
+use crate::editing::{CellEdit, DataEditor};
+use crate::explain::ExplainPlan;
+use crate::export::{CopyData, CopyDialog, CopyFormat, CopyOptions};
+use crate::history::{HistoryEntry, HistoryPanel, QueryHistory};
+use crate::i18n::{tr, tr_args};
+use crate::inspector::CellViewer;
+use crate::library::{LibraryEntry, LibraryPanel, PlaceholderPrompt, PromptAction, SaveDialog};
+use crate::params::{ParamPrompt, ParamType};
+use crate::record::RecordState;
+use crate::results::{GridState, ResultView};
+use crate::schema::SchemaCompare;
+use crate::session::{EditorTabState, PanelLayout, SessionState};
+use crate::settings::SettingsState;
+use crate::stats::StatsReport;
+use crate::theme::{self, Theme, ThemeSet};
+use duckdb::types::Value;
 use eframe::egui;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
 /// 菜单类型枚举
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -17,14 +42,29 @@ pub enum MenuType {
     Help,
 }
 
+impl MenuType {
+    /// 按菜单栏顺序排列的全部菜单
+    pub const ALL: [MenuType; 8] = [
+        MenuType::File,
+        MenuType::Edit,
+        MenuType::Navigate,
+        MenuType::Search,
+        MenuType::SqlEditor,
+        MenuType::Database,
+        MenuType::Window,
+        MenuType::Help,
+    ];
+}
+
 /// 菜单项结构
 #[derive(Clone, PartialEq)]
 pub struct MenuItem {
     pub label: String,
-    pub shortcut: Option<String>,       // 快捷键
-    pub action: String,                 // 动作描述
-    pub enabled: bool,                  // 是否启用
-    pub submenu: Option<Vec<MenuItem>>, // 子菜单
+    pub shortcut: Option<String>,         // 快捷键
+    pub default_shortcut: Option<String>, // 默认快捷键
+    pub action: String,                   // 动作描述
+    pub enabled: bool,                    // 是否启用
+    pub submenu: Option<Vec<MenuItem>>,   // 子菜单
 }
 
 impl MenuItem {
@@ -32,6 +72,7 @@ impl MenuItem {
         Self {
             label: label.to_owned(),
             shortcut: shortcut.map(|s| s.to_owned()),
+            default_shortcut: shortcut.map(|s| s.to_owned()),
             action: action.to_owned(),
             enabled: true,
             submenu: None,
@@ -64,8 +105,54 @@ pub struct ExplorerTab {
     pub expanded_paths: std::collections::HashSet<String>, // 记录展开的路径
 }
 
+/// SQL 编辑器标签页
+#[derive(Clone, Debug, Default)]
+pub struct EditorTab {
+    pub title: String,
+    pub file_path: Option<String>,             // 关联的文件路径
+    pub content: String,                       // 编辑器内容
+    pub is_dirty: bool,                        // 是否有未保存的修改
+    pub connection: Option<String>,            // 关联的数据库路径
+    pub result: Option<QueryResult>,           // 最近一次执行的结果
+    pub error: Option<String>,                 // 最近一次执行的错误
+    pub plan: Option<ExplainPlan>,             // 最近一次获取的执行计划
+    pub view: ResultView,                      // 结果面板显示的内容
+    pub edits: Option<DataEditor>,             // 结果可以编辑时暂存的修改
+    pub grid: GridState,                       // 结果表格的选区和筛选条件
+    pub source: Option<(String, QueryParams)>, // 产生结果的语句，重新执行时使用
+    pub chart: ChartState,                     // 结果的图表设置
+    pub record: RecordState,                   // 记录视图的搜索和比较设置
+}
+
+/// 数据库连接
+pub struct DbConnection {
+    pub name: String,
+    pub path: String,
+    pub manager: DatabaseManager,
+    pub objects: Option<Vec<DbObject>>, // 侧边栏显示的数据库对象，`None` 表示需要重新读取
+}
+
+/// 通知级别
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// 通知消息
+#[derive(Clone, Debug)]
+pub struct Notification {
+    pub level: NotificationLevel,
+    pub title: String,
+    pub message: String,
+    pub created_at: Option<f64>, // 首次显示的时间戳
+}
+
+/// 普通通知的显示时长（秒），警告和错误需要手动关闭
+pub const NOTIFICATION_TIMEOUT: f64 = 5.0;
+
 /// 主应用状态
-#[derive(Clone)]
 pub struct MyApp {
     pub name: String,
     pub age: u32,
@@ -79,6 +166,39 @@ pub struct MyApp {
     pub status_message_time: f64,      // 消息显示时间
     pub explorer_tabs: Vec<ExplorerTab>, // 文件资源管理器标签页
     pub active_explorer_tab: Option<usize>, // 当前活动的资源管理器标签页索引
+    pub editor_tabs: Vec<EditorTab>,   // SQL 编辑器标签页
+    pub active_editor_tab: Option<usize>, // 当前活动的编辑器标签页索引
+    pub connections: Vec<DbConnection>, // 活动的数据库连接
+    pub active_connection: Option<usize>, // 当前选中的连接索引
+    pub panel_layout: PanelLayout,     // 面板布局
+    pub config: AppConfig,             // 应用配置
+    last_saved_config: Option<AppConfig>, // 上次保存的配置
+    last_saved_session: Option<SessionState>, // 上次保存的会话
+    last_save_time: f64,               // 上次自动保存的时间戳
+    pub notifications: Vec<Notification>, // 待显示的通知
+    config_watcher: ConfigWatcher,     // 配置文件监视器
+    appearance_dirty: bool,            // 外观设置是否需要重新应用
+    fonts_dirty: bool,                 // 字体是否需要重新加载
+    pub history: Rc<QueryHistory>,     // 查询历史
+    pub history_panel: HistoryPanel,   // 历史面板状态
+    pub themes: ThemeSet,              // 当前使用的浅色/深色主题
+    pub missing_themes: HashSet<String>, // 已经提示过找不到的主题
+    pub custom_themes: Vec<Theme>,     // 主题目录中的自定义主题
+    pub library_panel: LibraryPanel,   // 查询库面板状态
+    pub placeholder_prompt: Option<PlaceholderPrompt>, // 待填写的占位符
+    pub param_prompt: Option<ParamPrompt>, // 待输入的绑定参数
+    pub param_values: HashMap<String, (ParamType, String)>, // 上次输入的命名参数
+    pub copy_options: CopyOptions,     // 上次使用的复制选项
+    pub copy_dialog: Option<CopyDialog>, // 高级复制对话框
+    pub clipboard: Option<String>,     // 待写入剪贴板的文本
+    pub column_stats: Option<StatsReport>, // 列统计窗口
+    pub cell_viewer: Option<CellViewer>, // 单元格查看窗口
+    pub result_diff: Option<ResultDiff>, // 数据对比窗口
+    pub schema_compare: Option<SchemaCompare>, // 结构对比窗口
+    pub table_designer: Option<TableDesigner>, // 表设计窗口
+    pub close_prompt: bool,            // 是否显示未提交事务的退出确认
+    close_confirmed: bool,             // 已确认退出，不再拦截关闭请求
+    pub settings: SettingsState,       // 设置面板状态
 }
 
 impl Default for MyApp {
@@ -89,24 +209,24 @@ impl Default for MyApp {
         menu_data.insert(
             MenuType::File,
             vec![
-                MenuItem::new("打开文件夹", Some("Ctrl+Shift+O"), "open_folder"),
-                MenuItem::new("最近的编辑", None, "recent_edit"),
-                MenuItem::new("查找指定文件...", Some("Ctrl+O"), "find_file"),
-                MenuItem::new("新建", Some("Ctrl+N"), "new_file"),
-                MenuItem::new("保存", Some("Ctrl+S"), "save_file"),
-                MenuItem::new("另存为", None, "save_as"),
-                MenuItem::new("保存全部", Some("Ctrl+Shift+S"), "save_all"),
-                MenuItem::new("关闭", Some("Ctrl+W"), "close_file"),
-                MenuItem::new("打印", Some("Ctrl+P"), "print_file"),
-                MenuItem::new("重命名", Some("F2"), "rename_file"),
-                MenuItem::new("刷新", Some("F5"), "refresh_file"),
+                MenuItem::new("menu.open_folder", Some("Ctrl+Shift+O"), "open_folder"),
+                MenuItem::new("menu.recent_edit", None, "recent_edit"),
+                MenuItem::new("menu.find_file", Some("Ctrl+O"), "find_file"),
+                MenuItem::new("menu.new_file", Some("Ctrl+N"), "new_file"),
+                MenuItem::new("menu.save_file", Some("Ctrl+S"), "save_file"),
+                MenuItem::new("menu.save_as", None, "save_as"),
+                MenuItem::new("menu.save_all", Some("Ctrl+Shift+S"), "save_all"),
+                MenuItem::new("menu.close_file", Some("Ctrl+W"), "close_file"),
+                MenuItem::new("menu.print_file", Some("Ctrl+P"), "print_file"),
+                MenuItem::new("menu.rename_file", Some("F2"), "rename_file"),
+                MenuItem::new("menu.refresh_file", Some("F5"), "refresh_file"),
                 MenuItem::new("---", None, "separator"),
-                MenuItem::new("导入", None, "import_file"),
-                MenuItem::new("导出", None, "export_file"),
+                MenuItem::new("menu.import_file", None, "import_file"),
+                MenuItem::new("menu.export_file", None, "export_file"),
                 MenuItem::new("---", None, "separator"),
-                MenuItem::new("属性", None, "file_property"),
+                MenuItem::new("menu.file_property", None, "file_property"),
                 MenuItem::new("---", None, "separator"),
-                MenuItem::new("退出", Some("Ctrl+Q"), "exit"),
+                MenuItem::new("menu.exit", Some("Ctrl+Q"), "exit"),
             ],
         );
 
@@ -114,47 +234,74 @@ impl Default for MyApp {
         menu_data.insert(
             MenuType::Edit,
             vec![
-                MenuItem::new("撤销", Some("Ctrl+Z"), "undo"),
-                MenuItem::new("重做", Some("Ctrl+Y"), "redo"),
-                MenuItem::new("剪切", Some("Ctrl+X"), "cut"),
-                MenuItem::new("复制", Some("Ctrl+C"), "copy"),
-                MenuItem::new("粘贴", Some("Ctrl+V"), "paste"),
-                MenuItem::new("删除", Some("Del"), "delete").enabled(false),
+                MenuItem::new("menu.undo", Some("Ctrl+Z"), "undo"),
+                MenuItem::new("menu.redo", Some("Ctrl+Y"), "redo"),
+                MenuItem::new("menu.cut", Some("Ctrl+X"), "cut"),
+                MenuItem::new("menu.copy", Some("Ctrl+C"), "copy"),
+                MenuItem::new("menu.copy_advanced", None, "copy_advanced"),
+                MenuItem::new("menu.paste", Some("Ctrl+V"), "paste"),
+                MenuItem::new("menu.delete", Some("Del"), "delete").enabled(false),
                 MenuItem::new("---", None, "separator"),
-                MenuItem::new("生成UUID", Some("Del"), "generate_uuid"),
+                MenuItem::new("menu.generate_uuid", Some("Del"), "generate_uuid"),
             ],
         );
 
         menu_data.insert(
             MenuType::Navigate,
-            vec![MenuItem::new("跳转到行", Some("Ctrl+G"), "goto_line")],
+            vec![MenuItem::new("menu.goto_line", Some("Ctrl+G"), "goto_line")],
         );
 
         menu_data.insert(
             MenuType::Search,
-            vec![MenuItem::new("查找", Some("Ctrl+F"), "find")],
+            vec![MenuItem::new("menu.find", Some("Ctrl+F"), "find")],
         );
 
         menu_data.insert(
             MenuType::SqlEditor,
-            vec![MenuItem::new("新建查询", None, "new_query")],
+            vec![
+                MenuItem::new("menu.new_query", None, "new_query"),
+                MenuItem::new("menu.run_query", Some("Ctrl+Enter"), "run_query"),
+                MenuItem::new("menu.explain", Some("Ctrl+E"), "explain"),
+                MenuItem::new(
+                    "menu.explain_analyze",
+                    Some("Ctrl+Shift+E"),
+                    "explain_analyze",
+                ),
+                MenuItem::new("---", None, "separator"),
+                MenuItem::new("menu.save_to_library", None, "save_to_library"),
+                MenuItem::new("menu.reload_library", None, "reload_library"),
+            ],
         );
 
         menu_data.insert(
             MenuType::Database,
-            vec![MenuItem::new("连接数据库", None, "connect_db")],
+            vec![
+                MenuItem::new("menu.connect_db", None, "connect_db"),
+                MenuItem::new("menu.compare_results", None, "compare_results"),
+                MenuItem::new("menu.compare_schemas", None, "compare_schemas"),
+                MenuItem::new("---", None, "separator"),
+                MenuItem::new("menu.toggle_auto_commit", None, "toggle_auto_commit"),
+                MenuItem::new("menu.commit", Some("Ctrl+Shift+C"), "commit"),
+                MenuItem::new("menu.rollback", Some("Ctrl+Shift+R"), "rollback"),
+            ],
         );
 
         menu_data.insert(
             MenuType::Window,
-            vec![MenuItem::new(
-                "新建窗口",
-                Some("Ctrl+Shift+N"),
-                "new_window",
-            )],
+            vec![
+                MenuItem::new("menu.new_window", Some("Ctrl+Shift+N"), "new_window"),
+                MenuItem::new("menu.toggle_sidebar", Some("Ctrl+B"), "toggle_sidebar"),
+                MenuItem::new("menu.toggle_status_bar", None, "toggle_status_bar"),
+                MenuItem::new("menu.toggle_history", Some("Ctrl+H"), "toggle_history"),
+                MenuItem::new("---", None, "separator"),
+                MenuItem::new("menu.open_settings", Some("Ctrl+,"), "open_settings"),
+            ],
         );
 
-        menu_data.insert(MenuType::Help, vec![MenuItem::new("关于", None, "about")]);
+        menu_data.insert(
+            MenuType::Help,
+            vec![MenuItem::new("menu.about", None, "about")],
+        );
 
         Self {
             name: "Arthur".to_owned(),
@@ -165,15 +312,412 @@ impl Default for MyApp {
             menu_click_pos: None,
             current_file: None,
             is_dark_mode: false,
-            status_message: "就绪".to_owned(),
+            status_message: tr("status.ready").to_owned(),
             status_message_time: 0.0,
             explorer_tabs: Vec::new(),
             active_explorer_tab: None,
+            editor_tabs: Vec::new(),
+            active_editor_tab: None,
+            connections: Vec::new(),
+            active_connection: None,
+            panel_layout: PanelLayout::default(),
+            config: AppConfig::default(),
+            last_saved_config: None,
+            last_saved_session: None,
+            last_save_time: 0.0,
+            notifications: Vec::new(),
+            config_watcher: ConfigWatcher::new(),
+            appearance_dirty: true,
+            fonts_dirty: true,
+            history: Rc::new(QueryHistory::disabled()),
+            history_panel: HistoryPanel::default(),
+            themes: ThemeSet::default(),
+            missing_themes: HashSet::new(),
+            custom_themes: Vec::new(),
+            library_panel: LibraryPanel::default(),
+            placeholder_prompt: None,
+            param_prompt: None,
+            param_values: HashMap::new(),
+            copy_options: CopyOptions::default(),
+            copy_dialog: None,
+            clipboard: None,
+            column_stats: None,
+            cell_viewer: None,
+            result_diff: None,
+            schema_compare: None,
+            table_designer: None,
+            close_prompt: false,
+            close_confirmed: false,
+            settings: SettingsState::default(),
         }
     }
 }
 
 impl MyApp {
+    /// 使用给定配置创建应用，并恢复上次的工作区会话
+    ///
+    /// `load_error` 为启动时加载配置失败的原因，以通知报告给用户。
+    pub fn with_config(config: AppConfig, load_error: Option<ConfigLoadError>) -> Self {
+        let mut app = Self {
+            is_dark_mode: config.is_dark_mode,
+            last_saved_config: Some(config.clone()),
+            config,
+            ..Self::default()
+        };
+
+        let issues = app.config.validate();
+        if !issues.is_empty() {
+            app.config = app.config.sanitized();
+        }
+        crate::i18n::set_language(&app.config.language);
+        app.status_message = tr("status.ready").to_owned();
+        if let Some(error) = load_error {
+            app.report_config_load_error(&error);
+        }
+        if !issues.is_empty() {
+            app.report_config_issues(&issues);
+        }
+        app.apply_keybindings();
+
+        match QueryHistory::open_default() {
+            Ok(history) => app.history = Rc::new(history),
+            Err(e) => app.notify(
+                NotificationLevel::Warning,
+                tr("notify.history_unavailable"),
+                &e.to_string(),
+            ),
+        }
+
+        let session = SessionState::load();
+        app.restore_session(&session);
+        app.last_saved_session = Some(session);
+        app
+    }
+
+    /// 检查配置文件是否被外部修改，并热应用新配置
+    pub fn check_config_reload(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        match self.config_watcher.poll(now) {
+            Some(ConfigReload::Loaded(config, issues)) => {
+                if !issues.is_empty() {
+                    self.report_config_issues(&issues);
+                }
+                self.apply_config(config.sanitized());
+                // 重新加载的配置与文件一致，不算作需要自动保存的修改，
+                // 否则会把用户手动编辑的内容改写成规范化后的样子
+                self.last_saved_config = Some(self.config.clone());
+                self.set_status_message(tr("status.config_reloaded").to_owned());
+            }
+            Some(ConfigReload::Failed(error)) => {
+                self.notify(
+                    NotificationLevel::Error,
+                    tr("notify.config_parse_failed"),
+                    &tr_args("notify.config_keep_current", &[("error", &error)]),
+                );
+            }
+            None => {}
+        }
+    }
+
+    /// 报告启动时无法读取或解析的配置文件
+    fn report_config_load_error(&mut self, error: &ConfigLoadError) {
+        let mut message = tr_args("notify.config_use_default", &[("error", &error.error)]);
+        match &error.backup {
+            Some(Ok(path)) => {
+                message.push('\n');
+                message.push_str(&tr_args(
+                    "notify.config_backed_up",
+                    &[("path", &path.display())],
+                ));
+            }
+            Some(Err(e)) => {
+                message.push('\n');
+                message.push_str(&tr_args("notify.config_backup_failed", &[("error", e)]));
+            }
+            None => {}
+        }
+        self.notify(
+            NotificationLevel::Error,
+            tr("notify.config_load_failed"),
+            &message,
+        );
+    }
+
+    /// 以通知形式报告配置校验问题
+    fn report_config_issues(&mut self, issues: &[crate::config::ConfigIssue]) {
+        let message = issues
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.notify(
+            NotificationLevel::Warning,
+            tr("notify.config_invalid"),
+            &message,
+        );
+    }
+
+    /// 应用新配置（保留当前窗口状态）
+    pub fn apply_config(&mut self, mut config: AppConfig) {
+        config.window_width = self.config.window_width;
+        config.window_height = self.config.window_height;
+        config.is_maximized = self.config.is_maximized;
+
+        if config.ui_font != self.config.ui_font || config.mono_font != self.config.mono_font {
+            self.fonts_dirty = true;
+        }
+        self.is_dark_mode = config.is_dark_mode;
+        crate::i18n::set_language(&config.language);
+        self.config = config;
+        for connection in &mut self.connections {
+            if let Err(e) = connection
+                .manager
+                .set_profiling(self.config.connections.profiling)
+            {
+                eprintln!("设置性能分析失败: {}", e);
+            }
+        }
+        self.apply_keybindings();
+        self.appearance_dirty = true;
+    }
+
+    /// 根据配置更新菜单快捷键
+    fn apply_keybindings(&mut self) {
+        for items in self.menu_data.values_mut() {
+            for item in items.iter_mut() {
+                item.shortcut = self
+                    .config
+                    .keybindings
+                    .get(&item.action)
+                    .cloned()
+                    .or_else(|| item.default_shortcut.clone());
+            }
+        }
+    }
+
+    /// 处理快捷键
+    pub fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        let bindings: Vec<(String, String)> = self
+            .menu_data
+            .values()
+            .flatten()
+            .filter(|item| item.enabled)
+            .filter_map(|item| item.shortcut.clone().map(|s| (item.action.clone(), s)))
+            .collect();
+
+        let action = crate::keymap::triggered_action(
+            ctx,
+            bindings.iter().map(|(a, s)| (a.as_str(), s.as_str())),
+        );
+        if let Some(action) = action {
+            self.handle_menu_action(&action);
+        }
+    }
+
+    /// 应用字体、主题和字体大小
+    pub fn apply_appearance(&mut self, ctx: &egui::Context) {
+        if self.fonts_dirty {
+            self.fonts_dirty = false;
+            crate::fonts::setup_fonts(ctx, &self.config.ui_font, &self.config.mono_font);
+        }
+
+        // 跟随系统时由 egui 切换主题，这里只记录当前的明暗
+        if self.config.theme.follow_system {
+            self.is_dark_mode = ctx.theme() == egui::Theme::Dark;
+        }
+
+        if !self.appearance_dirty {
+            return;
+        }
+        self.appearance_dirty = false;
+
+        self.reload_themes();
+        let preference = if self.config.theme.follow_system {
+            egui::ThemePreference::System
+        } else if self.is_dark_mode {
+            egui::ThemePreference::Dark
+        } else {
+            egui::ThemePreference::Light
+        };
+        self.themes.apply(ctx, preference);
+
+        let size = self.config.font_size;
+        ctx.all_styles_mut(|style| {
+            for (text_style, font_id) in style.text_styles.iter_mut() {
+                font_id.size = match text_style {
+                    egui::TextStyle::Heading => size * 1.4,
+                    egui::TextStyle::Small => size * 0.75,
+                    _ => size,
+                };
+            }
+        });
+    }
+
+    /// 重新加载自定义主题，并按配置选择浅色和深色主题
+    pub fn reload_themes(&mut self) {
+        let (custom_themes, errors) = theme::load_custom_themes();
+        let (themes, missing) = ThemeSet::resolve(&self.config.theme, &custom_themes);
+
+        for error in errors {
+            self.notify(
+                NotificationLevel::Warning,
+                tr("notify.theme_load_failed"),
+                &error,
+            );
+        }
+        // 每次刷新外观都会重新解析主题，同一个主题只提示一次
+        for name in missing {
+            if self.missing_themes.insert(name.clone()) {
+                let title = tr_args("notify.theme_not_found", &[("name", &name)]);
+                self.notify(NotificationLevel::Warning, &title, "");
+            }
+        }
+
+        self.themes = themes;
+        self.custom_themes = custom_themes;
+    }
+
+    /// 添加通知
+    pub fn notify(&mut self, level: NotificationLevel, title: &str, message: &str) {
+        self.notifications.push(Notification {
+            level,
+            title: title.to_owned(),
+            message: message.to_owned(),
+            created_at: None,
+        });
+    }
+
+    /// 从会话快照恢复工作区
+    fn restore_session(&mut self, session: &SessionState) {
+        for root in &session.explorer_roots {
+            if std::path::Path::new(root).is_dir() {
+                self.add_explorer_tab(root);
+            }
+        }
+        if let Some(index) = session.active_explorer_tab
+            && index < self.explorer_tabs.len()
+        {
+            self.active_explorer_tab = Some(index);
+            for (i, tab) in self.explorer_tabs.iter_mut().enumerate() {
+                tab.is_active = i == index;
+            }
+        }
+
+        let mut failed = Vec::new();
+        if self.config.connections.restore_on_startup {
+            for path in &session.connections {
+                if !self.open_connection(path) {
+                    failed.push(path.clone());
+                }
+            }
+        }
+        self.active_connection = session
+            .active_connection
+            .filter(|&i| i < self.connections.len())
+            .or(if self.connections.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+
+        self.editor_tabs = session
+            .editor_tabs
+            .iter()
+            .map(|tab| EditorTab {
+                title: tab.title.clone(),
+                file_path: tab.file_path.clone(),
+                content: tab.content.clone(),
+                is_dirty: tab.is_dirty,
+                connection: tab.connection.clone(),
+                ..EditorTab::default()
+            })
+            .collect();
+        self.active_editor_tab = session
+            .active_editor_tab
+            .filter(|&i| i < self.editor_tabs.len());
+
+        self.panel_layout = session.panel_layout.clone();
+
+        if !failed.is_empty() {
+            self.set_status_message(tr_args(
+                "status.connections_restore_failed",
+                &[("paths", &failed.join(", "))],
+            ));
+        } else if !self.editor_tabs.is_empty() || !self.explorer_tabs.is_empty() {
+            self.set_status_message(tr("status.workspace_restored").to_owned());
+        }
+    }
+
+    /// 生成当前工作区的会话快照
+    pub fn session_snapshot(&self) -> SessionState {
+        SessionState {
+            explorer_roots: self.explorer_tabs.iter().map(|t| t.path.clone()).collect(),
+            active_explorer_tab: self.active_explorer_tab,
+            editor_tabs: self
+                .editor_tabs
+                .iter()
+                .map(|tab| EditorTabState {
+                    title: tab.title.clone(),
+                    file_path: tab.file_path.clone(),
+                    content: tab.content.clone(),
+                    is_dirty: tab.is_dirty,
+                    connection: tab.connection.clone(),
+                })
+                .collect(),
+            active_editor_tab: self.active_editor_tab,
+            connections: self.connections.iter().map(|c| c.path.clone()).collect(),
+            active_connection: self.active_connection,
+            panel_layout: self.panel_layout.clone(),
+        }
+    }
+
+    /// 记录窗口尺寸和最大化状态
+    pub fn update_window_state(&mut self, ctx: &egui::Context) {
+        let (maximized, inner_rect) =
+            ctx.input(|i| (i.viewport().maximized, i.viewport().inner_rect));
+
+        if let Some(maximized) = maximized {
+            self.config.is_maximized = maximized;
+        }
+        // 最大化时保留还原后的尺寸
+        if !self.config.is_maximized
+            && let Some(rect) = inner_rect
+        {
+            self.config.set_window_size(rect.width(), rect.height());
+        }
+        self.config.is_dark_mode = self.is_dark_mode;
+    }
+
+    /// 定期自动保存工作区，防止崩溃时丢失未保存的内容
+    pub fn auto_save_workspace(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        if now - self.last_save_time >= self.config.editor.autosave_interval_secs as f64 {
+            self.last_save_time = now;
+            self.save_workspace();
+        }
+    }
+
+    /// 保存配置和会话（仅在内容变化时写入磁盘）
+    pub fn save_workspace(&mut self) {
+        if self.last_saved_config.as_ref() != Some(&self.config) {
+            match self.config.save() {
+                Ok(()) => {
+                    self.config_watcher.mark_saved();
+                    self.last_saved_config = Some(self.config.clone());
+                }
+                Err(e) => eprintln!("保存配置失败: {}", e),
+            }
+        }
+
+        let session = self.session_snapshot();
+        if self.last_saved_session.as_ref() != Some(&session) {
+            match session.save() {
+                Ok(()) => self.last_saved_session = Some(session),
+                Err(e) => eprintln!("保存会话失败: {}", e),
+            }
+        }
+    }
+
     /// 处理菜单项点击
     pub fn handle_menu_action(&mut self, action: &str) {
         println!("handle_menu_action 被调用，动作: {}", action);
@@ -196,30 +740,66 @@ impl MyApp {
             "redo" => self.redo(),
             "cut" => self.cut(),
             "copy" => self.copy(),
+            "copy_tsv" => self.copy_results(CopyFormat::Tsv),
+            "copy_csv" => self.copy_results(CopyFormat::Csv),
+            "copy_markdown" => self.copy_results(CopyFormat::Markdown),
+            "copy_json" => self.copy_results(CopyFormat::Json),
+            "copy_insert" => self.copy_results(CopyFormat::Insert),
+            "copy_in_list" => self.copy_results(CopyFormat::InList),
+            "copy_advanced" => self.open_copy_dialog(),
             "paste" => self.paste(),
             "delete" => self.delete(),
             "generate_uuid" => self.generate_uuid(),
             "goto_line" => self.goto_line(),
             "find" => self.find(),
             "new_query" => self.new_query(),
+            "run_query" => self.run_query(),
+            "explain" => self.explain_query(false),
+            "explain_analyze" => self.explain_query(true),
+            "save_to_library" => self.save_to_library(),
+            "reload_library" => LibraryPanel::reload(self),
             "connect_db" => self.connect_database(),
+            "toggle_auto_commit" => self.toggle_auto_commit(),
+            "commit" => self.end_transaction(true),
+            "rollback" => self.end_transaction(false),
+            "apply_edits" => self.apply_edits(),
+            "push_down_filter" => self.push_down_filter(),
+            "column_stats" => self.show_result_stats(),
+            "view_cell" => self.open_cell_viewer(),
+            "compare_results" => self.open_result_diff(),
+            "compare_schemas" => self.open_schema_compare(),
+            "export_chart_png" => self.export_chart_png(),
+            "export_chart_svg" => self.export_chart_svg(),
             "new_window" => self.new_window(),
             "about" => self.show_about(),
             "exit" => self.exit(),
             "toggle_dark_mode" => self.toggle_dark_mode(),
+            "open_settings" => self.settings.open(&self.config),
+            "toggle_sidebar" => {
+                self.panel_layout.sidebar_visible = !self.panel_layout.sidebar_visible
+            }
+            "toggle_status_bar" => {
+                self.panel_layout.status_bar_visible = !self.panel_layout.status_bar_visible
+            }
+            "toggle_history" => {
+                self.panel_layout.history_visible = !self.panel_layout.history_visible
+            }
             _ => println!("执行动作: {}", action),
         }
     }
 
     /// 打开文件夹
     fn open_folder(&mut self) {
-        self.set_status_message("正在打开文件夹...".to_owned());
+        self.set_status_message(tr("status.opening_folder").to_owned());
 
         // 使用Windows文件选择对话框
-        let folder_path = match rfd::FileDialog::new().set_title("选择文件夹").pick_folder() {
+        let folder_path = match rfd::FileDialog::new()
+            .set_title(tr("dialog.pick_folder"))
+            .pick_folder()
+        {
             Some(path) => path.to_string_lossy().to_string(),
             None => {
-                self.set_status_message("用户取消了文件夹选择".to_owned());
+                self.set_status_message(tr("status.folder_cancelled").to_owned());
                 return;
             }
         };
@@ -234,12 +814,12 @@ impl MyApp {
     fn add_explorer_tab(&mut self, folder_path: &str) {
         let folder_name = match std::path::Path::new(folder_path).file_name() {
             Some(name) => name.to_string_lossy().to_string(),
-            None => "文件夹".to_string(),
+            None => tr("explorer.default_folder").to_string(),
         };
 
         // 创建新的资源管理器标签页
         let new_tab = ExplorerTab {
-            name: "文件资源管理器".to_string(), // 固定标签名
+            name: tr("explorer.tab_name").to_string(), // 固定标签名
             path: folder_path.to_string(),
             file_tree: Vec::new(),
             is_active: true,
@@ -263,7 +843,7 @@ impl MyApp {
             }
         }
 
-        self.set_status_message(format!("已打开文件夹: {}", folder_name));
+        self.set_status_message(tr_args("status.folder_opened", &[("name", &folder_name)]));
     }
 
     /// 加载文件夹树状结构
@@ -272,46 +852,44 @@ impl MyApp {
 
         // 读取真实的文件系统目录结构
         if let Ok(entries) = std::fs::read_dir(folder_path) {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    let is_directory = path.is_dir();
-
-                    // 获取文件名
-                    let name = match path.file_name() {
-                        Some(os_str) => os_str.to_string_lossy().to_string(),
-                        None => continue,
-                    };
-
-                    // 跳过隐藏文件（以 . 开头的文件/文件夹）
-                    if name.starts_with('.') {
-                        continue;
-                    }
-
-                    // 创建文件项
-                    let mut file_item = FileItem {
-                        name,
-                        path: path.to_string_lossy().to_string(),
-                        is_directory,
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let is_directory = path.is_dir();
+
+                // 获取文件名
+                let name = match path.file_name() {
+                    Some(os_str) => os_str.to_string_lossy().to_string(),
+                    None => continue,
+                };
+
+                // 跳过隐藏文件（以 . 开头的文件/文件夹）
+                if name.starts_with('.') && !self.config.files.show_hidden_files {
+                    continue;
+                }
+
+                // 创建文件项
+                let mut file_item = FileItem {
+                    name,
+                    path: path.to_string_lossy().to_string(),
+                    is_directory,
+                    is_expanded: false,
+                    children: Vec::new(),
+                };
+
+                // 如果是目录，递归加载子项（但不展开，节省性能）
+                if is_directory {
+                    // 这里可以添加一个简单的子项来标记是否有内容
+                    // 实际展开时再加载详细内容
+                    file_item.children.push(FileItem {
+                        name: "...".to_string(),
+                        path: "".to_string(),
+                        is_directory: false,
                         is_expanded: false,
                         children: Vec::new(),
-                    };
-
-                    // 如果是目录，递归加载子项（但不展开，节省性能）
-                    if is_directory {
-                        // 这里可以添加一个简单的子项来标记是否有内容
-                        // 实际展开时再加载详细内容
-                        file_item.children.push(FileItem {
-                            name: "...".to_string(),
-                            path: "".to_string(),
-                            is_directory: false,
-                            is_expanded: false,
-                            children: Vec::new(),
-                        });
-                    }
-
-                    file_tree.push(file_item);
+                    });
                 }
+
+                file_tree.push(file_item);
             }
         }
 
@@ -330,177 +908,1024 @@ impl MyApp {
     /// 新建文件
     fn new_file(&mut self) {
         self.current_file = None;
-        self.name = "新文件".to_owned();
-        self.set_status_message("已创建新文件".to_owned());
+        self.name = tr("editor.untitled").to_owned();
+        self.set_status_message(tr("status.file_created").to_owned());
         println!("创建新文件");
     }
 
     /// 打开文件
     fn open_file(&mut self) {
-        // 这里会调用文件对话框
-        self.set_status_message("正在打开文件对话框...".to_owned());
-        println!("打开文件对话框");
+        let path = match rfd::FileDialog::new()
+            .set_title(tr("dialog.open_file"))
+            .add_filter("SQL", &["sql"])
+            .pick_file()
+        {
+            Some(path) => path.to_string_lossy().to_string(),
+            None => {
+                self.set_status_message(tr("status.file_cancelled").to_owned());
+                return;
+            }
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                let title =
+                    crate::utils::FileUtils::get_filename(&path).unwrap_or_else(|| path.clone());
+                self.add_editor_tab(EditorTab {
+                    title,
+                    file_path: Some(path.clone()),
+                    content,
+                    is_dirty: false,
+                    connection: self.active_connection_path(),
+                    ..EditorTab::default()
+                });
+                self.current_file = Some(path.clone());
+                self.set_status_message(tr_args("status.file_opened", &[("path", &path)]));
+            }
+            Err(e) => self.set_status_message(tr_args("status.file_open_failed", &[("error", &e)])),
+        }
     }
 
     /// 保存文件
     fn save_file(&mut self) {
-        self.set_status_message("正在保存文件...".to_owned());
-        println!("保存文件");
+        let Some(index) = self.active_editor_tab else {
+            self.set_status_message(tr("status.no_open_file").to_owned());
+            return;
+        };
+
+        match self.editor_tabs[index].file_path.clone() {
+            Some(path) => self.write_editor_tab(index, &path),
+            None => self.save_as(),
+        }
+    }
+
+    /// 将编辑器标签页内容写入文件
+    fn write_editor_tab(&mut self, index: usize, path: &str) {
+        let tab = &mut self.editor_tabs[index];
+        match crate::utils::FileUtils::write_file(path, &tab.content) {
+            Ok(()) => {
+                tab.file_path = Some(path.to_owned());
+                tab.is_dirty = false;
+                if let Some(name) = crate::utils::FileUtils::get_filename(path) {
+                    tab.title = name;
+                }
+                self.current_file = Some(path.to_owned());
+                self.config.add_recent_file(path);
+                self.set_status_message(tr_args("status.file_saved", &[("path", &path)]));
+            }
+            Err(e) => self.set_status_message(tr_args("status.file_save_failed", &[("error", &e)])),
+        }
     }
 
     /// 退出应用
     fn exit(&mut self) {
-        self.set_status_message("正在退出应用...".to_owned());
+        self.set_status_message(tr("status.exiting").to_owned());
         println!("退出应用");
         // 在实际应用中，这里会触发应用关闭
     }
 
     /// 切换深色模式
     pub fn toggle_dark_mode(&mut self) {
+        // 手动切换后不再跟随系统，并提示用户
+        let followed_system = std::mem::take(&mut self.config.theme.follow_system);
         self.is_dark_mode = !self.is_dark_mode;
-        let mode = if self.is_dark_mode {
-            "深色"
-        } else {
-            "浅色"
+        self.appearance_dirty = true;
+        let message = match (self.is_dark_mode, followed_system) {
+            (true, false) => tr("status.dark_mode_on"),
+            (false, false) => tr("status.dark_mode_off"),
+            (true, true) => tr("status.dark_mode_on_follow_off"),
+            (false, true) => tr("status.dark_mode_off_follow_off"),
         };
-        self.set_status_message(format!("已切换到{}模式", mode));
+        self.set_status_message(message.to_owned());
         println!("切换深色模式: {}", self.is_dark_mode);
     }
 
     /// 另存为
     fn save_as(&mut self) {
-        self.set_status_message("正在打开另存为对话框...".to_owned());
-        println!("另存为文件");
+        let Some(index) = self.active_editor_tab else {
+            self.set_status_message(tr("status.no_open_file").to_owned());
+            return;
+        };
+
+        match rfd::FileDialog::new()
+            .set_title(tr("dialog.save_as"))
+            .add_filter("SQL", &["sql"])
+            .set_file_name(format!("{}.sql", self.editor_tabs[index].title))
+            .save_file()
+        {
+            Some(path) => self.write_editor_tab(index, &path.to_string_lossy()),
+            None => self.set_status_message(tr("status.save_cancelled").to_owned()),
+        }
     }
 
     /// 保存全部
     fn save_all(&mut self) {
-        self.set_status_message("正在保存所有打开的文件...".to_owned());
-        println!("保存全部文件");
+        let paths: Vec<(usize, String)> = self
+            .editor_tabs
+            .iter()
+            .enumerate()
+            .filter(|(_, tab)| tab.is_dirty)
+            .filter_map(|(i, tab)| tab.file_path.clone().map(|p| (i, p)))
+            .collect();
+
+        for (index, path) in &paths {
+            self.write_editor_tab(*index, path);
+        }
+        self.set_status_message(tr_args("status.saved_files", &[("count", &paths.len())]));
     }
 
     /// 关闭文件
     fn close_file(&mut self) {
-        if self.current_file.is_some() {
-            self.set_status_message("正在关闭当前文件...".to_owned());
+        if let Some(index) = self.active_editor_tab {
+            self.close_editor_tab(index);
+        } else if self.current_file.is_some() {
+            self.set_status_message(tr("status.closing_file").to_owned());
             self.current_file = None;
         } else {
-            self.set_status_message("没有打开的文件".to_owned());
+            self.set_status_message(tr("status.no_open_file").to_owned());
         }
         println!("关闭文件");
     }
 
+    /// 添加编辑器标签页并设为活动状态
+    pub fn add_editor_tab(&mut self, tab: EditorTab) {
+        self.editor_tabs.push(tab);
+        self.active_editor_tab = Some(self.editor_tabs.len() - 1);
+    }
+
+    /// 关闭编辑器标签页
+    pub fn close_editor_tab(&mut self, index: usize) {
+        if index >= self.editor_tabs.len() {
+            return;
+        }
+
+        let tab = self.editor_tabs.remove(index);
+        self.active_editor_tab = match self.active_editor_tab {
+            _ if self.editor_tabs.is_empty() => None,
+            Some(active) if active > index => Some(active - 1),
+            Some(active) => Some(active.min(self.editor_tabs.len() - 1)),
+            None => None,
+        };
+        if tab.file_path.is_some() && tab.file_path == self.current_file {
+            self.current_file = None;
+        }
+        self.set_status_message(tr_args("status.tab_closed", &[("title", &tab.title)]));
+    }
+
     /// 打印文件
     fn print_file(&mut self) {
-        self.set_status_message("正在准备打印...".to_owned());
+        self.set_status_message(tr("status.printing").to_owned());
         println!("打印文件");
     }
 
     /// 重命名文件
     fn rename_file(&mut self) {
-        self.set_status_message("正在重命名文件...".to_owned());
+        self.set_status_message(tr("status.renaming").to_owned());
         println!("重命名文件");
     }
 
     /// 刷新文件
     fn refresh_file(&mut self) {
-        self.set_status_message("正在刷新文件内容...".to_owned());
+        self.set_status_message(tr("status.refreshing").to_owned());
         println!("刷新文件");
     }
 
     /// 导入文件
     fn import_file(&mut self) {
-        self.set_status_message("正在导入文件...".to_owned());
+        self.set_status_message(tr("status.importing").to_owned());
         println!("导入文件");
     }
 
     /// 导出文件
     fn export_file(&mut self) {
-        self.set_status_message("正在导出文件...".to_owned());
+        self.set_status_message(tr("status.exporting").to_owned());
         println!("导出文件");
     }
 
     /// 显示文件属性
     fn show_file_property(&mut self) {
-        self.set_status_message("正在显示文件属性...".to_owned());
+        self.set_status_message(tr("status.showing_property").to_owned());
         println!("显示文件属性");
     }
 
     /// 撤销操作
     fn undo(&mut self) {
-        self.set_status_message("正在撤销上一步操作...".to_owned());
+        self.set_status_message(tr("status.undoing").to_owned());
         println!("撤销操作");
     }
 
     /// 重做操作
     fn redo(&mut self) {
-        self.set_status_message("正在重做下一步操作...".to_owned());
+        self.set_status_message(tr("status.redoing").to_owned());
         println!("重做操作");
     }
 
     /// 剪切
     fn cut(&mut self) {
-        self.set_status_message("正在剪切选中内容...".to_owned());
+        self.set_status_message(tr("status.cutting").to_owned());
         println!("剪切操作");
     }
 
-    /// 复制
+    /// 复制结果表格中选中的单元格（TSV 格式）
     fn copy(&mut self) {
-        self.set_status_message("正在复制选中内容...".to_owned());
-        println!("复制操作");
+        self.copy_results(CopyFormat::Tsv);
+    }
+
+    /// 按指定格式复制选中的单元格，其它选项沿用上次的设置
+    fn copy_results(&mut self, format: CopyFormat) {
+        let options = CopyOptions {
+            format,
+            ..self.copy_options.clone()
+        };
+        self.copy_with_options(&options);
+    }
+
+    /// 按选项复制选中的单元格
+    pub fn copy_with_options(&mut self, options: &CopyOptions) {
+        let Some(data) = self.selected_data() else {
+            self.set_status_message(tr("status.nothing_selected").to_owned());
+            return;
+        };
+        let rows = data.rows.len();
+        self.clipboard = Some(crate::export::format(&data, options));
+        self.set_status_message(tr_args("status.copied", &[("count", &rows)]));
+    }
+
+    /// 打开高级复制对话框
+    fn open_copy_dialog(&mut self) {
+        self.copy_dialog = Some(CopyDialog {
+            options: self.copy_options.clone(),
+        });
+    }
+
+    /// 当前标签页结果表格中选中的数据
+    pub fn selected_data(&self) -> Option<CopyData<'_>> {
+        let tab = self
+            .active_editor_tab
+            .and_then(|i| self.editor_tabs.get(i))?;
+        let result = tab.result.as_ref()?;
+        let selection = tab.grid.selection?;
+        let data = CopyData {
+            table: tab.edits.as_ref().map(|edits| edits.table.to_string()),
+            ..CopyData::new(
+                result,
+                tab.grid.selected_rows(result.rows.len()),
+                selection.column_range(result.columns.len()),
+            )
+        };
+        Some(data).filter(|data| !data.rows.is_empty())
     }
 
     /// 粘贴
     fn paste(&mut self) {
-        self.set_status_message("正在粘贴内容...".to_owned());
+        self.set_status_message(tr("status.pasting").to_owned());
         println!("粘贴操作");
     }
 
     /// 删除
     fn delete(&mut self) {
-        self.set_status_message("正在删除选中内容...".to_owned());
+        self.set_status_message(tr("status.deleting").to_owned());
         println!("删除操作");
     }
 
     /// 生成UUID
     fn generate_uuid(&mut self) {
-        self.set_status_message("正在生成UUID...".to_owned());
+        self.set_status_message(tr("status.generating_uuid").to_owned());
         println!("生成UUID");
     }
 
     /// 跳转到行
     fn goto_line(&mut self) {
-        self.set_status_message("正在打开跳转到行对话框...".to_owned());
+        self.set_status_message(tr("status.goto_line").to_owned());
         println!("跳转到行");
     }
 
     /// 查找
     fn find(&mut self) {
-        self.set_status_message("正在打开查找对话框...".to_owned());
+        self.set_status_message(tr("status.finding").to_owned());
         println!("查找操作");
     }
 
     /// 新建查询
     fn new_query(&mut self) {
-        self.set_status_message("正在创建新的SQL查询...".to_owned());
+        let title = tr_args(
+            "editor.query_title",
+            &[("index", &(self.editor_tabs.len() + 1))],
+        );
+        self.add_editor_tab(EditorTab {
+            title: title.clone(),
+            connection: self.active_connection_path(),
+            ..EditorTab::default()
+        });
+        self.set_status_message(tr_args("status.tab_created", &[("title", &title)]));
         println!("新建SQL查询");
     }
 
     /// 连接数据库
     fn connect_database(&mut self) {
-        self.set_status_message("正在打开数据库连接对话框...".to_owned());
-        println!("连接数据库");
+        let path = match rfd::FileDialog::new()
+            .set_title(tr("dialog.pick_database"))
+            .add_filter("DuckDB", &["duckdb", "db"])
+            .pick_file()
+        {
+            Some(path) => path.to_string_lossy().to_string(),
+            None => {
+                self.set_status_message(tr("status.db_cancelled").to_owned());
+                return;
+            }
+        };
+
+        if self.open_connection(&path) {
+            self.active_connection = Some(self.connections.len() - 1);
+            self.set_status_message(tr_args("status.db_connected", &[("path", &path)]));
+        } else {
+            self.set_status_message(tr_args("status.db_connect_failed", &[("path", &path)]));
+        }
+    }
+
+    /// 打开数据库连接，已连接的路径不会重复打开
+    fn open_connection(&mut self, path: &str) -> bool {
+        if self.connections.iter().any(|c| c.path == path) {
+            return true;
+        }
+
+        let mut manager = DatabaseManager::new();
+        manager.set_history(Rc::clone(&self.history));
+        if manager
+            .connect_with_access(path, self.config.connections.read_only)
+            .is_err()
+        {
+            return false;
+        }
+        if let Err(e) = manager.set_profiling(self.config.connections.profiling) {
+            eprintln!("设置性能分析失败: {}", e);
+        }
+
+        let name = crate::utils::FileUtils::get_filename(path).unwrap_or_else(|| path.to_owned());
+        self.connections.push(DbConnection {
+            name,
+            path: path.to_owned(),
+            manager,
+            objects: None,
+        });
+        true
+    }
+
+    /// 执行当前编辑器标签页中的 SQL
+    ///
+    /// SQL 中有 `${name}` 占位符时先弹出对话框填写。
+    pub fn run_query(&mut self) {
+        let Some(index) = self.active_editor_tab else {
+            self.set_status_message(tr("status.no_open_file").to_owned());
+            return;
+        };
+
+        let sql = self.editor_tabs[index].content.clone();
+        let last_values = &self.library_panel.last_values;
+        match PlaceholderPrompt::new(&sql, PromptAction::Run(index), last_values) {
+            Some(prompt) => self.placeholder_prompt = Some(prompt),
+            None => self.execute_tab_sql(index, &sql),
+        }
+    }
+
+    /// 在指定编辑器标签页的连接上执行 SQL
+    ///
+    /// 语句中有绑定参数（`?`、`$name`）时先弹出对话框输入参数值。
+    pub fn execute_tab_sql(&mut self, index: usize, sql: &str) {
+        let statements = crate::highlight::split_statements(sql);
+        if statements.is_empty() {
+            self.set_status_message(tr("status.no_statement").to_owned());
+            return;
+        }
+
+        match ParamPrompt::new(index, &statements, &self.param_values) {
+            Some(prompt) => self.param_prompt = Some(prompt),
+            None => {
+                let statements = statements
+                    .into_iter()
+                    .map(|sql| (sql.to_string(), QueryParams::None))
+                    .collect();
+                self.run_tab_statements(index, statements);
+            }
+        }
+    }
+
+    /// 依次执行语句，遇到错误时停止；标签页显示最后一条语句的结果
+    pub fn run_tab_statements(&mut self, index: usize, statements: Vec<(String, QueryParams)>) {
+        let Some(tab) = self.editor_tabs.get_mut(index) else {
+            return;
+        };
+
+        // 标签页没有关联连接时使用当前选中的连接
+        if tab.connection.is_none() {
+            tab.connection = self
+                .active_connection
+                .and_then(|i| self.connections.get(i))
+                .map(|c| c.path.clone());
+        }
+        let Some(connection) = tab
+            .connection
+            .as_ref()
+            .and_then(|path| self.connections.iter().find(|c| &c.path == path))
+        else {
+            self.set_status_message(tr("status.no_connection").to_owned());
+            return;
+        };
+
+        let started = std::time::Instant::now();
+        let mut result = None;
+        let mut error = None;
+        for (statement, params) in &statements {
+            let max_rows = self.config.grid.max_rows;
+            match connection
+                .manager
+                .run_with_params(statement, params, max_rows)
+            {
+                Ok(r) => result = Some(r),
+                Err(e) => {
+                    error = Some(e.to_string());
+                    break;
+                }
+            }
+        }
+        let elapsed_ms = format!("{:.1}", started.elapsed().as_secs_f64() * 1000.0);
+
+        // 单表查询的结果可以直接编辑
+        let source = statements.last().cloned().filter(|_| error.is_none());
+        let edits = match (&result, &source) {
+            (Some(result), Some((sql, _))) => DataEditor::detect(&connection.manager, sql, result),
+            _ => None,
+        };
+
+        let message = match &error {
+            Some(e) => tr_args("status.query_failed", &[("error", e)]),
+            None => tr_args(
+                "status.query_done",
+                &[("count", &statements.len()), ("ms", &elapsed_ms)],
+            ),
+        };
+        tab.result = result;
+        tab.error = error;
+        tab.edits = edits;
+        tab.source = source;
+        tab.grid = GridState::default();
+        tab.chart.invalidate();
+        tab.record.pinned = None;
+        // 重新执行后图表保持显示，执行计划已经过时
+        if tab.view == ResultView::Plan {
+            tab.view = ResultView::Grid;
+        }
+        // 语句可能创建或删除了表
+        let path = tab.connection.clone();
+        for connection in &mut self.connections {
+            if Some(&connection.path) == path.as_ref() {
+                connection.objects = None;
+            }
+        }
+        self.history_panel.dirty = true;
+        self.set_status_message(message);
+    }
+
+    /// 获取当前编辑器中最后一条语句的执行计划
+    ///
+    /// `analyze` 为 true 时语句会被实际执行，计划中包含每个算子的耗时和实际行数。
+    pub fn explain_query(&mut self, analyze: bool) {
+        let Some(index) = self.active_editor_tab else {
+            self.set_status_message(tr("status.no_open_file").to_owned());
+            return;
+        };
+        let Some(connection) = self
+            .current_connection_index()
+            .and_then(|i| self.connections.get(i))
+        else {
+            self.set_status_message(tr("status.no_connection").to_owned());
+            return;
+        };
+
+        let tab = &mut self.editor_tabs[index];
+        let Some(statement) = crate::highlight::split_statements(&tab.content).pop() else {
+            self.set_status_message(tr("status.no_statement").to_owned());
+            return;
+        };
+
+        let result = connection
+            .manager
+            .explain(statement, analyze)
+            .map_err(|e| e.to_string())
+            .and_then(|json| ExplainPlan::parse(&json));
+        let message = match result {
+            Ok(plan) => {
+                tab.plan = Some(plan);
+                tab.view = ResultView::Plan;
+                tab.error = None;
+                tr("status.explain_done").to_owned()
+            }
+            Err(e) => {
+                let message = tr_args("status.query_failed", &[("error", &e)]);
+                tab.error = Some(e);
+                message
+            }
+        };
+        if tab.connection.is_none() {
+            tab.connection = Some(connection.path.clone());
+        }
+        self.history_panel.dirty = true;
+        self.set_status_message(message);
+    }
+
+    /// 当前编辑器标签页使用的连接，没有时为选中的连接
+    pub fn current_connection_index(&self) -> Option<usize> {
+        self.active_editor_tab
+            .and_then(|i| self.editor_tabs.get(i))
+            .and_then(|tab| tab.connection.as_ref())
+            .and_then(|path| self.connections.iter().position(|c| &c.path == path))
+            .or(self.active_connection)
+    }
+
+    /// 把结果表格中暂存的修改写入数据库，成功后重新执行查询
+    ///
+    /// 修改在一个事务中执行，失败时保留暂存的修改。已经在手动提交的事务中时
+    /// 沿用该事务，失败后不回滚，由用户决定提交还是回滚。
+    fn apply_edits(&mut self) {
+        let Some(index) = self.active_editor_tab else {
+            return;
+        };
+        let tab = &self.editor_tabs[index];
+        let (Some(edits), Some(result), Some(source)) = (&tab.edits, &tab.result, &tab.source)
+        else {
+            return;
+        };
+        let Some(connection) = tab
+            .connection
+            .as_ref()
+            .and_then(|path| self.connections.iter().find(|c| &c.path == path))
+        else {
+            self.set_status_message(tr("status.no_connection").to_owned());
+            return;
+        };
+
+        let statements = edits.statements(result);
+        let source = vec![source.clone()];
+        match connection.manager.execute_in_transaction(&statements) {
+            Ok(count) => {
+                self.run_tab_statements(index, source);
+                self.set_status_message(tr_args("status.edits_applied", &[("count", &count)]));
+            }
+            Err(e) => {
+                // 手动提交模式下不会回滚用户的事务，提示用户处理
+                let mut message = e.to_string();
+                if connection.manager.in_transaction() {
+                    message.push('\n');
+                    message.push_str(tr("notify.transaction_left_open"));
+                }
+                self.notify(
+                    NotificationLevel::Error,
+                    tr("notify.edits_failed"),
+                    &message,
+                );
+            }
+        }
+    }
+
+    /// 把结果表格的筛选条件包装为 WHERE 子句，在数据库中重新执行查询
+    ///
+    /// 结果被截断时客户端只能筛选已经读取的行，在数据库中筛选可以得到完整的结果。
+    fn push_down_filter(&mut self) {
+        let Some(index) = self.active_editor_tab else {
+            return;
+        };
+        let tab = &self.editor_tabs[index];
+        let (Some(result), Some((sql, params))) = (&tab.result, &tab.source) else {
+            return;
+        };
+        let Some(wrapped) = tab.grid.filter.wrap_query(sql, &result.columns) else {
+            return;
+        };
+        let statements = vec![(wrapped, params.clone())];
+        self.run_tab_statements(index, statements);
+    }
+
+    /// 在窗口中查看结果表格中选中的单元格
+    fn open_cell_viewer(&mut self) {
+        let Some(tab) = self.active_editor_tab.and_then(|i| self.editor_tabs.get(i)) else {
+            return;
+        };
+        let (Some(result), Some(selection)) = (&tab.result, tab.grid.selection) else {
+            return;
+        };
+        let (display, col) = selection.cursor;
+        let row = tab.grid.row_index(display, result.rows.len());
+        let source = tab.source.as_ref().map(|(sql, _)| sql.clone());
+        let editable = tab.edits.as_ref().is_some_and(|e| e.can_edit(row, col));
+        if let Some(viewer) = CellViewer::new(result, row, col, source, editable) {
+            self.cell_viewer = Some(viewer);
+        }
+    }
+
+    /// 打开数据对比窗口，两侧默认使用当前标签页的查询和连接
+    fn open_result_diff(&mut self) {
+        if self.result_diff.is_some() {
+            return;
+        }
+        let tab = self.active_editor_tab.and_then(|i| self.editor_tabs.get(i));
+        let sql = tab.map_or("", |tab| tab.content.as_str());
+        let connection = self
+            .current_connection_index()
+            .map(|i| self.connections[i].path.clone());
+        self.result_diff = Some(ResultDiff::new(sql, connection));
+    }
+
+    /// 打开结构对比窗口，两侧默认使用当前的连接
+    fn open_schema_compare(&mut self) {
+        if self.schema_compare.is_some() {
+            return;
+        }
+        let connection = self
+            .current_connection_index()
+            .map(|i| self.connections[i].path.clone());
+        self.schema_compare = Some(SchemaCompare::new(connection));
+    }
+
+    /// 暂存从文件载入的 BLOB 值，单元格不能编辑时返回 `false`
+    pub fn stage_cell_blob(&mut self, row: usize, col: usize, bytes: &[u8]) -> bool {
+        let Some(tab) = self
+            .active_editor_tab
+            .and_then(|i| self.editor_tabs.get_mut(i))
+        else {
+            return false;
+        };
+        let (Some(result), Some(editor)) = (&tab.result, &mut tab.edits) else {
+            return false;
+        };
+        if row >= result.rows.len() || !editor.can_edit(row, col) {
+            return false;
+        }
+        // DuckDB 把 `\xHH` 形式的文本转换为 BLOB
+        let text = format_value(&Value::Blob(bytes.to_vec()));
+        editor.set_cell(result, row, col, CellEdit::Text(text));
+        let size = bytes.len().to_string();
+        self.set_status_message(tr_args("status.blob_loaded", &[("size", &size)]));
+        true
+    }
+
+    /// 把当前标签页的图表导出为 PNG
+    ///
+    /// 图表在下一帧截图后保存，结果显示在图表工具栏中。
+    fn export_chart_png(&mut self) {
+        let Some(tab) = self
+            .active_editor_tab
+            .and_then(|i| self.editor_tabs.get_mut(i))
+        else {
+            return;
+        };
+        if let Some(path) = rfd::FileDialog::new()
+            .set_title(tr("dialog.export_chart"))
+            .add_filter("PNG", &["png"])
+            .set_file_name(format!("{}.png", tab.title))
+            .save_file()
+        {
+            tab.chart.png_export = Some(path);
+            tab.chart.message = None;
+        }
+    }
+
+    /// 把当前标签页的图表导出为 SVG
+    fn export_chart_svg(&mut self) {
+        let Some(tab) = self
+            .active_editor_tab
+            .and_then(|i| self.editor_tabs.get_mut(i))
+        else {
+            return;
+        };
+        let Some(result) = &tab.result else {
+            return;
+        };
+        let svg = match tab.chart.data(result) {
+            Ok(data) => data.to_svg(),
+            Err(key) => {
+                self.set_status_message(tr(key).to_owned());
+                return;
+            }
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr("dialog.export_chart"))
+            .add_filter("SVG", &["svg"])
+            .set_file_name(format!("{}.svg", tab.title))
+            .save_file()
+        else {
+            return;
+        };
+        let path = path.display().to_string();
+        tab.chart.message = Some(match std::fs::write(&path, svg) {
+            Ok(()) => tr_args("chart.exported", &[("path", &path)]),
+            Err(e) => tr_args("chart.export_failed", &[("error", &e)]),
+        });
+    }
+
+    /// 计算当前标签页结果中各列的统计信息
+    ///
+    /// 统计基于重新执行的完整查询，不受结果最大行数的限制。
+    fn show_result_stats(&mut self) {
+        let Some(tab) = self.active_editor_tab.and_then(|i| self.editor_tabs.get(i)) else {
+            return;
+        };
+        let Some((sql, params)) = &tab.source else {
+            return;
+        };
+        let Some(index) = tab
+            .connection
+            .as_ref()
+            .and_then(|path| self.connections.iter().position(|c| &c.path == path))
+        else {
+            self.set_status_message(tr("status.no_connection").to_owned());
+            return;
+        };
+        let relation = StatsReport::subquery(sql);
+        let (title, params) = (tab.title.clone(), params.clone());
+        self.compute_stats(index, title, &relation, &params);
+    }
+
+    /// 计算表中各列的统计信息
+    pub fn show_table_stats(&mut self, connection: usize, table: &TableName) {
+        let relation = table.to_string();
+        let title = table.name.clone();
+        self.compute_stats(connection, title, &relation, &QueryParams::None);
+    }
+
+    fn compute_stats(
+        &mut self,
+        connection: usize,
+        title: String,
+        relation: &str,
+        params: &QueryParams,
+    ) {
+        let Some(connection) = self.connections.get(connection) else {
+            return;
+        };
+        match StatsReport::compute(&connection.manager, title, relation, params) {
+            Ok(report) => self.column_stats = Some(report),
+            Err(e) => self.notify(
+                NotificationLevel::Error,
+                tr("notify.stats_failed"),
+                &e.to_string(),
+            ),
+        }
+    }
+
+    /// 切换当前连接的自动提交
+    fn toggle_auto_commit(&mut self) {
+        let Some(connection) = self
+            .current_connection_index()
+            .and_then(|i| self.connections.get_mut(i))
+        else {
+            self.set_status_message(tr("status.no_connection").to_owned());
+            return;
+        };
+
+        let auto_commit = !connection.manager.auto_commit();
+        connection.manager.set_auto_commit(auto_commit);
+        let key = if auto_commit {
+            "status.auto_commit_on"
+        } else {
+            "status.auto_commit_off"
+        };
+        let message = tr_args(key, &[("name", &connection.name)]);
+        self.set_status_message(message);
+    }
+
+    /// 提交或回滚当前连接的事务
+    fn end_transaction(&mut self, commit: bool) {
+        let Some(connection) = self
+            .current_connection_index()
+            .and_then(|i| self.connections.get(i))
+        else {
+            self.set_status_message(tr("status.no_connection").to_owned());
+            return;
+        };
+        if !connection.manager.in_transaction() {
+            self.set_status_message(tr("status.no_transaction").to_owned());
+            return;
+        }
+
+        let (result, key) = if commit {
+            (connection.manager.commit(), "status.committed")
+        } else {
+            (connection.manager.rollback(), "status.rolled_back")
+        };
+        let message = match result {
+            Ok(()) => tr_args(key, &[("name", &connection.name)]),
+            Err(e) => tr_args("status.query_failed", &[("error", &e)]),
+        };
+        self.history_panel.dirty = true;
+        self.set_status_message(message);
+    }
+
+    /// 有未提交事务的连接名称
+    pub fn pending_transactions(&self) -> Vec<&str> {
+        self.connections
+            .iter()
+            .filter(|c| c.manager.in_transaction())
+            .map(|c| c.name.as_str())
+            .collect()
+    }
+
+    /// 提交或回滚所有连接上未结束的事务，返回失败的错误信息
+    pub fn end_all_transactions(&self, commit: bool) -> Vec<String> {
+        let mut errors = Vec::new();
+        let pending = self
+            .connections
+            .iter()
+            .filter(|c| c.manager.in_transaction());
+        for connection in pending {
+            let result = if commit {
+                connection.manager.commit()
+            } else {
+                connection.manager.rollback()
+            };
+            if let Err(e) = result {
+                errors.push(format!("{}: {}", connection.name, e));
+            }
+        }
+        errors
+    }
+
+    /// 有未提交的事务时拦截关闭窗口，改为显示确认对话框
+    pub fn check_close_request(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| i.viewport().close_requested())
+            && !self.close_confirmed
+            && !self.pending_transactions().is_empty()
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.close_prompt = true;
+        }
+    }
+
+    /// 提交或回滚所有事务后关闭窗口，失败时保持窗口打开
+    pub fn confirm_close(&mut self, ctx: &egui::Context, commit: bool) {
+        self.close_prompt = false;
+        let errors = self.end_all_transactions(commit);
+        if !errors.is_empty() {
+            self.notify(
+                NotificationLevel::Error,
+                tr("notify.transaction_end_failed"),
+                &errors.join("\n"),
+            );
+            return;
+        }
+        self.close_confirmed = true;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    }
+
+    /// 在新的编辑器标签页中打开历史记录
+    pub fn open_history_entry(&mut self, entry: &HistoryEntry) {
+        let title = tr_args(
+            "editor.query_title",
+            &[("index", &(self.editor_tabs.len() + 1))],
+        );
+        self.add_editor_tab(EditorTab {
+            title,
+            content: entry.sql.clone(),
+            connection: Some(entry.connection.clone()).filter(|c| !c.is_empty()),
+            ..EditorTab::default()
+        });
+
+        // 历史记录所属的连接没有打开时尝试重新连接
+        if !entry.connection.is_empty() && !self.open_connection(&entry.connection) {
+            let message = tr_args("status.db_connect_failed", &[("path", &entry.connection)]);
+            self.set_status_message(message);
+        }
+    }
+
+    /// 在新的编辑器标签页中打开生成的查询，没有指定连接时使用当前标签页的连接
+    pub fn open_query_tab(&mut self, sql: String, connection: Option<String>) {
+        let title = tr_args(
+            "editor.query_title",
+            &[("index", &(self.editor_tabs.len() + 1))],
+        );
+        let connection = connection.or_else(|| {
+            self.current_connection_index()
+                .map(|i| self.connections[i].path.clone())
+        });
+        self.add_editor_tab(EditorTab {
+            title: title.clone(),
+            content: sql,
+            connection,
+            ..EditorTab::default()
+        });
+        self.set_status_message(tr_args("status.tab_created", &[("title", &title)]));
+    }
+
+    /// 生成数据库对象的语句并在新的编辑器标签页中打开
+    pub fn generate_sql(&mut self, index: usize, object: &DbObject, template: SqlTemplate) {
+        let Some(connection) = self.connections.get_mut(index) else {
+            return;
+        };
+        match crate::ddl::generate(&connection.manager, object, template) {
+            Ok(Some(sql)) => {
+                let path = connection.path.clone();
+                self.open_query_tab(sql, Some(path));
+            }
+            Ok(None) => {
+                // 对象已被删除，重新读取侧边栏
+                connection.objects = None;
+                let message = tr_args("status.object_missing", &[("name", &object.name)]);
+                self.set_status_message(message);
+            }
+            Err(e) => self.notify(
+                NotificationLevel::Error,
+                tr("notify.generate_sql_failed"),
+                &e.to_string(),
+            ),
+        }
+    }
+
+    /// 打开表设计窗口，`table` 为 `None` 时设计新表
+    pub fn open_table_designer(&mut self, index: usize, table: Option<&TableName>) {
+        let Some(connection) = self.connections.get(index) else {
+            return;
+        };
+        let path = connection.path.clone();
+        let designer = match table {
+            Some(table) => TableDesigner::edit_table(path, &connection.manager, table),
+            None => connection
+                .manager
+                .fetch("SELECT current_database()", &QueryParams::None, 1)
+                .map(|result| {
+                    let database = result.rows.first().map(|row| format_value(&row[0]));
+                    TableDesigner::new_table(path, database.unwrap_or_default())
+                }),
+        };
+        match designer {
+            Ok(designer) => self.table_designer = Some(designer),
+            Err(e) => self.notify(
+                NotificationLevel::Error,
+                tr("notify.designer_failed"),
+                &e.to_string(),
+            ),
+        }
+    }
+
+    /// 在新的编辑器标签页中打开查询库条目
+    pub fn open_library_entry(&mut self, entry: &LibraryEntry) {
+        self.add_editor_tab(EditorTab {
+            title: entry.name.clone(),
+            content: entry.sql.clone(),
+            connection: self.active_connection_path(),
+            ..EditorTab::default()
+        });
+    }
+
+    /// 把文本追加到当前编辑器，没有打开的编辑器时新建查询
+    pub fn insert_into_editor(&mut self, text: &str) {
+        if self.active_editor_tab.is_none() {
+            self.new_query();
+        }
+        let Some(tab) = self
+            .active_editor_tab
+            .and_then(|i| self.editor_tabs.get_mut(i))
+        else {
+            return;
+        };
+
+        if !tab.content.is_empty() && !tab.content.ends_with('\n') {
+            tab.content.push('\n');
+        }
+        tab.content.push_str(text);
+        tab.is_dirty = true;
+    }
+
+    /// 打开“保存到查询库”对话框，内容为当前编辑器中的 SQL
+    fn save_to_library(&mut self) {
+        let Some(tab) = self.active_editor_tab.and_then(|i| self.editor_tabs.get(i)) else {
+            self.set_status_message(tr("status.no_open_file").to_owned());
+            return;
+        };
+
+        self.library_panel.save_dialog = Some(SaveDialog {
+            entry: LibraryEntry {
+                name: tab.title.clone(),
+                sql: tab.content.clone(),
+                ..LibraryEntry::default()
+            },
+        });
+    }
+
+    /// 当前选中连接的数据库路径
+    fn active_connection_path(&self) -> Option<String> {
+        self.active_connection
+            .and_then(|i| self.connections.get(i))
+            .map(|c| c.path.clone())
     }
 
     /// 新建窗口
     fn new_window(&mut self) {
-        self.set_status_message("正在创建新窗口...".to_owned());
+        self.set_status_message(tr("status.new_window").to_owned());
         println!("新建窗口");
     }
 
     /// 显示关于信息
     fn show_about(&mut self) {
-        self.set_status_message("正在显示关于信息...".to_owned());
+        self.set_status_message(tr("status.showing_about").to_owned());
         println!("显示关于信息");
     }
 
@@ -515,8 +1940,9 @@ impl MyApp {
         if self.status_message_time > 0.0 && ctx.input(|i| i.time) - self.status_message_time > 3.0
         {
             // 3秒后恢复默认消息
-            if self.status_message != "就绪" {
-                self.status_message = "就绪".to_owned();
+            let ready = tr("status.ready");
+            if self.status_message != ready {
+                self.status_message = ready.to_owned();
                 self.status_message_time = 0.0;
             }
         }
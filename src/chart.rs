@@ -0,0 +1,1044 @@
+//! 图表模块
+//! 用 egui_plot 把查询结果绘制为折线图、柱状图、散点图、直方图和饼图，并导出为 PNG 或 SVG
+
+use crate::database::{QueryResult, format_value};
+use crate::i18n::{tr, tr_args};
+use duckdb::types::Value;
+use eframe::egui::{self, Color32};
+use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoint, PlotUi, Points, Polygon, Text};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// 系列的颜色，图表和导出的 SVG 使用相同的颜色
+const PALETTE: [Color32; 8] = [
+    Color32::from_rgb(66, 133, 244),
+    Color32::from_rgb(219, 68, 55),
+    Color32::from_rgb(244, 180, 0),
+    Color32::from_rgb(15, 157, 88),
+    Color32::from_rgb(171, 71, 188),
+    Color32::from_rgb(0, 172, 193),
+    Color32::from_rgb(255, 112, 67),
+    Color32::from_rgb(158, 157, 36),
+];
+
+/// 导出的 SVG 的尺寸
+const SVG_SIZE: (f64, f64) = (800.0, 480.0);
+
+/// 第 `i` 个系列的颜色
+fn color(i: usize) -> Color32 {
+    PALETTE[i % PALETTE.len()]
+}
+
+/// 图表类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChartKind {
+    #[default]
+    Line,
+    Bar,
+    Scatter,
+    Histogram,
+    Pie,
+}
+
+impl ChartKind {
+    pub const ALL: [ChartKind; 5] = [
+        ChartKind::Line,
+        ChartKind::Bar,
+        ChartKind::Scatter,
+        ChartKind::Histogram,
+        ChartKind::Pie,
+    ];
+
+    /// 显示名称的文本键
+    pub fn label(self) -> &'static str {
+        match self {
+            ChartKind::Line => "chart.kind_line",
+            ChartKind::Bar => "chart.kind_bar",
+            ChartKind::Scatter => "chart.kind_scatter",
+            ChartKind::Histogram => "chart.kind_histogram",
+            ChartKind::Pie => "chart.kind_pie",
+        }
+    }
+}
+
+/// 按 X 列分组时 Y 列的汇总方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Aggregation {
+    #[default]
+    None,
+    Sum,
+    Avg,
+    Count,
+}
+
+impl Aggregation {
+    pub const ALL: [Aggregation; 4] = [
+        Aggregation::None,
+        Aggregation::Sum,
+        Aggregation::Avg,
+        Aggregation::Count,
+    ];
+
+    /// 显示名称的文本键
+    pub fn label(self) -> &'static str {
+        match self {
+            Aggregation::None => "chart.agg_none",
+            Aggregation::Sum => "chart.agg_sum",
+            Aggregation::Avg => "chart.agg_avg",
+            Aggregation::Count => "chart.agg_count",
+        }
+    }
+}
+
+/// 图表设置
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartConfig {
+    pub kind: ChartKind,
+    pub x: usize,      // X 列，饼图的分类列
+    pub y: Vec<usize>, // Y 列，每列一个系列；直方图使用第一列
+    pub aggregation: Aggregation,
+    pub bins: usize, // 直方图的分组数
+}
+
+impl Default for ChartConfig {
+    fn default() -> Self {
+        Self {
+            kind: ChartKind::default(),
+            x: 0,
+            y: Vec::new(),
+            aggregation: Aggregation::default(),
+            bins: 20,
+        }
+    }
+}
+
+impl ChartConfig {
+    /// 根据结果选择默认的列：X 为第一个非数值列，Y 为第一个其他的数值列
+    pub fn suggest(result: &QueryResult) -> Self {
+        let kinds: Vec<AxisKind> = (0..result.columns.len())
+            .map(|col| AxisKind::detect(result, col))
+            .collect();
+        let x = kinds
+            .iter()
+            .position(|kind| *kind != AxisKind::Numeric)
+            .unwrap_or(0);
+        let y = (0..kinds.len())
+            .find(|&col| col != x && kinds[col] == AxisKind::Numeric)
+            .into_iter()
+            .collect();
+        Self {
+            x,
+            y,
+            ..Self::default()
+        }
+    }
+
+    /// 设置中的列是否都在结果中
+    fn fits(&self, result: &QueryResult) -> bool {
+        let count = result.columns.len();
+        self.x < count && self.y.iter().all(|&col| col < count)
+    }
+}
+
+/// X 轴的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AxisKind {
+    #[default]
+    Numeric,
+    Time,     // 日期和时间戳，坐标为 Unix 秒数
+    Category, // 其他类型，坐标为分类的序号
+}
+
+impl AxisKind {
+    /// 按列中非空的值判断坐标轴类型
+    fn detect(result: &QueryResult, col: usize) -> Self {
+        let mut values = result
+            .rows
+            .iter()
+            .map(|values| &values[col])
+            .filter(|value| !matches!(value, Value::Null))
+            .peekable();
+        if values.peek().is_none() {
+            return AxisKind::Category;
+        }
+        let values: Vec<&Value> = values.collect();
+        if values.iter().all(|value| time(value).is_some()) {
+            AxisKind::Time
+        } else if values.iter().all(|value| number(value).is_some()) {
+            AxisKind::Numeric
+        } else {
+            AxisKind::Category
+        }
+    }
+}
+
+/// 一个系列的数据点
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Series {
+    pub name: String,
+    pub points: Vec<[f64; 2]>,
+}
+
+/// 计算好的图表数据
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChartData {
+    pub kind: ChartKind,
+    pub axis: AxisKind,
+    pub categories: Vec<String>, // 分类轴的标签，饼图各扇区的名称
+    pub series: Vec<Series>,
+    pub bar_width: f64,
+}
+
+/// 数值
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::TinyInt(_)
+        | Value::SmallInt(_)
+        | Value::Int(_)
+        | Value::BigInt(_)
+        | Value::HugeInt(_)
+        | Value::UTinyInt(_)
+        | Value::USmallInt(_)
+        | Value::UInt(_)
+        | Value::UBigInt(_)
+        | Value::UHugeInt(_)
+        | Value::Float(_)
+        | Value::Double(_)
+        | Value::Decimal(_) => format_value(value).parse().ok(),
+        _ => None,
+    }
+}
+
+/// 日期和时间戳转换为 Unix 秒数
+fn time(value: &Value) -> Option<f64> {
+    match value {
+        Value::Date32(days) => Some(*days as f64 * 86400.0),
+        Value::Timestamp(unit, v) => Some(unit.to_micros(*v) as f64 / 1_000_000.0),
+        _ => None,
+    }
+}
+
+/// 格式化时间轴的刻度，跨度超过两天时只显示日期
+fn format_time(seconds: f64, span: f64) -> String {
+    let Some(time) = chrono::DateTime::from_timestamp(seconds.round() as i64, 0) else {
+        return String::new();
+    };
+    let format = if span > 2.0 * 86400.0 {
+        "%Y-%m-%d"
+    } else {
+        "%m-%d %H:%M"
+    };
+    time.naive_utc().format(format).to_string()
+}
+
+/// 按 X 分组汇总 Y，`ys[s][row]` 为第 `s` 个系列在第 `row` 行的值
+///
+/// 返回每个系列的点，按分组第一次出现的顺序排列。
+fn aggregate(
+    xs: &[Option<f64>],
+    ys: &[Vec<Option<f64>>],
+    aggregation: Aggregation,
+) -> Vec<Vec<[f64; 2]>> {
+    if aggregation == Aggregation::None {
+        return ys
+            .iter()
+            .map(|values| {
+                xs.iter()
+                    .zip(values)
+                    .filter_map(|(x, y)| Some([(*x)?, (*y)?]))
+                    .collect()
+            })
+            .collect();
+    }
+
+    let mut groups: Vec<f64> = Vec::new();
+    let mut positions: HashMap<u64, usize> = HashMap::new();
+    // 每个系列每个分组的合计和个数
+    let mut totals: Vec<Vec<(f64, usize)>> = vec![Vec::new(); ys.len()];
+    for (row, x) in xs.iter().enumerate() {
+        let Some(x) = *x else {
+            continue;
+        };
+        let group = *positions.entry(x.to_bits()).or_insert_with(|| {
+            groups.push(x);
+            groups.len() - 1
+        });
+        for (series, values) in ys.iter().enumerate() {
+            totals[series].resize(groups.len(), (0.0, 0));
+            if let Some(y) = values[row] {
+                let total = &mut totals[series][group];
+                total.0 += y;
+                total.1 += 1;
+            }
+        }
+    }
+
+    totals
+        .into_iter()
+        .map(|totals| {
+            groups
+                .iter()
+                .zip(totals)
+                .filter_map(|(&x, (sum, count))| {
+                    let y = match aggregation {
+                        Aggregation::Sum => sum,
+                        Aggregation::Avg if count > 0 => sum / count as f64,
+                        Aggregation::Count => count as f64,
+                        _ => return None,
+                    };
+                    Some([x, y])
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// 按设置计算图表数据，失败时返回说明原因的文本键
+pub fn build(result: &QueryResult, config: &ChartConfig) -> Result<ChartData, &'static str> {
+    if !config.fits(result) {
+        return Err("chart.invalid_columns");
+    }
+    let counting = config.aggregation == Aggregation::Count;
+    if config.y.is_empty() && !(counting && config.kind != ChartKind::Histogram) {
+        return Err("chart.need_y");
+    }
+    if config.kind == ChartKind::Histogram {
+        return histogram(result, config.y[0], config.bins);
+    }
+
+    // 饼图总是按分类汇总，没有选择汇总方式时求和
+    let (axis, aggregation) = match config.kind {
+        ChartKind::Pie if config.aggregation == Aggregation::None => {
+            (AxisKind::Category, Aggregation::Sum)
+        }
+        ChartKind::Pie => (AxisKind::Category, config.aggregation),
+        _ => (AxisKind::detect(result, config.x), config.aggregation),
+    };
+
+    let mut categories = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let xs: Vec<Option<f64>> = result
+        .rows
+        .iter()
+        .map(|values| {
+            let value = &values[config.x];
+            match axis {
+                AxisKind::Numeric => number(value),
+                AxisKind::Time => time(value),
+                AxisKind::Category => {
+                    let label = format_value(value);
+                    let next = categories.len();
+                    let position = *positions.entry(label.clone()).or_insert_with(|| {
+                        categories.push(label);
+                        next
+                    });
+                    Some(position as f64)
+                }
+            }
+        })
+        .collect();
+
+    // 只统计行数时没有 Y 列
+    let (names, ys): (Vec<String>, Vec<Vec<Option<f64>>>) = if config.y.is_empty() {
+        (
+            vec!["count(*)".to_string()],
+            vec![vec![Some(1.0); xs.len()]],
+        )
+    } else {
+        let columns = if config.kind == ChartKind::Pie {
+            &config.y[..1]
+        } else {
+            &config.y[..]
+        };
+        columns
+            .iter()
+            .map(|&col| {
+                let values = result.rows.iter().map(|values| number(&values[col]));
+                (result.columns[col].clone(), values.collect())
+            })
+            .unzip()
+    };
+
+    let mut series: Vec<Series> = aggregate(&xs, &ys, aggregation)
+        .into_iter()
+        .zip(names)
+        .map(|(points, name)| Series { name, points })
+        .collect();
+    if axis != AxisKind::Category {
+        for series in &mut series {
+            series.points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        }
+    }
+
+    // 柱子的宽度为相邻 X 的最小间距，多个系列并排显示
+    let gap = match axis {
+        AxisKind::Category => 1.0,
+        _ => {
+            let mut xs: Vec<f64> = series
+                .iter()
+                .flat_map(|s| s.points.iter().map(|p| p[0]))
+                .collect();
+            xs.sort_by(f64::total_cmp);
+            xs.windows(2)
+                .map(|pair| pair[1] - pair[0])
+                .filter(|gap| *gap > 0.0)
+                .fold(f64::INFINITY, f64::min)
+        }
+    };
+    let gap = if gap.is_finite() { gap } else { 1.0 };
+
+    Ok(ChartData {
+        kind: config.kind,
+        axis,
+        categories,
+        bar_width: gap * 0.8 / series.len().max(1) as f64,
+        series,
+    })
+}
+
+/// 把一列数值按等宽区间分组
+fn histogram(result: &QueryResult, col: usize, bins: usize) -> Result<ChartData, &'static str> {
+    let values: Vec<f64> = result
+        .rows
+        .iter()
+        .filter_map(|values| number(&values[col]))
+        .collect();
+    if values.is_empty() {
+        return Err("chart.no_numeric");
+    }
+    let bins = bins.clamp(1, 200);
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let width = if max > min {
+        (max - min) / bins as f64
+    } else {
+        1.0
+    };
+
+    let mut counts = vec![0usize; bins];
+    for value in values {
+        let bin = ((value - min) / width) as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+    let points = counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| [min + width * (i as f64 + 0.5), count as f64])
+        .collect();
+
+    Ok(ChartData {
+        kind: ChartKind::Histogram,
+        axis: AxisKind::Numeric,
+        categories: Vec::new(),
+        series: vec![Series {
+            name: result.columns[col].clone(),
+            points,
+        }],
+        bar_width: width,
+    })
+}
+
+impl ChartData {
+    /// 第 `s` 个系列的柱子，多个系列在同一位置并排
+    fn bars(&self, s: usize) -> Vec<(f64, f64)> {
+        let offset = (s as f64 - (self.series.len() as f64 - 1.0) / 2.0) * self.bar_width;
+        self.series[s]
+            .points
+            .iter()
+            .map(|p| (p[0] + offset, p[1]))
+            .collect()
+    }
+
+    /// 饼图的扇区：名称、数值、起止角度（弧度，从正上方顺时针）
+    fn wedges(&self) -> Vec<(String, f64, f64, f64)> {
+        let points: Vec<[f64; 2]> = self
+            .series
+            .first()
+            .map(|s| s.points.iter().copied().filter(|p| p[1] > 0.0).collect())
+            .unwrap_or_default();
+        let total: f64 = points.iter().map(|p| p[1]).sum();
+        let mut start = 0.0;
+        points
+            .into_iter()
+            .map(|[x, y]| {
+                let sweep = y / total * std::f64::consts::TAU;
+                let name = self.categories.get(x as usize).cloned().unwrap_or_default();
+                let wedge = (name, y, start, start + sweep);
+                start += sweep;
+                wedge
+            })
+            .collect()
+    }
+
+    /// X 坐标的显示文本
+    fn format_x(&self, x: f64, span: f64) -> String {
+        match self.axis {
+            AxisKind::Numeric => format_number(x),
+            AxisKind::Time => format_time(x, span),
+            AxisKind::Category => {
+                let index = x.round();
+                if (x - index).abs() > 0.01 || index < 0.0 {
+                    return String::new();
+                }
+                self.categories
+                    .get(index as usize)
+                    .cloned()
+                    .unwrap_or_default()
+            }
+        }
+    }
+
+    /// 导出为 SVG
+    pub fn to_svg(&self) -> String {
+        let (width, height) = SVG_SIZE;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" font-family=\"sans-serif\" font-size=\"12\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n"
+        );
+        if self.kind == ChartKind::Pie {
+            self.svg_pie(&mut svg);
+        } else {
+            self.svg_xy(&mut svg);
+        }
+        // 图例
+        let names: Vec<&str> = match self.kind {
+            ChartKind::Pie => self.categories.iter().map(String::as_str).collect(),
+            _ => self.series.iter().map(|s| s.name.as_str()).collect(),
+        };
+        for (i, name) in names.iter().enumerate() {
+            let y = 20.0 + i as f64 * 18.0;
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"10\" height=\"10\" fill=\"{}\"/><text x=\"{}\" y=\"{}\">{}</text>",
+                width - 150.0,
+                y - 9.0,
+                hex(color(i)),
+                width - 134.0,
+                y,
+                escape_xml(name)
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn svg_xy(&self, svg: &mut String) {
+        let (width, height) = SVG_SIZE;
+        let (left, right, top, bottom) = (60.0, width - 170.0, 20.0, height - 40.0);
+        let points = self.series.iter().flat_map(|s| s.points.iter());
+        let (mut x_min, mut x_max, mut y_min, mut y_max) =
+            (f64::INFINITY, f64::NEG_INFINITY, 0.0_f64, f64::NEG_INFINITY);
+        for p in points {
+            x_min = x_min.min(p[0]);
+            x_max = x_max.max(p[0]);
+            y_min = y_min.min(p[1]);
+            y_max = y_max.max(p[1]);
+        }
+        if !x_min.is_finite() {
+            return;
+        }
+        let bars = matches!(self.kind, ChartKind::Bar | ChartKind::Histogram);
+        if bars {
+            // 留出两端柱子的宽度
+            let half = self.bar_width * self.series.len() as f64 / 2.0;
+            x_min -= half;
+            x_max += half;
+        }
+        if x_max <= x_min {
+            x_max = x_min + 1.0;
+        }
+        if y_max <= y_min {
+            y_max = y_min + 1.0;
+        }
+        let sx = |x: f64| left + (x - x_min) / (x_max - x_min) * (right - left);
+        let sy = |y: f64| bottom - (y - y_min) / (y_max - y_min) * (bottom - top);
+
+        // 坐标轴和刻度
+        let _ = writeln!(
+            svg,
+            "<path d=\"M{left} {top} V{bottom} H{right}\" stroke=\"#888\" fill=\"none\"/>"
+        );
+        for i in 0..=4 {
+            let y = y_min + (y_max - y_min) * i as f64 / 4.0;
+            let _ = writeln!(
+                svg,
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+                left - 6.0,
+                sy(y) + 4.0,
+                format_number(y)
+            );
+        }
+        let ticks: Vec<f64> = match self.axis {
+            AxisKind::Category => (0..self.categories.len()).map(|i| i as f64).collect(),
+            _ => (0..=4)
+                .map(|i| x_min + (x_max - x_min) * i as f64 / 4.0)
+                .collect(),
+        };
+        for x in ticks {
+            let _ = writeln!(
+                svg,
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+                sx(x),
+                bottom + 16.0,
+                escape_xml(&self.format_x(x, x_max - x_min))
+            );
+        }
+
+        for (s, series) in self.series.iter().enumerate() {
+            let fill = hex(color(s));
+            match self.kind {
+                ChartKind::Line => {
+                    let path: Vec<String> = series
+                        .points
+                        .iter()
+                        .map(|p| format!("{:.1},{:.1}", sx(p[0]), sy(p[1])))
+                        .collect();
+                    let _ = writeln!(
+                        svg,
+                        "<polyline points=\"{}\" fill=\"none\" stroke=\"{fill}\" stroke-width=\"2\"/>",
+                        path.join(" ")
+                    );
+                }
+                ChartKind::Scatter => {
+                    for p in &series.points {
+                        let _ = writeln!(
+                            svg,
+                            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{fill}\"/>",
+                            sx(p[0]),
+                            sy(p[1])
+                        );
+                    }
+                }
+                _ => {
+                    for (x, y) in self.bars(s) {
+                        let x0 = sx(x - self.bar_width / 2.0);
+                        let x1 = sx(x + self.bar_width / 2.0);
+                        let (y0, y1) = (sy(y.max(0.0)), sy(y.min(0.0)));
+                        let _ = writeln!(
+                            svg,
+                            "<rect x=\"{x0:.1}\" y=\"{y0:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{fill}\"/>",
+                            x1 - x0,
+                            y1 - y0
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn svg_pie(&self, svg: &mut String) {
+        let (width, height) = SVG_SIZE;
+        let (cx, cy, r) = ((width - 160.0) / 2.0, height / 2.0, height / 2.0 - 30.0);
+        let point = |angle: f64| (cx + r * angle.sin(), cy - r * angle.cos());
+        for (i, (_, _, start, end)) in self.wedges().into_iter().enumerate() {
+            let fill = hex(color(i));
+            if end - start >= std::f64::consts::TAU - 1e-9 {
+                let _ = writeln!(
+                    svg,
+                    "<circle cx=\"{cx}\" cy=\"{cy}\" r=\"{r}\" fill=\"{fill}\"/>"
+                );
+                continue;
+            }
+            let (x0, y0) = point(start);
+            let (x1, y1) = point(end);
+            let large = i32::from(end - start > std::f64::consts::PI);
+            let _ = writeln!(
+                svg,
+                "<path d=\"M{cx} {cy} L{x0:.1} {y0:.1} A{r} {r} 0 {large} 1 {x1:.1} {y1:.1} Z\" fill=\"{fill}\" stroke=\"white\"/>"
+            );
+        }
+    }
+}
+
+/// 格式化坐标轴上的数值，去掉多余的小数位
+fn format_number(value: f64) -> String {
+    let text = format!("{:.4}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// SVG 中的颜色
+fn hex(color: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+/// 转义 XML 文本
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 结果面板中图表的状态
+#[derive(Debug, Clone, Default)]
+pub struct ChartState {
+    pub config: Option<ChartConfig>, // 第一次显示时根据结果选择
+    cache: Option<(ChartConfig, Result<ChartData, &'static str>)>,
+    pub png_export: Option<PathBuf>, // 等待截图的 PNG 导出路径
+    screenshot_requested: bool,
+    plot_rect: Option<egui::Rect>,
+    pub message: Option<String>, // 最近一次导出的结果
+}
+
+impl ChartState {
+    /// 结果改变后重新计算图表数据
+    pub fn invalidate(&mut self) {
+        self.cache = None;
+    }
+
+    /// 按当前设置计算的图表数据
+    pub fn data(&mut self, result: &QueryResult) -> Result<&ChartData, &'static str> {
+        let config = match &self.config {
+            Some(config) if config.fits(result) => config.clone(),
+            _ => ChartConfig::suggest(result),
+        };
+        if self
+            .cache
+            .as_ref()
+            .is_none_or(|(built, _)| *built != config)
+        {
+            self.cache = Some((config.clone(), build(result, &config)));
+        }
+        self.config = Some(config);
+        match &self.cache {
+            Some((_, data)) => data.as_ref().map_err(|e| *e),
+            None => Err("chart.need_y"),
+        }
+    }
+
+    /// 收到截图后裁剪出图表区域并保存为 PNG
+    fn save_screenshot(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.png_export.clone() else {
+            return;
+        };
+        if !self.screenshot_requested {
+            self.screenshot_requested = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(egui::UserData::default()));
+            return;
+        }
+        let image = ctx.input(|i| {
+            i.raw.events.iter().find_map(|event| match event {
+                egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        });
+        let (Some(image), Some(rect)) = (image, self.plot_rect) else {
+            return;
+        };
+
+        let pixels_per_point = ctx.pixels_per_point();
+        let bounds = egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(image.width() as f32, image.height() as f32) / pixels_per_point,
+        );
+        let region = image.region(&rect.intersect(bounds), Some(pixels_per_point));
+        let bytes: Vec<u8> = region.pixels.iter().flat_map(|c| c.to_array()).collect();
+        let saved = image::save_buffer(
+            &path,
+            &bytes,
+            region.width() as u32,
+            region.height() as u32,
+            image::ColorType::Rgba8,
+        );
+        let path = path.display().to_string();
+        self.message = Some(match saved {
+            Ok(()) => tr_args("chart.exported", &[("path", &path)]),
+            Err(e) => tr_args("chart.export_failed", &[("error", &e)]),
+        });
+        self.png_export = None;
+        self.screenshot_requested = false;
+    }
+}
+
+/// 渲染图表设置和图表，返回选择的导出动作
+pub fn show(
+    ui: &mut egui::Ui,
+    result: &QueryResult,
+    state: &mut ChartState,
+) -> Option<&'static str> {
+    let mut action = None;
+    if result.columns.is_empty() {
+        return None;
+    }
+    let _ = state.data(result);
+    let mut config = state.config.clone()?;
+
+    ui.horizontal_wrapped(|ui| {
+        egui::ComboBox::from_id_salt("chart_kind")
+            .selected_text(tr(config.kind.label()))
+            .show_ui(ui, |ui| {
+                for kind in ChartKind::ALL {
+                    ui.selectable_value(&mut config.kind, kind, tr(kind.label()));
+                }
+            });
+
+        if config.kind != ChartKind::Histogram {
+            let x_label = if config.kind == ChartKind::Pie {
+                "chart.category"
+            } else {
+                "chart.x"
+            };
+            ui.label(tr(x_label));
+            egui::ComboBox::from_id_salt("chart_x")
+                .selected_text(&result.columns[config.x])
+                .show_ui(ui, |ui| {
+                    for (col, name) in result.columns.iter().enumerate() {
+                        ui.selectable_value(&mut config.x, col, name);
+                    }
+                });
+        }
+
+        let y_names: Vec<&str> = config
+            .y
+            .iter()
+            .map(|&c| result.columns[c].as_str())
+            .collect();
+        ui.label(tr("chart.y"));
+        ui.menu_button(
+            if y_names.is_empty() {
+                "-".to_string()
+            } else {
+                y_names.join(", ")
+            },
+            |ui| {
+                for (col, name) in result.columns.iter().enumerate() {
+                    let mut checked = config.y.contains(&col);
+                    if ui.checkbox(&mut checked, name).changed() {
+                        if checked {
+                            config.y.push(col);
+                        } else {
+                            config.y.retain(|&c| c != col);
+                        }
+                    }
+                }
+            },
+        );
+
+        if config.kind == ChartKind::Histogram {
+            ui.label(tr("chart.bins"));
+            ui.add(egui::DragValue::new(&mut config.bins).range(1..=200));
+        } else {
+            ui.label(tr("chart.aggregation"));
+            egui::ComboBox::from_id_salt("chart_aggregation")
+                .selected_text(tr(config.aggregation.label()))
+                .show_ui(ui, |ui| {
+                    for aggregation in Aggregation::ALL {
+                        ui.selectable_value(
+                            &mut config.aggregation,
+                            aggregation,
+                            tr(aggregation.label()),
+                        );
+                    }
+                });
+        }
+
+        ui.separator();
+        if ui.button(tr("chart.export_png")).clicked() {
+            action = Some("export_chart_png");
+        }
+        if ui.button(tr("chart.export_svg")).clicked() {
+            action = Some("export_chart_svg");
+        }
+        if let Some(message) = &state.message {
+            ui.label(egui::RichText::new(message).weak());
+        }
+    });
+    state.config = Some(config);
+
+    if result.truncated {
+        let hint = tr_args("chart.truncated", &[("count", &result.rows.len())]);
+        ui.label(egui::RichText::new(hint).weak());
+    }
+
+    let data = match state.data(result) {
+        Ok(data) => data.clone(),
+        Err(key) => {
+            ui.label(egui::RichText::new(tr(key)).weak());
+            return action;
+        }
+    };
+    let response = plot(ui, &data);
+    state.plot_rect = Some(response.rect);
+    state.save_screenshot(ui.ctx());
+    action
+}
+
+/// 用 egui_plot 绘制图表
+fn plot(ui: &mut egui::Ui, data: &ChartData) -> egui::Response {
+    let mut plot = Plot::new("result_chart").legend(Legend::default());
+    if data.kind == ChartKind::Pie {
+        plot = plot
+            .data_aspect(1.0)
+            .show_axes(false)
+            .show_grid(false)
+            .show_x(false)
+            .show_y(false);
+    } else {
+        plot = plot
+            .x_axis_formatter(|mark, range| data.format_x(mark.value, range.end() - range.start()))
+            .label_formatter(|name, point| {
+                let x = data.format_x(point.x, 0.0);
+                let text = format!("x = {}\ny = {}", x, format_number(point.y));
+                if name.is_empty() {
+                    text
+                } else {
+                    format!("{}\n{}", name, text)
+                }
+            });
+        if matches!(data.kind, ChartKind::Bar | ChartKind::Histogram) {
+            plot = plot.include_y(0.0);
+        }
+    }
+
+    plot.show(ui, |plot_ui| draw(plot_ui, data)).response
+}
+
+fn draw(plot_ui: &mut PlotUi, data: &ChartData) {
+    if data.kind == ChartKind::Pie {
+        for (i, (name, value, start, end)) in data.wedges().into_iter().enumerate() {
+            // 每个扇区用折线近似圆弧
+            let steps = ((end - start) / 0.05).ceil().max(1.0) as usize;
+            let mut points = vec![[0.0, 0.0]];
+            points.extend((0..=steps).map(|k| {
+                let angle = start + (end - start) * k as f64 / steps as f64;
+                [angle.sin(), angle.cos()]
+            }));
+            plot_ui.polygon(
+                Polygon::new(name.clone(), points)
+                    .fill_color(color(i))
+                    .width(1.0),
+            );
+            let middle = (start + end) / 2.0;
+            let label = format!("{}\n{}", name, format_number(value));
+            plot_ui.text(Text::new(
+                name,
+                PlotPoint::new(middle.sin() * 1.2, middle.cos() * 1.2),
+                label,
+            ));
+        }
+        return;
+    }
+
+    for (s, series) in data.series.iter().enumerate() {
+        match data.kind {
+            ChartKind::Line => {
+                plot_ui.line(Line::new(series.name.clone(), series.points.clone()).color(color(s)))
+            }
+            ChartKind::Scatter => plot_ui.points(
+                Points::new(series.name.clone(), series.points.clone())
+                    .color(color(s))
+                    .radius(3.0),
+            ),
+            _ => {
+                let bars = data
+                    .bars(s)
+                    .into_iter()
+                    .map(|(x, y)| Bar::new(x, y).width(data.bar_width))
+                    .collect();
+                plot_ui.bar_chart(BarChart::new(series.name.clone(), bars).color(color(s)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> QueryResult {
+        let row = |day: i32, city: &str, amount: i32| {
+            vec![
+                Value::Date32(day),
+                Value::Text(city.into()),
+                Value::Int(amount),
+            ]
+        };
+        QueryResult {
+            columns: vec!["day".into(), "city".into(), "amount".into()],
+            rows: vec![
+                row(19725, "b", 5),
+                row(19723, "a", 10),
+                row(19723, "b", 20),
+                vec![Value::Date32(19724), Value::Text("a".into()), Value::Null],
+            ],
+            ..QueryResult::default()
+        }
+    }
+
+    #[test]
+    fn builds_series_with_aggregation() {
+        let result = sample();
+        let config = ChartConfig::suggest(&result);
+        assert_eq!((config.x, config.y.clone()), (0, vec![2]));
+
+        // 时间轴按时间排序，没有汇总时跳过空值
+        let data = build(&result, &config).unwrap();
+        assert_eq!(data.axis, AxisKind::Time);
+        let day = 19723.0 * 86400.0;
+        assert_eq!(
+            data.series[0].points,
+            [[day, 10.0], [day, 20.0], [day + 2.0 * 86400.0, 5.0]]
+        );
+        assert_eq!(format_time(day, 10.0 * 86400.0), "2024-01-01");
+
+        let sum = ChartConfig {
+            kind: ChartKind::Bar,
+            x: 1,
+            aggregation: Aggregation::Sum,
+            ..config.clone()
+        };
+        let data = build(&result, &sum).unwrap();
+        assert_eq!(data.categories, ["b", "a"]);
+        assert_eq!(data.series[0].points, [[0.0, 25.0], [1.0, 10.0]]);
+
+        let count = ChartConfig {
+            kind: ChartKind::Pie,
+            x: 1,
+            y: Vec::new(),
+            aggregation: Aggregation::Count,
+            ..config.clone()
+        };
+        let data = build(&result, &count).unwrap();
+        assert_eq!(data.series[0].points, [[0.0, 2.0], [1.0, 2.0]]);
+        assert_eq!(data.wedges()[1].2, std::f64::consts::PI);
+
+        let histogram = ChartConfig {
+            kind: ChartKind::Histogram,
+            bins: 3,
+            ..config
+        };
+        let data = build(&result, &histogram).unwrap();
+        let counts: Vec<f64> = data.series[0].points.iter().map(|p| p[1]).collect();
+        assert_eq!(counts, [1.0, 1.0, 1.0]);
+        assert_eq!(data.bar_width, 5.0);
+    }
+
+    #[test]
+    fn exports_svg() {
+        let result = sample();
+        let config = ChartConfig {
+            kind: ChartKind::Line,
+            x: 1,
+            y: vec![2],
+            aggregation: Aggregation::Avg,
+            ..ChartConfig::default()
+        };
+        let svg = build(&result, &config).unwrap().to_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("<polyline points="));
+        assert!(svg.contains(">amount</text>"));
+
+        let pie = ChartConfig {
+            kind: ChartKind::Pie,
+            ..config
+        };
+        let svg = build(&result, &pie).unwrap().to_svg();
+        assert_eq!(svg.matches("<path d=\"M").count(), 2);
+    }
+}
@@ -1,78 +1,425 @@
 //! 配置模块
 //! 处理应用配置和设置
 
+use crate::i18n::{tr, tr_args};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::SystemTime;
+
+/// 当前配置文件的结构版本
+pub const CONFIG_VERSION: u32 = 1;
+
+/// 覆盖配置文件路径的环境变量
+pub const CONFIG_ENV_VAR: &str = "E_CONFIG";
+
+/// 配置文件名
+const CONFIG_FILE_NAME: &str = "app_config.json";
+
+/// 旧版本使用的相对路径，仅用于一次性迁移
+const LEGACY_CONFIG_PATH: &str = "config/app_config.json";
+
+/// 支持的界面语言
+pub const SUPPORTED_LANGUAGES: &[&str] = &["zh-CN", "en-US"];
+
+/// 字体大小的允许范围
+pub const FONT_SIZE_RANGE: std::ops::RangeInclusive<f32> = 8.0..=48.0;
+
+/// 自动保存间隔的最小值（秒）
+pub const MIN_AUTOSAVE_INTERVAL: u32 = 5;
+
+/// 命令行 `--config` 指定的配置文件路径
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// 配置迁移函数，下标 i 的函数把版本 i 的配置升级到版本 i + 1
+type Migration = fn(&mut serde_json::Map<String, Value>);
+
+/// 按版本顺序排列的迁移函数
+const MIGRATIONS: &[Migration] = &[migrate_v0_to_v1];
 
 /// 应用配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
+    pub version: u32,
     pub window_width: f32,
     pub window_height: f32,
     pub is_maximized: bool,
     pub is_dark_mode: bool,
+    pub theme: ThemeConfig,
     pub recent_files: Vec<String>,
     pub font_size: f32,
+    pub ui_font: String,   // 界面字体文件路径，为空时自动选择
+    pub mono_font: String, // 等宽编辑器字体文件路径，为空时自动选择
     pub language: String,
+    pub keybindings: BTreeMap<String, String>, // 自定义快捷键（动作 -> 快捷键）
+    pub editor: EditorConfig,
+    pub grid: GridConfig,
+    pub connections: ConnectionsConfig,
+    pub files: FilesConfig,
+}
+
+/// 主题设置
+///
+/// `light` 和 `dark` 为自定义主题名称，为空时使用内置主题。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub follow_system: bool, // 跟随系统的浅色/深色设置
+    pub light: String,       // 浅色模式使用的主题
+    pub dark: String,        // 深色模式使用的主题
+}
+
+/// 编辑器设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditorConfig {
+    pub word_wrap: bool,             // 自动换行
+    pub autosave_interval_secs: u32, // 工作区自动保存间隔（秒）
+}
+
+impl Default for EditorConfig {
+    fn default() -> Self {
+        Self {
+            word_wrap: true,
+            autosave_interval_secs: 30,
+        }
+    }
+}
+
+/// 结果表格设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridConfig {
+    pub max_rows: usize,       // 每次查询最多读取的行数
+    pub null_display: String,  // NULL 值的显示文本
+    pub column_max_width: f32, // 列的最大宽度
+}
+
+impl Default for GridConfig {
+    fn default() -> Self {
+        Self {
+            max_rows: 10_000,
+            null_display: "NULL".to_string(),
+            column_max_width: 300.0,
+        }
+    }
+}
+
+/// 数据库连接设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnectionsConfig {
+    pub restore_on_startup: bool, // 启动时恢复上次的连接
+    pub read_only: bool,          // 以只读模式打开数据库
+    pub profiling: bool,          // 记录每条语句的性能分析信息
+}
+
+impl Default for ConnectionsConfig {
+    fn default() -> Self {
+        Self {
+            restore_on_startup: true,
+            read_only: false,
+            profiling: true,
+        }
+    }
+}
+
+/// 文件设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilesConfig {
+    pub show_hidden_files: bool, // 资源管理器中显示隐藏文件
+    pub max_recent_files: usize, // 最近文件的最大数量
+    pub library_dir: String,     // 查询库目录，为空时使用配置目录下的 library
+}
+
+impl Default for FilesConfig {
+    fn default() -> Self {
+        Self {
+            show_hidden_files: false,
+            max_recent_files: 10,
+            library_dir: String::new(),
+        }
+    }
+}
+
+/// 配置校验问题
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            version: CONFIG_VERSION,
             window_width: 1200.0,
             window_height: 800.0,
             is_maximized: false,
             is_dark_mode: false,
+            theme: ThemeConfig::default(),
             recent_files: Vec::new(),
             font_size: 14.0,
+            ui_font: String::new(),
+            mono_font: String::new(),
             language: "zh-CN".to_string(),
+            keybindings: BTreeMap::new(),
+            editor: EditorConfig::default(),
+            grid: GridConfig::default(),
+            connections: ConnectionsConfig::default(),
+            files: FilesConfig::default(),
         }
     }
 }
 
 impl AppConfig {
-    /// 从文件加载配置
-    pub fn load() -> Self {
-        let config_path = "config/app_config.json";
-
-        if Path::new(config_path).exists() {
-            match fs::read_to_string(config_path) {
-                Ok(content) => match serde_json::from_str(&content) {
-                    Ok(config) => {
-                        println!("配置加载成功");
-                        return config;
-                    }
-                    Err(e) => {
-                        eprintln!("配置文件解析错误: {}", e);
-                    }
-                },
-                Err(e) => {
-                    eprintln!("读取配置文件失败: {}", e);
-                }
+    /// 设置命令行指定的配置文件路径（只在启动时调用一次）
+    pub fn set_path_override(path: PathBuf) {
+        let _ = CONFIG_PATH_OVERRIDE.set(path);
+    }
+
+    /// 配置文件路径
+    ///
+    /// 优先级：`--config` 参数 > `E_CONFIG` 环境变量 > 平台配置目录
+    /// （Linux 下为 `$XDG_CONFIG_HOME/e`）。
+    pub fn config_path() -> PathBuf {
+        if let Some(path) = CONFIG_PATH_OVERRIDE.get() {
+            return path.clone();
+        }
+        if let Some(path) = std::env::var_os(CONFIG_ENV_VAR).filter(|p| !p.is_empty()) {
+            return PathBuf::from(path);
+        }
+
+        dirs::config_dir()
+            .map(|dir| dir.join(env!("CARGO_PKG_NAME")))
+            .unwrap_or_else(|| PathBuf::from("config"))
+            .join(CONFIG_FILE_NAME)
+    }
+
+    /// 配置目录，会话等其他数据文件与配置文件放在一起
+    pub fn config_dir() -> PathBuf {
+        match Self::config_path().parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        }
+    }
+
+    /// 从文件加载配置，文件无法读取或解析时返回默认配置和失败的原因
+    pub fn load() -> (Self, Option<ConfigLoadError>) {
+        let config_path = Self::config_path();
+
+        // 新位置还没有配置文件时，沿用旧版本工作目录下的配置
+        if !config_path.exists() && Path::new(LEGACY_CONFIG_PATH).exists() {
+            println!("从旧位置迁移配置: {}", LEGACY_CONFIG_PATH);
+            return Self::load_from(Path::new(LEGACY_CONFIG_PATH));
+        }
+
+        Self::load_from(&config_path)
+    }
+
+    /// 从指定路径加载配置
+    ///
+    /// 文件无法解析时会保留一份 `.bak` 备份，再使用默认配置，
+    /// 避免下次保存时覆盖掉用户的原始内容。
+    pub fn load_from(config_path: &Path) -> (Self, Option<ConfigLoadError>) {
+        if !config_path.exists() {
+            println!("使用默认配置");
+            return (Self::default(), None);
+        }
+
+        let content = match fs::read_to_string(config_path) {
+            Ok(content) => content,
+            Err(e) => {
+                let error = ConfigLoadError {
+                    error: e.to_string(),
+                    backup: None,
+                };
+                return (Self::default(), Some(error));
+            }
+        };
+
+        match Self::parse(&content) {
+            Ok(config) => {
+                println!("配置加载成功");
+                (config, None)
+            }
+            Err(error) => {
+                let backup_path = Self::backup_path(config_path);
+                let backup = fs::copy(config_path, &backup_path)
+                    .map(|_| backup_path)
+                    .map_err(|e| e.to_string());
+                (
+                    Self::default(),
+                    Some(ConfigLoadError {
+                        error,
+                        backup: Some(backup),
+                    }),
+                )
             }
         }
+    }
 
-        // 如果加载失败，返回默认配置
-        println!("使用默认配置");
-        Self::default()
+    /// 解析配置内容，必要时迁移到当前版本
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let value: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        let value = migrate(value)?;
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    }
+
+    /// 无法解析的配置文件的备份路径
+    pub fn backup_path(config_path: &Path) -> PathBuf {
+        let mut path = config_path.as_os_str().to_owned();
+        path.push(".bak");
+        PathBuf::from(path)
     }
 
     /// 保存配置到文件
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let config_dir = "config";
-        if !Path::new(config_dir).exists() {
+        self.save_to(&Self::config_path())
+    }
+
+    /// 保存配置到指定路径（先写临时文件再重命名）
+    pub fn save_to(&self, config_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(config_dir) = config_path.parent() {
             fs::create_dir_all(config_dir)?;
         }
 
-        let config_path = format!("{}/app_config.json", config_dir);
         let content = serde_json::to_string_pretty(self)?;
-        fs::write(config_path, content)?;
+        let tmp_path = config_path.with_extension("json.tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, config_path)?;
 
         println!("配置保存成功");
         Ok(())
     }
 
+    /// 校验配置中的取值范围
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        let mut issue = |field: &str, message: String| {
+            issues.push(ConfigIssue {
+                field: field.to_string(),
+                message,
+            })
+        };
+
+        if !FONT_SIZE_RANGE.contains(&self.font_size) {
+            issue(
+                "font_size",
+                tr_args(
+                    "config.out_of_range",
+                    &[
+                        ("value", &self.font_size),
+                        ("min", FONT_SIZE_RANGE.start()),
+                        ("max", FONT_SIZE_RANGE.end()),
+                    ],
+                ),
+            );
+        }
+        if !(is_positive(self.window_width) && is_positive(self.window_height)) {
+            issue(
+                "window_width/window_height",
+                tr_args(
+                    "config.window_size",
+                    &[
+                        ("width", &self.window_width),
+                        ("height", &self.window_height),
+                    ],
+                ),
+            );
+        }
+        if !SUPPORTED_LANGUAGES.contains(&self.language.as_str()) {
+            issue(
+                "language",
+                tr_args(
+                    "config.unknown_language",
+                    &[
+                        ("language", &self.language),
+                        ("supported", &SUPPORTED_LANGUAGES.join(", ")),
+                    ],
+                ),
+            );
+        }
+        if self.editor.autosave_interval_secs < MIN_AUTOSAVE_INTERVAL {
+            issue(
+                "editor.autosave_interval_secs",
+                tr_args(
+                    "config.below_minimum",
+                    &[
+                        ("value", &self.editor.autosave_interval_secs),
+                        ("min", &MIN_AUTOSAVE_INTERVAL),
+                    ],
+                ),
+            );
+        }
+        if self.grid.max_rows == 0 {
+            issue(
+                "grid.max_rows",
+                tr_args("config.must_be_positive", &[("value", &self.grid.max_rows)]),
+            );
+        }
+        if !is_positive(self.grid.column_max_width) {
+            issue(
+                "grid.column_max_width",
+                tr_args(
+                    "config.must_be_positive",
+                    &[("value", &self.grid.column_max_width)],
+                ),
+            );
+        }
+        for (action, shortcut) in &self.keybindings {
+            if !crate::keymap::is_valid_shortcut(shortcut) {
+                issue(
+                    &format!("keybindings.{}", action),
+                    tr_args("config.invalid_shortcut", &[("shortcut", shortcut)]),
+                );
+            }
+        }
+
+        issues
+    }
+
+    /// 返回把无效字段替换为默认值后的配置
+    pub fn sanitized(&self) -> Self {
+        let defaults = Self::default();
+        let mut config = self.clone();
+
+        if !FONT_SIZE_RANGE.contains(&config.font_size) {
+            config.font_size = defaults.font_size;
+        }
+        if !(is_positive(config.window_width) && is_positive(config.window_height)) {
+            config.set_window_size(defaults.window_width, defaults.window_height);
+        }
+        if !SUPPORTED_LANGUAGES.contains(&config.language.as_str()) {
+            config.language = defaults.language;
+        }
+        if config.editor.autosave_interval_secs < MIN_AUTOSAVE_INTERVAL {
+            config.editor.autosave_interval_secs = defaults.editor.autosave_interval_secs;
+        }
+        if config.grid.max_rows == 0 {
+            config.grid.max_rows = defaults.grid.max_rows;
+        }
+        if !is_positive(config.grid.column_max_width) {
+            config.grid.column_max_width = defaults.grid.column_max_width;
+        }
+        config
+            .keybindings
+            .retain(|_, shortcut| crate::keymap::is_valid_shortcut(shortcut));
+
+        config
+    }
+
     /// 添加最近文件
     pub fn add_recent_file(&mut self, file_path: &str) {
         // 移除已存在的相同路径
@@ -82,9 +429,7 @@ impl AppConfig {
         self.recent_files.insert(0, file_path.to_string());
 
         // 限制最近文件数量
-        if self.recent_files.len() > 10 {
-            self.recent_files.truncate(10);
-        }
+        self.recent_files.truncate(self.files.max_recent_files);
     }
 
     /// 获取窗口尺寸
@@ -98,3 +443,171 @@ impl AppConfig {
         self.window_height = height;
     }
 }
+
+/// 是否为正数（NaN 不算）
+fn is_positive(value: f32) -> bool {
+    value > 0.0
+}
+
+/// 配置文件监视器
+///
+/// 定期检查配置文件的修改时间，发现外部修改时重新加载。
+pub struct ConfigWatcher {
+    path: PathBuf,
+    last_modified: Option<SystemTime>,
+    last_check: f64,
+}
+
+/// 配置文件的检查间隔（秒）
+const WATCH_INTERVAL: f64 = 1.0;
+
+/// 启动时加载配置失败的原因
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigLoadError {
+    pub error: String,
+    pub backup: Option<Result<PathBuf, String>>, // 解析失败时原文件的备份路径，或备份失败的原因
+}
+
+/// 配置重新加载的结果
+pub enum ConfigReload {
+    /// 成功加载，附带校验问题
+    Loaded(Box<AppConfig>, Vec<ConfigIssue>),
+    /// 文件无法解析，保持当前配置
+    Failed(String),
+}
+
+impl ConfigWatcher {
+    /// 监视当前配置文件
+    pub fn new() -> Self {
+        let path = AppConfig::config_path();
+        let last_modified = Self::modified_time(&path);
+        Self {
+            path,
+            last_modified,
+            last_check: 0.0,
+        }
+    }
+
+    fn modified_time(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    /// 应用自己保存配置后调用，避免把自己的写入当作外部修改
+    pub fn mark_saved(&mut self) {
+        self.last_modified = Self::modified_time(&self.path);
+    }
+
+    /// 检查配置文件是否被修改，`now` 为当前时间（秒）
+    pub fn poll(&mut self, now: f64) -> Option<ConfigReload> {
+        if now - self.last_check < WATCH_INTERVAL {
+            return None;
+        }
+        self.last_check = now;
+
+        let modified = Self::modified_time(&self.path);
+        if modified.is_none() || modified == self.last_modified {
+            return None;
+        }
+        self.last_modified = modified;
+
+        let result = fs::read_to_string(&self.path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| AppConfig::parse(&content));
+        Some(match result {
+            Ok(config) => {
+                let issues = config.validate();
+                ConfigReload::Loaded(Box::new(config), issues)
+            }
+            Err(e) => ConfigReload::Failed(e),
+        })
+    }
+}
+
+impl Default for ConfigWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 将配置 JSON 迁移到当前版本
+fn migrate(mut value: Value) -> Result<Value, String> {
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| tr("config.not_object").to_string())?;
+
+    // 没有 version 字段的是最早期的配置文件
+    let mut version = object.get("version").and_then(Value::as_u64).unwrap_or(0) as usize;
+    if version > CONFIG_VERSION as usize {
+        return Err(tr_args(
+            "config.version_too_new",
+            &[("version", &version), ("supported", &CONFIG_VERSION)],
+        ));
+    }
+
+    while version < CONFIG_VERSION as usize {
+        MIGRATIONS[version](object);
+        version += 1;
+        object.insert("version".to_string(), Value::from(version));
+    }
+
+    Ok(value)
+}
+
+/// v0 -> v1：补充版本号，并把旧的 `zh_CN` 风格语言代码统一为 `zh-CN`
+fn migrate_v0_to_v1(object: &mut serde_json::Map<String, Value>) {
+    if let Some(Value::String(language)) = object.get_mut("language") {
+        *language = language.replace('_', "-");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_unversioned_config() {
+        let config = AppConfig::parse(r#"{"is_dark_mode": true, "language": "en_US"}"#).unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert!(config.is_dark_mode);
+        assert_eq!(config.language, "en-US");
+        assert_eq!(config.font_size, AppConfig::default().font_size);
+    }
+
+    #[test]
+    fn test_reject_newer_config() {
+        let content = format!(r#"{{"version": {}}}"#, CONFIG_VERSION + 1);
+        assert!(AppConfig::parse(&content).is_err());
+    }
+
+    #[test]
+    fn test_validate_out_of_range_values() {
+        let mut config = AppConfig::default();
+        assert!(config.validate().is_empty());
+
+        config.font_size = -3.0;
+        config.language = "fr-FR".to_string();
+        config
+            .keybindings
+            .insert("save_file".to_string(), "Ctrl+Nope".to_string());
+
+        let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+        assert_eq!(fields, ["font_size", "language", "keybindings.save_file"]);
+        assert!(config.sanitized().validate().is_empty());
+    }
+
+    #[test]
+    fn test_unparseable_config_is_backed_up() {
+        let dir = std::env::temp_dir().join(format!("e-config-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CONFIG_FILE_NAME);
+        fs::write(&path, "{ not json").unwrap();
+
+        let (config, error) = AppConfig::load_from(&path);
+        assert_eq!(config, AppConfig::default());
+        let backup = AppConfig::backup_path(&path);
+        assert_eq!(error.unwrap().backup, Some(Ok(backup.clone())));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "{ not json");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,13 +1,22 @@
 //! 数据库模块
 //! 处理数据库连接和操作
 
-use duckdb::{Connection, Result};
+use crate::history::QueryHistory;
+use crate::profile::QueryProfile;
+use duckdb::types::Value;
+use duckdb::{AccessMode, Config, Connection, Result};
+use std::cell::Cell;
 use std::io;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
 /// 自定义数据库错误类型
 #[derive(Debug)]
 pub enum DatabaseError {
     NotConnected,
+    MissingParameter(String), // 没有提供值的绑定参数
     DuckDB(duckdb::Error),
     IO(io::Error),
 }
@@ -28,6 +37,7 @@ impl std::fmt::Display for DatabaseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             DatabaseError::NotConnected => write!(f, "未连接到数据库"),
+            DatabaseError::MissingParameter(name) => write!(f, "缺少参数: {}", name),
             DatabaseError::DuckDB(e) => write!(f, "数据库错误: {}", e),
             DatabaseError::IO(e) => write!(f, "IO错误: {}", e),
         }
@@ -36,10 +46,159 @@ impl std::fmt::Display for DatabaseError {
 
 impl std::error::Error for DatabaseError {}
 
+/// 查询结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    pub truncated: bool,               // 是否因为超过最大行数而没有读完
+    pub profile: Option<QueryProfile>, // 执行的性能信息
+}
+
+/// 语句的绑定参数
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum QueryParams {
+    #[default]
+    None,
+    Positional(Vec<Value>),      // `?` 和 `$1`，按序号绑定
+    Named(Vec<(String, Value)>), // `$name`，按名称绑定
+}
+
+impl QueryParams {
+    /// 按语句中参数的顺序排列参数值
+    fn resolve(&self, stmt: &duckdb::Statement) -> Result<Vec<Value>, DatabaseError> {
+        (1..=stmt.parameter_count())
+            .map(|i| {
+                let name = stmt.parameter_name(i)?;
+                let value = match self {
+                    QueryParams::None => None,
+                    QueryParams::Positional(values) => name
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|n| values.get(n.checked_sub(1)?)),
+                    QueryParams::Named(values) => values
+                        .iter()
+                        .find(|(key, _)| *key == name)
+                        .map(|(_, value)| value),
+                };
+                value.cloned().ok_or(DatabaseError::MissingParameter(name))
+            })
+            .collect()
+    }
+}
+
+/// 表名，可以带数据库和模式名
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableName {
+    pub database: Option<String>,
+    pub schema: Option<String>,
+    pub name: String,
+}
+
+impl TableName {
+    /// 解析 SQL 中的表名，如 `t`、`main.t`、`db."My Table"`
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = Vec::new();
+        let mut current = String::new();
+        let mut quoted = false;
+        let mut chars = text.trim().chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    current.push('"');
+                }
+                '"' => quoted = !quoted,
+                '.' if !quoted => parts.push(std::mem::take(&mut current)),
+                _ => current.push(c),
+            }
+        }
+        parts.push(current);
+
+        if quoted || parts.len() > 3 || parts.iter().any(|p| p.is_empty()) {
+            return None;
+        }
+        let name = parts.pop()?;
+        let schema = parts.pop();
+        let database = parts.pop();
+        Some(Self {
+            database,
+            schema,
+            name,
+        })
+    }
+}
+
+impl std::fmt::Display for TableName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for part in [&self.database, &self.schema].into_iter().flatten() {
+            write!(f, "{}.", quote_identifier(part))?;
+        }
+        write!(f, "{}", quote_identifier(&self.name))
+    }
+}
+
+/// 数据库对象的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectKind {
+    Table,
+    View,
+    Index,
+    Sequence,
+    Macro,
+}
+
+impl ObjectKind {
+    pub const ALL: [ObjectKind; 5] = [
+        ObjectKind::Table,
+        ObjectKind::View,
+        ObjectKind::Index,
+        ObjectKind::Sequence,
+        ObjectKind::Macro,
+    ];
+
+    /// 解析 `list_objects` 查询中的类型名
+    fn parse(kind: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.name() == kind)
+    }
+
+    /// 类型名，与 `DROP` 等语句中的关键字对应
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObjectKind::Table => "table",
+            ObjectKind::View => "view",
+            ObjectKind::Index => "index",
+            ObjectKind::Sequence => "sequence",
+            ObjectKind::Macro => "macro",
+        }
+    }
+}
+
+/// 数据库中的对象，名称带数据库和模式名
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbObject {
+    pub kind: ObjectKind,
+    pub name: TableName,
+}
+
+/// 表中一列的信息
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnInfo {
+    pub name: String,
+    pub data_type: String,
+    pub nullable: bool,
+    pub default: Option<String>, // 默认值表达式
+    pub primary_key: bool,       // 是否属于主键
+}
+
 /// 数据库管理器
 pub struct DatabaseManager {
     connection: Option<Connection>,
     current_db_path: Option<String>,
+    history: Option<Rc<QueryHistory>>, // 记录执行过的语句
+    auto_commit: bool,                 // 为 false 时语句在显式事务中执行，需要手动提交
+    in_transaction: Cell<bool>,        // 是否有未结束的事务
+    profile_path: Option<PathBuf>,     // 开启性能分析时 DuckDB 写入统计信息的文件
 }
 
 impl DatabaseManager {
@@ -48,12 +207,47 @@ impl DatabaseManager {
         Self {
             connection: None,
             current_db_path: None,
+            history: None,
+            auto_commit: true,
+            in_transaction: Cell::new(false),
+            profile_path: None,
+        }
+    }
+
+    /// 设置查询历史，之后执行的语句都会被记录
+    pub fn set_history(&mut self, history: Rc<QueryHistory>) {
+        self.history = Some(history);
+    }
+
+    /// 记录一条执行过的语句
+    fn record(
+        &self,
+        sql: &str,
+        duration: Duration,
+        outcome: Result<Option<usize>, String>,
+        profile: Option<&QueryProfile>,
+    ) {
+        if let Some(history) = &self.history {
+            let connection = self.current_db_path.as_deref().unwrap_or_default();
+            history.record_profiled(connection, sql, duration, outcome, profile);
         }
     }
 
     /// 连接到数据库
     pub fn connect(&mut self, db_path: &str) -> Result<()> {
-        match Connection::open(db_path) {
+        self.connect_with_access(db_path, false)
+    }
+
+    /// 连接到数据库，可选择只读模式
+    pub fn connect_with_access(&mut self, db_path: &str, read_only: bool) -> Result<()> {
+        let access_mode = if read_only {
+            AccessMode::ReadOnly
+        } else {
+            AccessMode::Automatic
+        };
+        let config = Config::default().access_mode(access_mode)?;
+
+        match Connection::open_with_flags(db_path, config) {
             Ok(conn) => {
                 self.connection = Some(conn);
                 self.current_db_path = Some(db_path.to_string());
@@ -70,18 +264,369 @@ impl DatabaseManager {
     /// 断开数据库连接
     pub fn disconnect(&mut self) {
         self.connection = None;
+        self.remove_profile_output();
+        self.in_transaction.set(false);
         self.current_db_path = None;
         println!("已断开数据库连接");
     }
 
     /// 执行 SQL 查询
     pub fn execute_query(&self, query: &str) -> Result<(), DatabaseError> {
-        if let Some(ref conn) = self.connection {
-            conn.execute(query, [])?;
-            println!("执行查询: {}", query);
-            Ok(())
+        self.execute_with_params(query, &QueryParams::None)?;
+        println!("执行查询: {}", query);
+        Ok(())
+    }
+
+    /// 绑定参数后执行语句，返回影响的行数
+    ///
+    /// 带参数的语句会缓存预处理结果，重复执行时不再重新解析。
+    pub fn execute_with_params(
+        &self,
+        sql: &str,
+        params: &QueryParams,
+    ) -> Result<usize, DatabaseError> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or(DatabaseError::NotConnected)?;
+        let started = Instant::now();
+        let result = self.begin_if_manual(conn, sql);
+        let result = result.and_then(|()| {
+            if *params == QueryParams::None {
+                conn.execute(sql, []).map_err(DatabaseError::from)
+            } else {
+                let mut stmt = conn.prepare_cached(sql)?;
+                let values = params.resolve(&stmt)?;
+                Ok(stmt.execute(duckdb::params_from_iter(values))?)
+            }
+        });
+        self.track_transaction(sql, result.is_ok());
+        self.record(
+            sql,
+            started.elapsed(),
+            result.as_ref().map(|&n| Some(n)).map_err(|e| e.to_string()),
+            None,
+        );
+        result
+    }
+
+    /// 在一个事务中依次执行语句，返回影响的总行数
+    ///
+    /// 任何一条语句失败时回滚本次开启的事务。已经在用户的事务中时沿用当前事务，
+    /// 失败时不回滚，以免撤销事务中与这些语句无关的修改，由用户决定提交还是回滚。
+    pub fn execute_in_transaction(&self, statements: &[String]) -> Result<usize, DatabaseError> {
+        let had_transaction = self.in_transaction.get();
+        let own_transaction = self.auto_commit && !had_transaction;
+        if own_transaction {
+            self.begin_transaction()?;
+        }
+
+        let mut affected = 0;
+        for statement in statements {
+            match self.execute_with_params(statement, &QueryParams::None) {
+                Ok(n) => affected += n,
+                Err(e) => {
+                    if !had_transaction && self.in_transaction.get() {
+                        let _ = self.rollback();
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        if own_transaction {
+            self.commit()?;
+        }
+        Ok(affected)
+    }
+
+    /// 读取表的列信息，按列的顺序排列，表不存在时返回空列表
+    ///
+    /// 没有指定数据库和模式时使用当前的数据库和模式，名称不区分大小写。
+    pub fn table_columns(&self, table: &TableName) -> Result<Vec<ColumnInfo>, DatabaseError> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or(DatabaseError::NotConnected)?;
+        let mut stmt = conn.prepare_cached(
+            "SELECT c.column_name, c.data_type, c.is_nullable, c.column_default,
+                    coalesce(list_contains(k.columns, c.column_name), false)
+             FROM duckdb_columns() c
+             LEFT JOIN (
+                 SELECT database_name, schema_name, table_name,
+                        constraint_column_names AS columns
+                 FROM duckdb_constraints()
+                 WHERE constraint_type = 'PRIMARY KEY'
+             ) k USING (database_name, schema_name, table_name)
+             WHERE lower(c.table_name) = lower(?)
+               AND lower(c.schema_name) = lower(coalesce(?, current_schema()))
+               AND lower(c.database_name) = lower(coalesce(?, current_database()))
+             ORDER BY c.column_index",
+        )?;
+        let columns = stmt
+            .query_map(
+                duckdb::params![table.name, table.schema, table.database],
+                |row| {
+                    Ok(ColumnInfo {
+                        name: row.get(0)?,
+                        data_type: row.get(1)?,
+                        nullable: row.get(2)?,
+                        default: row.get(3)?,
+                        primary_key: row.get(4)?,
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(columns)
+    }
+
+    /// 语句中的绑定参数名称，按序号排列
+    ///
+    /// `?` 和 `$1` 的名称是序号，`$name` 的名称不带 `$`。
+    pub fn parameters(&self, sql: &str) -> Result<Vec<String>, DatabaseError> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or(DatabaseError::NotConnected)?;
+        let stmt = conn.prepare_cached(sql)?;
+        let names = (1..=stmt.parameter_count())
+            .map(|i| stmt.parameter_name(i))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(names)
+    }
+
+    /// 执行一条语句并读取结果，最多读取 `max_rows` 行
+    pub fn run(&self, sql: &str, max_rows: usize) -> Result<QueryResult, DatabaseError> {
+        self.run_with_params(sql, &QueryParams::None, max_rows)
+    }
+
+    /// 绑定参数后执行一条语句并读取结果
+    pub fn run_with_params(
+        &self,
+        sql: &str,
+        params: &QueryParams,
+        max_rows: usize,
+    ) -> Result<QueryResult, DatabaseError> {
+        if let Some(path) = &self.profile_path {
+            // 删除上一条语句的统计信息，避免语句失败时读到旧数据
+            let _ = std::fs::remove_file(path);
+        }
+        let started = Instant::now();
+        let mut result = self.run_statement(sql, params, max_rows);
+        let elapsed = started.elapsed();
+        self.track_transaction(sql, result.is_ok());
+
+        match &mut result {
+            Ok(r) => {
+                let mut profile = QueryProfile::new(elapsed, r.rows.len());
+                if let Some(text) = self
+                    .profile_path
+                    .as_ref()
+                    .and_then(|path| std::fs::read_to_string(path).ok())
+                {
+                    profile = profile.with_profiling_output(&text);
+                }
+                self.record(sql, elapsed, Ok(Some(r.rows.len())), Some(&profile));
+                r.profile = Some(profile);
+            }
+            Err(e) => self.record(sql, elapsed, Err(e.to_string()), None),
+        }
+        result
+    }
+
+    /// 执行界面内部使用的查询并读取结果，不记录历史和性能信息
+    pub fn fetch(
+        &self,
+        sql: &str,
+        params: &QueryParams,
+        max_rows: usize,
+    ) -> Result<QueryResult, DatabaseError> {
+        self.run_statement(sql, params, max_rows)
+    }
+
+    /// 把查询结果以文本写入新表，NULL 保持为 NULL
+    ///
+    /// 用于在另一个连接中处理结果，例如比较来自不同数据库的查询结果。
+    pub fn create_text_table(&self, name: &str, result: &QueryResult) -> Result<(), DatabaseError> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or(DatabaseError::NotConnected)?;
+        let columns: Vec<String> = result
+            .columns
+            .iter()
+            .map(|column| format!("{} VARCHAR", quote_identifier(column)))
+            .collect();
+        conn.execute_batch(&format!(
+            "CREATE TABLE {} ({})",
+            quote_identifier(name),
+            columns.join(", ")
+        ))?;
+        let mut appender = conn.appender(name)?;
+        for values in &result.rows {
+            appender.append_row(duckdb::appender_params_from_iter(values.iter().map(
+                |value| match value {
+                    Value::Null => None,
+                    value => Some(format_value(value)),
+                },
+            )))?;
+        }
+        Ok(())
+    }
+
+    fn run_statement(
+        &self,
+        sql: &str,
+        params: &QueryParams,
+        max_rows: usize,
+    ) -> Result<QueryResult, DatabaseError> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or(DatabaseError::NotConnected)?;
+        self.begin_if_manual(conn, sql)?;
+        if *params == QueryParams::None {
+            let mut stmt = conn.prepare(sql)?;
+            read_rows(&mut stmt, Vec::new(), max_rows)
         } else {
-            Err(DatabaseError::NotConnected)
+            let mut stmt = conn.prepare_cached(sql)?;
+            let values = params.resolve(&stmt)?;
+            read_rows(&mut stmt, values, max_rows)
+        }
+    }
+
+    /// 开启或关闭 DuckDB 的性能分析
+    ///
+    /// 开启后每条语句的统计信息以 JSON 写入临时文件，执行后读取到 `QueryResult::profile`。
+    pub fn set_profiling(&mut self, enabled: bool) -> Result<(), DatabaseError> {
+        if enabled == self.profile_path.is_some() {
+            return Ok(());
+        }
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or(DatabaseError::NotConnected)?;
+
+        if enabled {
+            static NEXT_FILE: AtomicUsize = AtomicUsize::new(0);
+            let path = std::env::temp_dir().join(format!(
+                "e-profile-{}-{}.json",
+                std::process::id(),
+                NEXT_FILE.fetch_add(1, Ordering::Relaxed)
+            ));
+            conn.execute_batch(&format!(
+                "SET enable_profiling = 'json'; SET profiling_output = '{}';",
+                path.to_string_lossy().replace('\'', "''")
+            ))?;
+            self.profile_path = Some(path);
+        } else {
+            conn.execute_batch("PRAGMA disable_profiling;")?;
+            self.remove_profile_output();
+        }
+        Ok(())
+    }
+
+    /// 是否开启了性能分析
+    pub fn profiling(&self) -> bool {
+        self.profile_path.is_some()
+    }
+
+    /// 删除性能分析的临时文件
+    fn remove_profile_output(&mut self) {
+        if let Some(path) = self.profile_path.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// 获取语句的执行计划（`EXPLAIN` 的 JSON 输出）
+    ///
+    /// `analyze` 为 true 时使用 `EXPLAIN ANALYZE`，语句会被实际执行并统计每个算子的耗时和行数。
+    pub fn explain(&self, sql: &str, analyze: bool) -> Result<String, DatabaseError> {
+        let options = if analyze {
+            "ANALYZE, FORMAT JSON"
+        } else {
+            "FORMAT JSON"
+        };
+        let result = self.run(&format!("EXPLAIN ({}) {}", options, sql), 1)?;
+        let plan = result
+            .rows
+            .first()
+            .and_then(|row| row.last())
+            .map(format_value)
+            .unwrap_or_default();
+        Ok(plan)
+    }
+
+    /// 是否自动提交
+    pub fn auto_commit(&self) -> bool {
+        self.auto_commit
+    }
+
+    /// 设置自动提交
+    ///
+    /// 关闭后第一条语句会开启事务，直到调用 [`commit`](Self::commit)
+    /// 或 [`rollback`](Self::rollback)。已经开启的事务不受影响。
+    pub fn set_auto_commit(&mut self, auto_commit: bool) {
+        self.auto_commit = auto_commit;
+    }
+
+    /// 是否有未结束的事务（包括在编辑器中执行的 `BEGIN`）
+    pub fn in_transaction(&self) -> bool {
+        self.in_transaction.get()
+    }
+
+    /// 开启事务
+    pub fn begin_transaction(&self) -> Result<(), DatabaseError> {
+        self.transaction_command("BEGIN TRANSACTION")
+    }
+
+    /// 提交事务
+    pub fn commit(&self) -> Result<(), DatabaseError> {
+        self.transaction_command("COMMIT")
+    }
+
+    /// 回滚事务
+    pub fn rollback(&self) -> Result<(), DatabaseError> {
+        self.transaction_command("ROLLBACK")
+    }
+
+    fn transaction_command(&self, sql: &str) -> Result<(), DatabaseError> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or(DatabaseError::NotConnected)?;
+        let started = Instant::now();
+        let result = conn.execute_batch(sql);
+        self.track_transaction(sql, result.is_ok());
+        self.record(
+            sql,
+            started.elapsed(),
+            result.as_ref().map(|_| None).map_err(|e| e.to_string()),
+            None,
+        );
+        Ok(result?)
+    }
+
+    /// 手动提交模式下，在执行语句前开启事务
+    ///
+    /// 语句本身是事务控制语句时不自动开启。
+    fn begin_if_manual(&self, conn: &Connection, sql: &str) -> Result<(), DatabaseError> {
+        if self.auto_commit || self.in_transaction.get() || transaction_control(sql).is_some() {
+            return Ok(());
+        }
+        conn.execute_batch("BEGIN TRANSACTION")?;
+        self.in_transaction.set(true);
+        Ok(())
+    }
+
+    /// 根据执行过的事务控制语句更新事务状态
+    ///
+    /// `COMMIT` 和 `ROLLBACK` 失败时事务也已经结束（没有事务或被 DuckDB 回滚）。
+    fn track_transaction(&self, sql: &str, succeeded: bool) {
+        match transaction_control(sql) {
+            Some(true) if succeeded => self.in_transaction.set(true),
+            Some(false) => self.in_transaction.set(false),
+            _ => {}
         }
     }
 
@@ -102,7 +647,7 @@ impl DatabaseManager {
                 conn.prepare("SELECT name FROM sqlite_master WHERE type='table' ORDER BY name")?;
 
             let tables = stmt
-                .query_map([], |row| Ok(row.get::<_, String>(0)?))?
+                .query_map([], |row| row.get::<_, String>(0))?
                 .collect::<Result<Vec<String>>>()?;
 
             Ok(tables)
@@ -111,6 +656,56 @@ impl DatabaseManager {
         }
     }
 
+    /// 读取所有用户创建的表、视图、索引、序列和宏，按数据库、模式和名称排序
+    pub fn list_objects(&self) -> Result<Vec<DbObject>, DatabaseError> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or(DatabaseError::NotConnected)?;
+        let mut stmt = conn.prepare_cached(
+            "SELECT * FROM (
+                 SELECT 'table' AS kind, database_name, schema_name, table_name AS name
+                 FROM duckdb_tables() WHERE NOT internal
+                 UNION ALL
+                 SELECT 'view', database_name, schema_name, view_name
+                 FROM duckdb_views() WHERE NOT internal
+                 UNION ALL
+                 SELECT 'index', database_name, schema_name, index_name
+                 FROM duckdb_indexes()
+                 UNION ALL
+                 SELECT 'sequence', database_name, schema_name, sequence_name
+                 FROM duckdb_sequences()
+                 UNION ALL
+                 SELECT DISTINCT 'macro', database_name, schema_name, function_name
+                 FROM duckdb_functions()
+                 WHERE NOT internal AND function_type IN ('macro', 'table_macro')
+             )
+             ORDER BY database_name, schema_name, name",
+        )?;
+        let objects = stmt
+            .query_map([], |row| {
+                let kind: String = row.get(0)?;
+                Ok((
+                    kind,
+                    TableName {
+                        database: Some(row.get(1)?),
+                        schema: Some(row.get(2)?),
+                        name: row.get(3)?,
+                    },
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(objects
+            .into_iter()
+            .filter_map(|(kind, name)| {
+                Some(DbObject {
+                    kind: ObjectKind::parse(&kind)?,
+                    name,
+                })
+            })
+            .collect())
+    }
+
     /// 执行查询并返回结果
     pub fn query(&self, sql: &str) -> Result<Vec<Vec<String>>, DatabaseError> {
         if let Some(ref conn) = self.connection {
@@ -135,8 +730,568 @@ impl DatabaseManager {
     }
 }
 
+impl Drop for DatabaseManager {
+    fn drop(&mut self) {
+        self.remove_profile_output();
+    }
+}
+
 impl Default for DatabaseManager {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// 事务控制语句开启（`Some(true)`）或结束（`Some(false)`）事务，其他语句为 `None`
+fn transaction_control(sql: &str) -> Option<bool> {
+    let first = sql
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .find(|word| !word.is_empty())
+        .unwrap_or_default()
+        .to_uppercase();
+    match first.as_str() {
+        "BEGIN" | "START" => Some(true),
+        "COMMIT" | "END" | "ROLLBACK" | "ABORT" => Some(false),
+        _ => None,
+    }
+}
+
+/// 执行语句并读取最多 `max_rows` 行结果
+fn read_rows(
+    stmt: &mut duckdb::Statement,
+    values: Vec<Value>,
+    max_rows: usize,
+) -> Result<QueryResult, DatabaseError> {
+    let mut rows = stmt.query(duckdb::params_from_iter(values))?;
+
+    // 列信息只有在语句执行后才能读取
+    let columns = rows
+        .as_ref()
+        .map(|stmt| stmt.column_names())
+        .unwrap_or_default();
+    let mut result = QueryResult {
+        columns,
+        ..QueryResult::default()
+    };
+
+    while let Some(row) = rows.next()? {
+        if result.rows.len() >= max_rows {
+            result.truncated = true;
+            break;
+        }
+        let values = (0..result.columns.len())
+            .map(|i| row.get::<_, Value>(i))
+            .collect::<Result<Vec<_>>>()?;
+        result.rows.push(values);
+    }
+
+    Ok(result)
+}
+
+/// 把查询结果中的值格式化为文本，NULL 显示为 `NULL`
+pub fn format_value(value: &Value) -> String {
+    match value {
+        Value::Null => "NULL".to_string(),
+        Value::Boolean(v) => v.to_string(),
+        Value::TinyInt(v) => v.to_string(),
+        Value::SmallInt(v) => v.to_string(),
+        Value::Int(v) => v.to_string(),
+        Value::BigInt(v) => v.to_string(),
+        Value::HugeInt(v) => v.to_string(),
+        Value::UHugeInt(v) => v.to_string(),
+        Value::UTinyInt(v) => v.to_string(),
+        Value::USmallInt(v) => v.to_string(),
+        Value::UInt(v) => v.to_string(),
+        Value::UBigInt(v) => v.to_string(),
+        Value::Float(v) => v.to_string(),
+        Value::Double(v) => v.to_string(),
+        Value::Decimal(v) => v.to_string(),
+        Value::Timestamp(unit, v) => chrono::DateTime::from_timestamp_micros(unit.to_micros(*v))
+            .map(|t| t.naive_utc().to_string())
+            .unwrap_or_else(|| v.to_string()),
+        Value::Text(v) | Value::Enum(v) => v.clone(),
+        Value::Blob(bytes) | Value::Geometry(bytes) => {
+            bytes.iter().map(|b| format!("\\x{:02X}", b)).collect()
+        }
+        Value::Date32(days) => chrono::NaiveDate::from_epoch_days(*days)
+            .map(|d| d.to_string())
+            .unwrap_or_else(|| days.to_string()),
+        Value::Time64(unit, v) => {
+            let micros = unit.to_micros(*v);
+            chrono::NaiveTime::from_num_seconds_from_midnight_opt(
+                (micros / 1_000_000) as u32,
+                (micros % 1_000_000) as u32 * 1000,
+            )
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| v.to_string())
+        }
+        Value::Interval {
+            months,
+            days,
+            nanos,
+        } => format!("{} months {} days {} us", months, days, nanos / 1000),
+        Value::List(items) | Value::Array(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(format_nested)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Struct(fields) => format!(
+            "{{{}}}",
+            fields
+                .iter()
+                .map(|(k, v)| format!("'{}': {}", k, format_nested(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Map(entries) => format!(
+            "{{{}}}",
+            entries
+                .iter()
+                .map(|(k, v)| format!("{}={}", format_nested(k), format_nested(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Union(v) => format_value(v),
+        other => format!("{:?}", other),
+    }
+}
+
+/// 把值写成 SQL 字面量
+///
+/// 数值和布尔值直接写出，其它类型写成字符串，由 DuckDB 转换为目标列的类型。
+pub fn sql_literal(value: &Value) -> String {
+    match value {
+        Value::Null => "NULL".to_string(),
+        Value::Boolean(_)
+        | Value::TinyInt(_)
+        | Value::SmallInt(_)
+        | Value::Int(_)
+        | Value::BigInt(_)
+        | Value::HugeInt(_)
+        | Value::UHugeInt(_)
+        | Value::UTinyInt(_)
+        | Value::USmallInt(_)
+        | Value::UInt(_)
+        | Value::UBigInt(_)
+        | Value::Decimal(_) => format_value(value),
+        Value::Float(v) if v.is_finite() => format_value(value),
+        Value::Double(v) if v.is_finite() => format_value(value),
+        _ => quote_string(&format_value(value)),
+    }
+}
+
+/// 把文本写成 SQL 字符串字面量
+pub fn quote_string(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// 给标识符加上双引号
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// 不需要加引号的标识符：小写字母或下划线开头，只包含小写字母、数字和下划线
+pub fn is_plain_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// DuckDB 中不能直接用作标识符的关键字，即 `duckdb_keywords()` 中类别不是
+/// `unreserved` 的关键字，按字母顺序排列
+const RESERVED_WORDS: &[&str] = &[
+    "all",
+    "analyse",
+    "analyze",
+    "and",
+    "anti",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "asof",
+    "asymmetric",
+    "at",
+    "authorization",
+    "between",
+    "bigint",
+    "binary",
+    "bit",
+    "boolean",
+    "both",
+    "by",
+    "case",
+    "cast",
+    "char",
+    "character",
+    "check",
+    "coalesce",
+    "collate",
+    "collation",
+    "column",
+    "columns",
+    "concurrently",
+    "constraint",
+    "create",
+    "cross",
+    "dec",
+    "decimal",
+    "default",
+    "deferrable",
+    "desc",
+    "describe",
+    "distinct",
+    "do",
+    "else",
+    "end",
+    "except",
+    "exists",
+    "extract",
+    "false",
+    "fetch",
+    "float",
+    "for",
+    "foreign",
+    "freeze",
+    "from",
+    "full",
+    "generated",
+    "glob",
+    "group",
+    "grouping",
+    "grouping_id",
+    "having",
+    "ilike",
+    "in",
+    "initially",
+    "inner",
+    "inout",
+    "int",
+    "integer",
+    "intersect",
+    "interval",
+    "into",
+    "is",
+    "isnull",
+    "join",
+    "lambda",
+    "lateral",
+    "leading",
+    "left",
+    "like",
+    "limit",
+    "map",
+    "national",
+    "natural",
+    "nchar",
+    "none",
+    "not",
+    "notnull",
+    "null",
+    "nullif",
+    "numeric",
+    "offset",
+    "on",
+    "only",
+    "or",
+    "order",
+    "out",
+    "outer",
+    "overlaps",
+    "overlay",
+    "pivot",
+    "pivot_longer",
+    "pivot_wider",
+    "placing",
+    "position",
+    "positional",
+    "precision",
+    "primary",
+    "qualify",
+    "real",
+    "references",
+    "returning",
+    "right",
+    "row",
+    "select",
+    "semi",
+    "setof",
+    "show",
+    "similar",
+    "smallint",
+    "some",
+    "struct",
+    "substring",
+    "summarize",
+    "symmetric",
+    "table",
+    "tablesample",
+    "then",
+    "time",
+    "timestamp",
+    "to",
+    "trailing",
+    "treat",
+    "trim",
+    "true",
+    "try_cast",
+    "union",
+    "unique",
+    "unpack",
+    "unpivot",
+    "using",
+    "values",
+    "varchar",
+    "variadic",
+    "verbose",
+    "when",
+    "where",
+    "window",
+    "with",
+    "xmlattributes",
+    "xmlconcat",
+    "xmlelement",
+    "xmlexists",
+    "xmlforest",
+    "xmlnamespaces",
+    "xmlparse",
+    "xmlpi",
+    "xmlroot",
+    "xmlserialize",
+    "xmltable",
+];
+
+/// 标识符，需要时才加引号，用于生成便于阅读的 SQL
+pub fn identifier(name: &str) -> String {
+    if is_plain_identifier(name) && RESERVED_WORDS.binary_search(&name).is_err() {
+        name.to_string()
+    } else {
+        quote_identifier(name)
+    }
+}
+
+/// 嵌套值中的字符串加上引号
+fn format_nested(value: &Value) -> String {
+    match value {
+        Value::Text(v) | Value::Enum(v) => format!("'{}'", v),
+        _ => format_value(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_reads_columns_and_truncates_rows() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        let history = Rc::new(QueryHistory::open_in_memory().unwrap());
+        manager.set_history(Rc::clone(&history));
+
+        let result = manager
+            .run(
+                "SELECT i AS n, NULL AS empty, [i, 2] AS list FROM range(5) t(i)",
+                3,
+            )
+            .unwrap();
+        assert_eq!(result.columns, ["n", "empty", "list"]);
+        assert_eq!(result.rows.len(), 3);
+        assert!(result.truncated);
+        assert_eq!(format_value(&result.rows[1][0]), "1");
+        assert_eq!(result.rows[1][1], Value::Null);
+        assert_eq!(format_value(&result.rows[1][2]), "[1, 2]");
+
+        assert!(manager.run("SELECT * FROM missing_table", 10).is_err());
+        let entries = history.search("", 10).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(!entries[0].success);
+        assert_eq!(entries[1].row_count, Some(3));
+    }
+
+    #[test]
+    fn binds_positional_and_named_parameters() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        manager
+            .execute_query("CREATE TABLE t (id INTEGER, name VARCHAR)")
+            .unwrap();
+
+        let insert = "INSERT INTO t VALUES (?, ?)";
+        for (id, name) in [(1, "a"), (2, "b"), (3, "c")] {
+            let params = QueryParams::Positional(vec![Value::Int(id), Value::Text(name.into())]);
+            assert_eq!(manager.execute_with_params(insert, &params).unwrap(), 1);
+        }
+
+        let sql = "SELECT name FROM t WHERE id >= $min AND name <> $skip ORDER BY id";
+        assert_eq!(manager.parameters(sql).unwrap(), ["min", "skip"]);
+        let params = QueryParams::Named(vec![
+            ("skip".to_string(), Value::Text("c".into())),
+            ("min".to_string(), Value::BigInt(2)),
+        ]);
+        let result = manager.run_with_params(sql, &params, 10).unwrap();
+        assert_eq!(result.rows, [[Value::Text("b".into())]]);
+
+        let missing = QueryParams::Named(vec![("min".to_string(), Value::BigInt(1))]);
+        assert!(matches!(
+            manager.run_with_params(sql, &missing, 10),
+            Err(DatabaseError::MissingParameter(name)) if name == "skip"
+        ));
+    }
+
+    #[test]
+    fn manual_commit_mode_keeps_changes_until_commit() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        manager
+            .execute_query("CREATE TABLE t (id INTEGER)")
+            .unwrap();
+        manager.set_auto_commit(false);
+        assert!(!manager.in_transaction());
+
+        manager.execute_query("INSERT INTO t VALUES (1)").unwrap();
+        assert!(manager.in_transaction());
+        manager.rollback().unwrap();
+        assert!(!manager.in_transaction());
+        assert!(manager.run("SELECT * FROM t", 10).unwrap().rows.is_empty());
+
+        manager.run("INSERT INTO t VALUES (2)", 10).unwrap();
+        manager.commit().unwrap();
+        manager.set_auto_commit(true);
+        manager.execute_query("BEGIN").unwrap();
+        assert!(manager.in_transaction());
+        manager.execute_query("COMMIT").unwrap();
+        assert_eq!(manager.run("SELECT * FROM t", 10).unwrap().rows.len(), 1);
+    }
+
+    #[test]
+    fn explain_returns_json_plan() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        manager
+            .execute_query("CREATE TABLE t AS SELECT range AS i FROM range(100)")
+            .unwrap();
+
+        let sql = "SELECT count(*) FROM t WHERE i > 10";
+        let plan = manager.explain(sql, false).unwrap();
+        assert!(plan.trim_start().starts_with('['));
+        let analyzed = manager.explain(sql, true).unwrap();
+        assert!(analyzed.contains("operator_timing"));
+    }
+
+    #[test]
+    fn profiling_attaches_metrics_to_results() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        manager.set_profiling(true).unwrap();
+
+        let result = manager.run("SELECT range FROM range(10)", 100).unwrap();
+        let profile = result.profile.unwrap();
+        assert_eq!(profile.rows, 10);
+        assert!(profile.latency.is_some());
+        assert!(profile.plan.is_some());
+
+        manager.set_profiling(false).unwrap();
+        let result = manager.run("SELECT 1", 100).unwrap();
+        assert_eq!(result.profile.unwrap().latency, None);
+    }
+
+    #[test]
+    fn reads_table_columns_and_parses_names() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        manager
+            .execute_query(
+                "CREATE TABLE items (id INTEGER PRIMARY KEY, name VARCHAR NOT NULL DEFAULT 'x', price DOUBLE)",
+            )
+            .unwrap();
+
+        let table = TableName::parse("main.\"ITEMS\"").unwrap();
+        assert_eq!(table.schema.as_deref(), Some("main"));
+        assert_eq!(table.to_string(), "\"main\".\"ITEMS\"");
+        let columns = manager.table_columns(&table).unwrap();
+        let names: Vec<_> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["id", "name", "price"]);
+        assert!(columns[0].primary_key && !columns[0].nullable);
+        assert!(!columns[1].primary_key);
+        assert_eq!(columns[1].default.as_deref(), Some("'x'"));
+        assert!(TableName::parse("a.b.c.d").is_none());
+    }
+
+    #[test]
+    fn failed_statement_rolls_back_transaction() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        manager
+            .execute_query("CREATE TABLE t (id INTEGER PRIMARY KEY)")
+            .unwrap();
+
+        let statements = [
+            "INSERT INTO t VALUES (1)".to_string(),
+            "INSERT INTO t VALUES (1)".to_string(),
+        ];
+        assert!(manager.execute_in_transaction(&statements).is_err());
+        assert!(!manager.in_transaction());
+        assert!(manager.run("SELECT * FROM t", 10).unwrap().rows.is_empty());
+
+        assert_eq!(manager.execute_in_transaction(&statements[..1]).unwrap(), 1);
+        assert_eq!(manager.run("SELECT * FROM t", 10).unwrap().rows.len(), 1);
+        assert_eq!(sql_literal(&Value::Text("it's".into())), "'it''s'");
+
+        // 手动提交模式下已有的事务由用户处理，失败时不回滚
+        manager.set_auto_commit(false);
+        manager.execute_query("INSERT INTO t VALUES (2)").unwrap();
+        assert!(manager.in_transaction());
+        assert!(manager.execute_in_transaction(&statements[..1]).is_err());
+        assert!(manager.in_transaction());
+    }
+
+    #[test]
+    fn identifiers_quote_reserved_words() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+
+        // 与 DuckDB 的关键字列表一致
+        let keywords = manager
+            .fetch(
+                "SELECT keyword_name FROM duckdb_keywords()
+                 WHERE keyword_category <> 'unreserved'
+                 ORDER BY keyword_name",
+                &QueryParams::None,
+                usize::MAX,
+            )
+            .unwrap();
+        let keywords: Vec<String> = keywords.rows.iter().map(|r| format_value(&r[0])).collect();
+        assert_eq!(keywords, RESERVED_WORDS);
+
+        let columns = ["for", "array", "value", "Name"];
+        let names: Vec<String> = columns.iter().map(|c| identifier(c)).collect();
+        assert_eq!(names, ["\"for\"", "\"array\"", "value", "\"Name\""]);
+        manager
+            .execute_query(&format!(
+                "CREATE TABLE t ({} INTEGER)",
+                names.join(" INTEGER, ")
+            ))
+            .unwrap();
+        manager
+            .execute_query(&format!(
+                "INSERT INTO t ({}) VALUES (1, 2, 3, 4)",
+                names.join(", ")
+            ))
+            .unwrap();
+        let result = manager
+            .fetch(
+                &format!("SELECT {} FROM t", names.join(", ")),
+                &QueryParams::None,
+                10,
+            )
+            .unwrap();
+        assert_eq!(result.columns, columns);
+        assert_eq!(result.rows.len(), 1);
+    }
+}
@@ -0,0 +1,356 @@
+//! SQL 生成模块
+//! 根据目录函数中的定义生成对象的建立语句，以及列出所有列的查询和修改语句模板
+
+use crate::database::{
+    ColumnInfo, DatabaseError, DatabaseManager, DbObject, ObjectKind, QueryParams, TableName,
+    format_value, identifier,
+};
+use crate::schema::macro_definition;
+use duckdb::types::Value;
+
+/// 可以生成的语句
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlTemplate {
+    Create,
+    Select,
+    Insert,
+    Update,
+    Delete,
+}
+
+impl SqlTemplate {
+    pub const ALL: [SqlTemplate; 5] = [
+        SqlTemplate::Create,
+        SqlTemplate::Select,
+        SqlTemplate::Insert,
+        SqlTemplate::Update,
+        SqlTemplate::Delete,
+    ];
+
+    /// 翻译键
+    pub fn label(&self) -> &'static str {
+        match self {
+            SqlTemplate::Create => "ddl.create",
+            SqlTemplate::Select => "ddl.select",
+            SqlTemplate::Insert => "ddl.insert",
+            SqlTemplate::Update => "ddl.update",
+            SqlTemplate::Delete => "ddl.delete",
+        }
+    }
+
+    /// 是否可以为该类型的对象生成
+    pub fn applies(&self, kind: ObjectKind) -> bool {
+        match self {
+            SqlTemplate::Create => true,
+            SqlTemplate::Select => matches!(kind, ObjectKind::Table | ObjectKind::View),
+            SqlTemplate::Insert | SqlTemplate::Update | SqlTemplate::Delete => {
+                kind == ObjectKind::Table
+            }
+        }
+    }
+}
+
+/// 生成对象的语句，对象已经不存在时返回 `None`
+pub fn generate(
+    manager: &DatabaseManager,
+    object: &DbObject,
+    template: SqlTemplate,
+) -> Result<Option<String>, DatabaseError> {
+    let template: fn(&str, &[ColumnInfo]) -> String = match template {
+        SqlTemplate::Create => return create_statement(manager, object),
+        SqlTemplate::Select => select_template,
+        SqlTemplate::Insert => insert_template,
+        SqlTemplate::Update => update_template,
+        SqlTemplate::Delete => delete_template,
+    };
+    let columns = manager.table_columns(&object.name)?;
+    if columns.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(template(&qualified_name(&object.name), &columns)))
+}
+
+/// 语句中使用的限定名，只在需要时加引号
+fn qualified_name(name: &TableName) -> String {
+    [&name.database, &name.schema]
+        .into_iter()
+        .flatten()
+        .chain(std::iter::once(&name.name))
+        .map(|part| identifier(part))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// 从目录函数中读取建立语句，表的语句后面附带它的索引
+fn create_statement(
+    manager: &DatabaseManager,
+    object: &DbObject,
+) -> Result<Option<String>, DatabaseError> {
+    let name = &object.name;
+    let params = QueryParams::Positional(vec![
+        Value::Text(name.database.clone().unwrap_or_default()),
+        Value::Text(name.schema.clone().unwrap_or_default()),
+        Value::Text(name.name.clone()),
+    ]);
+    let filter = "database_name = $1 AND schema_name = $2";
+    let sql = match object.kind {
+        ObjectKind::Table => format!(
+            "SELECT sql FROM (
+                 SELECT 0 AS ord, NULL AS index_name, sql FROM duckdb_tables()
+                 WHERE {filter} AND table_name = $3
+                 UNION ALL
+                 SELECT 1 AS ord, index_name, sql FROM duckdb_indexes()
+                 WHERE {filter} AND table_name = $3 AND sql IS NOT NULL
+             )
+             ORDER BY ord, index_name"
+        ),
+        ObjectKind::View => {
+            format!("SELECT sql FROM duckdb_views() WHERE {filter} AND view_name = $3")
+        }
+        ObjectKind::Index => {
+            format!("SELECT sql FROM duckdb_indexes() WHERE {filter} AND index_name = $3")
+        }
+        ObjectKind::Sequence => {
+            format!("SELECT sql FROM duckdb_sequences() WHERE {filter} AND sequence_name = $3")
+        }
+        ObjectKind::Macro => format!(
+            "SELECT function_type, parameters, macro_definition
+             FROM duckdb_functions()
+             WHERE {filter} AND function_name = $3
+               AND function_type IN ('macro', 'table_macro')
+             ORDER BY function_oid"
+        ),
+    };
+    let result = manager.fetch(&sql, &params, usize::MAX)?;
+
+    // 同名的宏可以有多个重载，合并为一条语句
+    let statements: Vec<String> = if object.kind == ObjectKind::Macro {
+        let definitions: Vec<String> = result
+            .rows
+            .iter()
+            .map(|values| macro_definition(&values[0], &values[1], &values[2]))
+            .collect();
+        if definitions.is_empty() {
+            Vec::new()
+        } else {
+            vec![format!(
+                "CREATE MACRO {}{};",
+                qualified_name(name),
+                definitions.join(", ")
+            )]
+        }
+    } else {
+        result
+            .rows
+            .iter()
+            .map(|values| {
+                let sql = format_value(&values[0]);
+                let sql = sql.trim();
+                if sql.ends_with(';') {
+                    sql.to_string()
+                } else {
+                    format!("{};", sql)
+                }
+            })
+            .collect()
+    };
+    if statements.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(statements.join("\n\n") + "\n"))
+}
+
+/// 每列一行的列表，除最后一行外以逗号结尾，`comment` 为行末的注释
+fn column_lines(items: &[(String, Option<String>)]) -> String {
+    let last = items.len().saturating_sub(1);
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, (item, comment))| {
+            let comma = if i < last { "," } else { "" };
+            match comment {
+                Some(comment) => format!("    {}{} -- {}", item, comma, comment),
+                None => format!("    {}{}", item, comma),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 列的类型说明，用于值占位符后的注释
+fn column_comment(column: &ColumnInfo) -> String {
+    let mut comment = format!("{} {}", column.name, column.data_type);
+    if !column.nullable {
+        comment.push_str(" NOT NULL");
+    }
+    if let Some(default) = &column.default {
+        comment.push_str(&format!(" DEFAULT {}", default));
+    }
+    comment
+}
+
+/// 定位一行的条件，有主键时按主键，否则按所有列
+///
+/// 值写成 `?` 参数，执行时会提示输入，不会在未修改时误执行
+fn where_clause(columns: &[ColumnInfo]) -> String {
+    let keys: Vec<&ColumnInfo> = if columns.iter().any(|c| c.primary_key) {
+        columns.iter().filter(|c| c.primary_key).collect()
+    } else {
+        columns.iter().collect()
+    };
+    keys.iter()
+        .map(|c| format!("{} = ?", identifier(&c.name)))
+        .collect::<Vec<_>>()
+        .join("\n  AND ")
+}
+
+fn select_template(name: &str, columns: &[ColumnInfo]) -> String {
+    let items: Vec<_> = columns
+        .iter()
+        .map(|c| (identifier(&c.name), None))
+        .collect();
+    format!("SELECT\n{}\nFROM {};\n", column_lines(&items), name)
+}
+
+fn insert_template(name: &str, columns: &[ColumnInfo]) -> String {
+    let names: Vec<_> = columns
+        .iter()
+        .map(|c| (identifier(&c.name), None))
+        .collect();
+    let values: Vec<_> = columns
+        .iter()
+        .map(|c| ("NULL".to_string(), Some(column_comment(c))))
+        .collect();
+    format!(
+        "INSERT INTO {} (\n{}\n) VALUES (\n{}\n);\n",
+        name,
+        column_lines(&names),
+        column_lines(&values)
+    )
+}
+
+/// 修改非主键列，所有列都是主键时修改所有列
+fn update_template(name: &str, columns: &[ColumnInfo]) -> String {
+    let mut set: Vec<&ColumnInfo> = columns.iter().filter(|c| !c.primary_key).collect();
+    if set.is_empty() {
+        set = columns.iter().collect();
+    }
+    let items: Vec<_> = set
+        .iter()
+        .map(|c| {
+            (
+                format!("{} = NULL", identifier(&c.name)),
+                Some(column_comment(c)),
+            )
+        })
+        .collect();
+    format!(
+        "UPDATE {} SET\n{}\nWHERE {};\n",
+        name,
+        column_lines(&items),
+        where_clause(columns)
+    )
+}
+
+fn delete_template(name: &str, columns: &[ColumnInfo]) -> String {
+    format!("DELETE FROM {}\nWHERE {};\n", name, where_clause(columns))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_definitions_and_templates() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        for sql in [
+            "CREATE SCHEMA s",
+            "CREATE TABLE s.t (id INTEGER PRIMARY KEY, \"Name\" VARCHAR NOT NULL, v DOUBLE)",
+            "CREATE INDEX ix ON s.t (v)",
+            "CREATE INDEX a_ix ON s.t (\"Name\")",
+            "CREATE VIEW s.w AS SELECT id FROM s.t",
+            "CREATE SEQUENCE s.q START 5",
+            "CREATE MACRO s.m(a) AS a + 1, (a, b) AS a + b",
+        ] {
+            manager.execute_query(sql).unwrap();
+        }
+
+        let objects = manager.list_objects().unwrap();
+        let kinds: Vec<_> = objects
+            .iter()
+            .map(|o| (o.kind, o.name.name.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (ObjectKind::Index, "a_ix"),
+                (ObjectKind::Index, "ix"),
+                (ObjectKind::Macro, "m"),
+                (ObjectKind::Sequence, "q"),
+                (ObjectKind::Table, "t"),
+                (ObjectKind::View, "w"),
+            ]
+        );
+        let object = |kind| objects.iter().find(|o| o.kind == kind).unwrap();
+
+        let table = generate(&manager, object(ObjectKind::Table), SqlTemplate::Create)
+            .unwrap()
+            .unwrap();
+        assert!(table.starts_with("CREATE TABLE s.t("), "{}", table);
+        assert!(
+            table.ends_with(
+                ");\n\nCREATE INDEX a_ix ON s.t(\"Name\");\n\nCREATE INDEX ix ON s.t(v);\n"
+            ),
+            "{}",
+            table
+        );
+        let view = generate(&manager, object(ObjectKind::View), SqlTemplate::Create)
+            .unwrap()
+            .unwrap();
+        assert!(view.starts_with("CREATE VIEW s.w AS SELECT"), "{}", view);
+        let sequence = generate(&manager, object(ObjectKind::Sequence), SqlTemplate::Create)
+            .unwrap()
+            .unwrap();
+        assert!(sequence.contains("START 5"), "{}", sequence);
+        let function = generate(&manager, object(ObjectKind::Macro), SqlTemplate::Create)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            function,
+            "CREATE MACRO memory.s.m(a) AS (a + 1), (a, b) AS (a + b);\n"
+        );
+
+        let table = object(ObjectKind::Table);
+        assert_eq!(
+            generate(&manager, table, SqlTemplate::Select)
+                .unwrap()
+                .unwrap(),
+            "SELECT\n    id,\n    \"Name\",\n    v\nFROM memory.s.t;\n"
+        );
+        assert_eq!(
+            generate(&manager, table, SqlTemplate::Update)
+                .unwrap()
+                .unwrap(),
+            "UPDATE memory.s.t SET\n    \"Name\" = NULL, -- Name VARCHAR NOT NULL\n    \
+             v = NULL -- v DOUBLE\nWHERE id = ?;\n"
+        );
+        assert!(
+            generate(&manager, table, SqlTemplate::Insert)
+                .unwrap()
+                .unwrap()
+                .contains("    NULL, -- id INTEGER NOT NULL\n")
+        );
+        // 条件中的参数没有填写时不会执行
+        let delete = generate(&manager, table, SqlTemplate::Delete)
+            .unwrap()
+            .unwrap();
+        assert_eq!(delete, "DELETE FROM memory.s.t\nWHERE id = ?;\n");
+        assert!(
+            manager
+                .execute_with_params(&delete, &QueryParams::None)
+                .is_err()
+        );
+        assert!(!SqlTemplate::Insert.applies(ObjectKind::View));
+    }
+}
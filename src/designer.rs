@@ -0,0 +1,1105 @@
+//! 表设计模块
+//! 以表单编辑表的列和约束，预览生成的语句并在一个事务中建表或修改表
+
+use crate::app::MyApp;
+use crate::database::{
+    DatabaseError, DatabaseManager, QueryParams, TableName, format_value, identifier,
+};
+use crate::i18n::{tr, tr_args};
+use duckdb::types::Value;
+use eframe::egui;
+
+/// 类型下拉菜单中的常用类型，也可以直接输入其他类型
+const COMMON_TYPES: [&str; 12] = [
+    "INTEGER",
+    "BIGINT",
+    "DOUBLE",
+    "DECIMAL(18, 3)",
+    "VARCHAR",
+    "BOOLEAN",
+    "DATE",
+    "TIMESTAMP",
+    "INTERVAL",
+    "UUID",
+    "JSON",
+    "BLOB",
+];
+
+/// 设计中的列
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DesignColumn {
+    pub name: String,
+    pub data_type: String,
+    pub not_null: bool,
+    pub default: String, // 默认值表达式，为空时没有默认值
+    pub primary_key: bool,
+    pub original: Option<usize>, // 对应原表中的第几列，新增的列为 `None`
+}
+
+impl DesignColumn {
+    /// 建表语句中的列定义
+    fn to_sql(&self) -> String {
+        let mut sql = format!("{} {}", identifier(self.name.trim()), self.data_type.trim());
+        if !self.default.trim().is_empty() {
+            sql.push_str(&format!(" DEFAULT {}", self.default.trim()));
+        }
+        if self.not_null {
+            sql.push_str(" NOT NULL");
+        }
+        sql
+    }
+}
+
+/// 外键约束
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ForeignKey {
+    pub columns: String,    // 本表的列，以逗号分隔
+    pub table: String,      // 引用的表
+    pub references: String, // 引用的列，以逗号分隔
+}
+
+impl ForeignKey {
+    fn to_sql(&self) -> String {
+        format!(
+            "FOREIGN KEY ({}) REFERENCES {} ({})",
+            identifier_list(&self.columns),
+            self.table.trim(),
+            identifier_list(&self.references)
+        )
+    }
+}
+
+/// 以逗号分隔的列名，按需要加上引号
+fn identifier_list(text: &str) -> String {
+    text.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(identifier)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 表上的索引，重建表后重新建立
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableIndex {
+    pub name: String,
+    pub unique: bool,
+    pub expressions: String,  // 括号中的索引表达式
+    pub columns: Vec<String>, // 表达式用到的列
+}
+
+impl TableIndex {
+    /// 从 `duckdb_indexes()` 的建立语句中取出表达式，`table_columns` 为表的所有列
+    fn parse(
+        manager: &DatabaseManager,
+        name: String,
+        unique: bool,
+        sql: &str,
+        table_columns: &[String],
+    ) -> Option<Self> {
+        // 第一个不在引号中的括号开始表达式列表
+        let sql = sql.trim().trim_end_matches(';').trim_end();
+        let mut quote = None;
+        let start = sql.char_indices().find_map(|(i, c)| {
+            match (quote, c) {
+                (None, '"' | '\'') => quote = Some(c),
+                (Some(q), _) if q == c => quote = None,
+                (None, '(') => return Some(i),
+                _ => {}
+            }
+            None
+        })?;
+        let expressions = sql[start + 1..].strip_suffix(')')?.trim().to_string();
+        // 解析不了时当作用到了所有列，只在所有列都保留时重建
+        let columns =
+            referenced_columns(manager, &expressions).unwrap_or_else(|| table_columns.to_vec());
+        Some(Self {
+            name,
+            unique,
+            expressions,
+            columns,
+        })
+    }
+
+    /// 在 `table` 上建立索引的语句
+    fn create_sql(&self, table: &str) -> String {
+        let unique = if self.unique { "UNIQUE " } else { "" };
+        format!(
+            "CREATE {}INDEX {} ON {} ({})",
+            unique,
+            identifier(&self.name),
+            table,
+            self.expressions
+        )
+    }
+}
+
+/// 表达式中引用的列名
+fn referenced_columns(manager: &DatabaseManager, expressions: &str) -> Option<Vec<String>> {
+    fn collect(node: &serde_json::Value, columns: &mut Vec<String>) {
+        match node {
+            serde_json::Value::Object(map) => {
+                if map.get("class").and_then(|c| c.as_str()) == Some("COLUMN_REF")
+                    && let Some(name) = map["column_names"]
+                        .as_array()
+                        .and_then(|names| names.last()?.as_str())
+                {
+                    columns.push(name.to_string());
+                }
+                map.values().for_each(|value| collect(value, columns));
+            }
+            serde_json::Value::Array(items) => items.iter().for_each(|item| collect(item, columns)),
+            _ => {}
+        }
+    }
+
+    let params = QueryParams::Positional(vec![Value::Text(format!("SELECT {}", expressions))]);
+    let result = manager
+        .fetch("SELECT json_serialize_sql($1::VARCHAR)", &params, 1)
+        .ok()?;
+    let tree: serde_json::Value =
+        serde_json::from_str(&format_value(result.rows.first()?.first()?)).ok()?;
+    if tree["error"].as_bool() != Some(false) {
+        return None;
+    }
+    let mut columns = Vec::new();
+    collect(&tree["statements"], &mut columns);
+    Some(columns)
+}
+
+/// 表的设计
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableDesign {
+    pub schema: String,
+    pub name: String,
+    pub columns: Vec<DesignColumn>,
+    pub checks: Vec<String>, // 检查约束的表达式
+    pub foreign_keys: Vec<ForeignKey>,
+    pub uniques: Vec<String>, // 唯一约束的定义，表单中不能修改，重建表时保留
+    pub indexes: Vec<TableIndex>, // 表上的索引，表单中不能修改，重建表时重新建立
+}
+
+impl TableDesign {
+    /// 新表，带一个整数主键列
+    pub fn new_table(schema: &str) -> Self {
+        Self {
+            schema: schema.to_string(),
+            columns: vec![DesignColumn {
+                name: "id".to_string(),
+                data_type: "INTEGER".to_string(),
+                not_null: true,
+                primary_key: true,
+                ..DesignColumn::default()
+            }],
+            ..Self::default()
+        }
+    }
+
+    /// 读取已有表的列和约束
+    pub fn load(manager: &DatabaseManager, table: &TableName) -> Result<Self, DatabaseError> {
+        let columns = manager
+            .table_columns(table)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, column)| DesignColumn {
+                name: column.name,
+                data_type: column.data_type,
+                not_null: !column.nullable,
+                default: column.default.unwrap_or_default(),
+                primary_key: column.primary_key,
+                original: Some(i),
+            })
+            .collect();
+        let mut design = Self {
+            schema: table.schema.clone().unwrap_or_default(),
+            name: table.name.clone(),
+            columns,
+            ..Self::default()
+        };
+
+        let params = QueryParams::Positional(vec![
+            Value::Text(table.database.clone().unwrap_or_default()),
+            Value::Text(design.schema.clone()),
+            Value::Text(table.name.clone()),
+        ]);
+        let constraints = manager.fetch(
+            "SELECT constraint_type, constraint_text,
+                    array_to_string(constraint_column_names, ', '),
+                    referenced_table, array_to_string(referenced_column_names, ', ')
+             FROM duckdb_constraints()
+             WHERE database_name = $1 AND schema_name = $2 AND table_name = $3
+               AND constraint_type IN ('CHECK', 'FOREIGN KEY', 'UNIQUE')
+             ORDER BY constraint_index",
+            &params,
+            usize::MAX,
+        )?;
+        for values in constraints.rows {
+            let text = format_value(&values[1]);
+            match format_value(&values[0]).as_str() {
+                "CHECK" => {
+                    // `CHECK((v > 0))` 中的表达式
+                    let expression = text
+                        .strip_prefix("CHECK(")
+                        .and_then(|rest| rest.strip_suffix(')'))
+                        .unwrap_or(&text);
+                    design.checks.push(expression.to_string());
+                }
+                "FOREIGN KEY" => design.foreign_keys.push(ForeignKey {
+                    columns: format_value(&values[2]),
+                    table: identifier(&format_value(&values[3])),
+                    references: format_value(&values[4]),
+                }),
+                _ => design.uniques.push(text),
+            }
+        }
+
+        let table_columns: Vec<String> = design.columns.iter().map(|c| c.name.clone()).collect();
+        let indexes = manager.fetch(
+            "SELECT index_name, is_unique, sql
+             FROM duckdb_indexes()
+             WHERE database_name = $1 AND schema_name = $2 AND table_name = $3
+               AND sql IS NOT NULL
+             ORDER BY index_name",
+            &params,
+            usize::MAX,
+        )?;
+        design.indexes = indexes
+            .rows
+            .iter()
+            .filter_map(|values| {
+                TableIndex::parse(
+                    manager,
+                    format_value(&values[0]),
+                    values[1] == Value::Boolean(true),
+                    &format_value(&values[2]),
+                    &table_columns,
+                )
+            })
+            .collect();
+        Ok(design)
+    }
+
+    /// 检查名称和类型，返回第一个问题的说明
+    fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err(tr("designer.error_no_name").to_string());
+        }
+        if self.columns.is_empty() {
+            return Err(tr("designer.error_no_columns").to_string());
+        }
+        for (i, column) in self.columns.iter().enumerate() {
+            let name = column.name.trim();
+            if name.is_empty() {
+                return Err(tr_args(
+                    "designer.error_column_name",
+                    &[("index", &(i + 1))],
+                ));
+            }
+            if column.data_type.trim().is_empty() {
+                return Err(tr_args("designer.error_type", &[("name", &name)]));
+            }
+            if self.columns[..i]
+                .iter()
+                .any(|c| c.name.trim().eq_ignore_ascii_case(name))
+            {
+                return Err(tr_args("designer.error_duplicate", &[("name", &name)]));
+            }
+        }
+        Ok(())
+    }
+
+    /// 建表语句，`name` 为限定的表名
+    fn create_sql(&self, name: &str) -> String {
+        let mut definitions: Vec<String> = self.columns.iter().map(DesignColumn::to_sql).collect();
+        let keys: Vec<String> = self
+            .columns
+            .iter()
+            .filter(|c| c.primary_key)
+            .map(|c| identifier(c.name.trim()))
+            .collect();
+        if !keys.is_empty() {
+            definitions.push(format!("PRIMARY KEY ({})", keys.join(", ")));
+        }
+        definitions.extend(self.uniques.iter().cloned());
+        definitions.extend(
+            self.checks
+                .iter()
+                .filter(|check| !check.trim().is_empty())
+                .map(|check| format!("CHECK ({})", check.trim())),
+        );
+        definitions.extend(
+            self.foreign_keys
+                .iter()
+                .filter(|key| !key.columns.trim().is_empty())
+                .map(ForeignKey::to_sql),
+        );
+        format!(
+            "CREATE TABLE {} (\n    {}\n)",
+            name,
+            definitions.join(",\n    ")
+        )
+    }
+
+    /// 主键、检查和外键约束是否与原表不同，DuckDB 不能修改已有表的这些约束
+    fn constraints_changed(&self, original: &TableDesign) -> bool {
+        let keys = |design: &TableDesign, original: bool| -> Vec<Option<usize>> {
+            design
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.primary_key)
+                .map(|(i, c)| if original { Some(i) } else { c.original })
+                .collect()
+        };
+        let checks = |design: &TableDesign| -> Vec<String> {
+            design
+                .checks
+                .iter()
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty())
+                .collect()
+        };
+        keys(self, false) != keys(original, true)
+            || checks(self) != checks(original)
+            || self.foreign_keys != original.foreign_keys
+    }
+
+    /// 保留的原有列是否改变了顺序，或新增的列不在最后
+    fn reordered(&self) -> bool {
+        let mut last = None;
+        let mut added = false;
+        for column in &self.columns {
+            match column.original {
+                Some(i) => {
+                    if added || last.is_some_and(|last| i < last) {
+                        return true;
+                    }
+                    last = Some(i);
+                }
+                None => added = true,
+            }
+        }
+        false
+    }
+
+    /// 把表改为当前设计的语句，`original` 为 `None` 时建立新表
+    ///
+    /// 约束或列的顺序改变、新增非空列，或者表上有索引时删除、重命名列、
+    /// 修改类型和非空约束，DuckDB 不能直接修改，改为新建表、复制数据、删除原表再重命名，之后重新建立原表的索引。
+    /// 用到已删除或重命名的列的索引不再建立，在删除原表的语句前加注释说明。
+    pub fn statements(
+        &self,
+        database: &str,
+        original: Option<&TableDesign>,
+    ) -> Result<Vec<String>, String> {
+        self.validate()?;
+        let qualified = |schema: &str, name: &str| {
+            format!(
+                "{}.{}.{}",
+                identifier(database),
+                identifier(schema),
+                identifier(name)
+            )
+        };
+        let name = self.name.trim();
+        let Some(original) = original else {
+            return Ok(vec![self.create_sql(&qualified(self.schema.trim(), name))]);
+        };
+        let table = qualified(&original.schema, &original.name);
+
+        // 表上有索引时 DuckDB 只能直接新增列和修改默认值
+        let indexes_block_alter = !original.indexes.is_empty()
+            && (original.name != name
+                || original.columns.len() > self.columns.iter().flat_map(|c| c.original).count()
+                || self.columns.iter().any(|column| {
+                    column.original.is_some_and(|i| {
+                        let old = &original.columns[i];
+                        old.name != column.name.trim()
+                            || old.data_type != column.data_type.trim()
+                            || old.not_null != column.not_null
+                    })
+                }));
+        let rebuild = self.constraints_changed(original)
+            || self.reordered()
+            || self
+                .columns
+                .iter()
+                .any(|c| c.original.is_none() && c.not_null)
+            || indexes_block_alter;
+        if rebuild {
+            let temporary = format!("{}__rebuild", name);
+            let (targets, sources): (Vec<String>, Vec<String>) = self
+                .columns
+                .iter()
+                .filter_map(|column| {
+                    let old = &original.columns[column.original?];
+                    let source = if old.data_type == column.data_type.trim() {
+                        identifier(&old.name)
+                    } else {
+                        format!(
+                            "CAST({} AS {})",
+                            identifier(&old.name),
+                            column.data_type.trim()
+                        )
+                    };
+                    Some((identifier(column.name.trim()), source))
+                })
+                .unzip();
+            let mut statements = vec![self.create_sql(&qualified(&original.schema, &temporary))];
+            if !targets.is_empty() {
+                statements.push(format!(
+                    "INSERT INTO {} ({}) SELECT {} FROM {}",
+                    qualified(&original.schema, &temporary),
+                    targets.join(", "),
+                    sources.join(", "),
+                    table
+                ));
+            }
+            let (kept, dropped): (Vec<&TableIndex>, Vec<&TableIndex>) =
+                original.indexes.iter().partition(|index| {
+                    index.columns.iter().all(|column| {
+                        self.columns.iter().any(|c| {
+                            c.original.is_some_and(|i| {
+                                original.columns[i].name.eq_ignore_ascii_case(column)
+                            }) && c.name.trim().eq_ignore_ascii_case(column)
+                        })
+                    })
+                });
+            let notes: String = dropped
+                .iter()
+                .map(|index| {
+                    format!(
+                        "-- {}\n",
+                        tr_args("designer.index_dropped", &[("name", &index.name)])
+                    )
+                })
+                .collect();
+            statements.push(format!("{}DROP TABLE {}", notes, table));
+            statements.push(format!(
+                "ALTER TABLE {} RENAME TO {}",
+                qualified(&original.schema, &temporary),
+                identifier(name)
+            ));
+            let renamed = qualified(&original.schema, name);
+            statements.extend(kept.iter().map(|index| index.create_sql(&renamed)));
+            return Ok(statements);
+        }
+
+        let mut statements = Vec::new();
+        let alter = |action: String| format!("ALTER TABLE {} {}", table, action);
+        for (i, old) in original.columns.iter().enumerate() {
+            if !self.columns.iter().any(|c| c.original == Some(i)) {
+                statements.push(alter(format!("DROP COLUMN {}", identifier(&old.name))));
+            }
+        }
+        for column in &self.columns {
+            let Some(old) = column.original.map(|i| &original.columns[i]) else {
+                continue;
+            };
+            let column_name = identifier(column.name.trim());
+            if old.name != column.name.trim() {
+                statements.push(alter(format!(
+                    "RENAME COLUMN {} TO {}",
+                    identifier(&old.name),
+                    column_name
+                )));
+            }
+            if old.data_type != column.data_type.trim() {
+                statements.push(alter(format!(
+                    "ALTER {} TYPE {}",
+                    column_name,
+                    column.data_type.trim()
+                )));
+            }
+            if old.default.trim() != column.default.trim() {
+                statements.push(alter(if column.default.trim().is_empty() {
+                    format!("ALTER {} DROP DEFAULT", column_name)
+                } else {
+                    format!(
+                        "ALTER {} SET DEFAULT {}",
+                        column_name,
+                        column.default.trim()
+                    )
+                }));
+            }
+            if old.not_null != column.not_null {
+                let action = if column.not_null { "SET" } else { "DROP" };
+                statements.push(alter(format!("ALTER {} {} NOT NULL", column_name, action)));
+            }
+        }
+        for column in self.columns.iter().filter(|c| c.original.is_none()) {
+            statements.push(alter(format!("ADD COLUMN {}", column.to_sql())));
+        }
+        if original.name != name {
+            statements.push(alter(format!("RENAME TO {}", identifier(name))));
+        }
+        Ok(statements)
+    }
+}
+
+/// 表设计窗口
+#[derive(Debug, Clone, Default)]
+pub struct TableDesigner {
+    pub connection: String,            // 连接的路径
+    pub database: String,              // 表所在的数据库
+    pub design: TableDesign,           // 正在编辑的设计
+    pub original: Option<TableDesign>, // 原表的结构，新建表时为 `None`
+    pub error: Option<String>,         // 上次执行失败的原因
+}
+
+impl TableDesigner {
+    /// 设计新表
+    pub fn new_table(connection: String, database: String) -> Self {
+        Self {
+            connection,
+            database,
+            design: TableDesign::new_table("main"),
+            ..Self::default()
+        }
+    }
+
+    /// 修改已有的表
+    pub fn edit_table(
+        connection: String,
+        manager: &DatabaseManager,
+        table: &TableName,
+    ) -> Result<Self, DatabaseError> {
+        let design = TableDesign::load(manager, table)?;
+        Ok(Self {
+            connection,
+            database: table.database.clone().unwrap_or_default(),
+            original: Some(design.clone()),
+            design,
+            error: None,
+        })
+    }
+
+    /// 在一个事务中执行语句，成功后重新读取表的结构
+    fn apply(&mut self, app: &mut MyApp, statements: &[String]) {
+        let Some(connection) = app
+            .connections
+            .iter_mut()
+            .find(|c| c.path == self.connection)
+        else {
+            self.error = Some(tr("status.no_connection").to_string());
+            return;
+        };
+        if let Err(e) = connection.manager.execute_in_transaction(statements) {
+            self.error = Some(e.to_string());
+            return;
+        }
+        connection.objects = None;
+        let table = TableName {
+            database: Some(self.database.clone()),
+            schema: Some(self.design.schema.trim().to_string()),
+            name: self.design.name.trim().to_string(),
+        };
+        match TableDesign::load(&connection.manager, &table) {
+            Ok(design) => {
+                self.original = Some(design.clone());
+                self.design = design;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        app.set_status_message(tr_args("designer.applied", &[("name", &table.name)]));
+    }
+
+    /// 渲染窗口
+    pub fn show(app: &mut MyApp, ctx: &egui::Context) {
+        let Some(mut designer) = app.table_designer.take() else {
+            return;
+        };
+        let statements = designer
+            .design
+            .statements(&designer.database, designer.original.as_ref());
+
+        let mut is_open = true;
+        let mut apply = false;
+        let mut open_script = false;
+        let title = match &designer.original {
+            Some(original) => tr_args("designer.edit_title", &[("name", &original.name)]),
+            None => tr("designer.new_title").to_string(),
+        };
+        egui::Window::new(title)
+            .id(egui::Id::new("table_designer"))
+            .open(&mut is_open)
+            .default_size([760.0, 560.0])
+            .show(ctx, |ui| {
+                let design = &mut designer.design;
+                egui::Grid::new("designer_table")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("designer.schema"));
+                        ui.add_enabled(
+                            designer.original.is_none(),
+                            egui::TextEdit::singleline(&mut design.schema).desired_width(200.0),
+                        );
+                        ui.end_row();
+                        ui.label(tr("designer.name"));
+                        ui.add(egui::TextEdit::singleline(&mut design.name).desired_width(200.0));
+                        ui.end_row();
+                    });
+
+                egui::ScrollArea::vertical()
+                    .max_height(ui.available_height() * 0.6)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        ui.separator();
+                        ui.strong(tr("designer.columns"));
+                        columns_editor(ui, design);
+                        ui.separator();
+                        ui.strong(tr("designer.checks"));
+                        checks_editor(ui, design);
+                        ui.separator();
+                        ui.strong(tr("designer.foreign_keys"));
+                        foreign_keys_editor(ui, design);
+                        if !design.uniques.is_empty() {
+                            ui.separator();
+                            ui.strong(tr("designer.uniques"))
+                                .on_hover_text(tr("designer.uniques_hint"));
+                            for unique in &design.uniques {
+                                ui.label(egui::RichText::new(unique).monospace());
+                            }
+                        }
+                    });
+
+                ui.separator();
+                ui.strong(tr("designer.preview"));
+                match &statements {
+                    Ok(statements) if statements.is_empty() => {
+                        ui.label(tr("designer.no_changes"));
+                    }
+                    Ok(statements) => {
+                        let mut preview = script(statements);
+                        egui::ScrollArea::vertical()
+                            .id_salt("designer_preview")
+                            .max_height(160.0)
+                            .show(ui, |ui| {
+                                ui.add(
+                                    egui::TextEdit::multiline(&mut preview)
+                                        .code_editor()
+                                        .interactive(false)
+                                        .desired_width(f32::INFINITY),
+                                );
+                            });
+                    }
+                    Err(problem) => {
+                        ui.colored_label(ui.visuals().warn_fg_color, problem);
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    let ready = statements.as_ref().is_ok_and(|s| !s.is_empty());
+                    if ui
+                        .add_enabled(ready, egui::Button::new(tr("designer.apply")))
+                        .on_hover_text(tr("designer.apply_hint"))
+                        .clicked()
+                    {
+                        apply = true;
+                    }
+                    if ui
+                        .add_enabled(ready, egui::Button::new(tr("designer.open_script")))
+                        .clicked()
+                    {
+                        open_script = true;
+                    }
+                    if let Some(original) = &designer.original
+                        && ui.button(tr("designer.reset")).clicked()
+                    {
+                        designer.design = original.clone();
+                        designer.error = None;
+                    }
+                });
+                if let Some(error) = &designer.error {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        tr_args("designer.apply_failed", &[("error", error)]),
+                    );
+                }
+            });
+
+        if let Ok(statements) = statements {
+            if apply {
+                designer.apply(app, &statements);
+            }
+            if open_script {
+                app.open_query_tab(script(&statements), Some(designer.connection.clone()));
+            }
+        }
+        if is_open {
+            app.table_designer = Some(designer);
+        }
+    }
+}
+
+/// 语句以分号结尾，每条之间空一行
+fn script(statements: &[String]) -> String {
+    statements
+        .iter()
+        .map(|statement| format!("{};", statement))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// 列的表格，可以移动、删除和新增列
+fn columns_editor(ui: &mut egui::Ui, design: &mut TableDesign) {
+    let mut moved = None;
+    let mut removed = None;
+    let count = design.columns.len();
+    egui::Grid::new("designer_columns")
+        .num_columns(7)
+        .spacing([6.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("");
+            ui.label(tr("designer.column_name"));
+            ui.label(tr("designer.column_type"));
+            ui.label(tr("designer.not_null"));
+            ui.label(tr("designer.primary_key"));
+            ui.label(tr("designer.default"));
+            ui.label("");
+            ui.end_row();
+
+            for (i, column) in design.columns.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(i > 0, egui::Button::new("▲").small())
+                        .clicked()
+                    {
+                        moved = Some((i, i - 1));
+                    }
+                    if ui
+                        .add_enabled(i + 1 < count, egui::Button::new("▼").small())
+                        .clicked()
+                    {
+                        moved = Some((i, i + 1));
+                    }
+                });
+                let name =
+                    ui.add(egui::TextEdit::singleline(&mut column.name).desired_width(140.0));
+                if column.original.is_none() {
+                    name.on_hover_text(tr("designer.new_column"));
+                }
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut column.data_type).desired_width(120.0));
+                    ui.menu_button("▾", |ui| {
+                        for data_type in COMMON_TYPES {
+                            if ui.button(data_type).clicked() {
+                                column.data_type = data_type.to_string();
+                                ui.close();
+                            }
+                        }
+                    });
+                });
+                ui.checkbox(&mut column.not_null, "");
+                if ui.checkbox(&mut column.primary_key, "").changed() && column.primary_key {
+                    column.not_null = true;
+                }
+                ui.add(
+                    egui::TextEdit::singleline(&mut column.default)
+                        .hint_text("NULL")
+                        .desired_width(120.0),
+                );
+                if ui
+                    .small_button("🗑")
+                    .on_hover_text(tr("designer.remove"))
+                    .clicked()
+                {
+                    removed = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+    if let Some((from, to)) = moved {
+        design.columns.swap(from, to);
+    }
+    if let Some(i) = removed {
+        design.columns.remove(i);
+    }
+    if ui.button(tr("designer.add_column")).clicked() {
+        design.columns.push(DesignColumn {
+            data_type: "VARCHAR".to_string(),
+            ..DesignColumn::default()
+        });
+    }
+}
+
+/// 检查约束的列表
+fn checks_editor(ui: &mut egui::Ui, design: &mut TableDesign) {
+    let mut removed = None;
+    for (i, check) in design.checks.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(check)
+                    .code_editor()
+                    .hint_text("price > 0")
+                    .desired_width(400.0),
+            );
+            if ui
+                .small_button("🗑")
+                .on_hover_text(tr("designer.remove"))
+                .clicked()
+            {
+                removed = Some(i);
+            }
+        });
+    }
+    if let Some(i) = removed {
+        design.checks.remove(i);
+    }
+    if ui.button(tr("designer.add_check")).clicked() {
+        design.checks.push(String::new());
+    }
+}
+
+/// 外键的列表
+fn foreign_keys_editor(ui: &mut egui::Ui, design: &mut TableDesign) {
+    let mut removed = None;
+    if !design.foreign_keys.is_empty() {
+        egui::Grid::new("designer_foreign_keys")
+            .num_columns(4)
+            .spacing([6.0, 4.0])
+            .show(ui, |ui| {
+                ui.label(tr("designer.fk_columns"));
+                ui.label(tr("designer.fk_table"));
+                ui.label(tr("designer.fk_references"));
+                ui.label("");
+                ui.end_row();
+                for (i, key) in design.foreign_keys.iter_mut().enumerate() {
+                    ui.add(egui::TextEdit::singleline(&mut key.columns).desired_width(140.0));
+                    ui.add(egui::TextEdit::singleline(&mut key.table).desired_width(140.0));
+                    ui.add(egui::TextEdit::singleline(&mut key.references).desired_width(140.0));
+                    if ui
+                        .small_button("🗑")
+                        .on_hover_text(tr("designer.remove"))
+                        .clicked()
+                    {
+                        removed = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+    if let Some(i) = removed {
+        design.foreign_keys.remove(i);
+    }
+    if ui.button(tr("designer.add_foreign_key")).clicked() {
+        design.foreign_keys.push(ForeignKey::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(manager: &DatabaseManager, design: &TableDesign, original: Option<&TableDesign>) {
+        let statements = design.statements("memory", original).unwrap();
+        manager.execute_in_transaction(&statements).unwrap();
+    }
+
+    fn load(manager: &DatabaseManager, name: &str) -> TableDesign {
+        let table = TableName {
+            database: Some("memory".to_string()),
+            schema: Some("main".to_string()),
+            name: name.to_string(),
+        };
+        TableDesign::load(manager, &table).unwrap()
+    }
+
+    #[test]
+    fn creates_and_alters_tables() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        manager
+            .execute_query("CREATE TABLE parent (id INTEGER PRIMARY KEY)")
+            .unwrap();
+        manager
+            .execute_query("INSERT INTO parent VALUES (1), (2)")
+            .unwrap();
+
+        let mut design = TableDesign::new_table("main");
+        design.name = "items".to_string();
+        design.columns.push(DesignColumn {
+            name: "Label".to_string(),
+            data_type: "VARCHAR".to_string(),
+            default: "'x'".to_string(),
+            ..DesignColumn::default()
+        });
+        design.checks.push("id > 0".to_string());
+        design.foreign_keys.push(ForeignKey {
+            columns: "id".to_string(),
+            table: "parent".to_string(),
+            references: "id".to_string(),
+        });
+        assert_eq!(
+            design.statements("memory", None).unwrap(),
+            [
+                "CREATE TABLE memory.main.items (\n    id INTEGER NOT NULL,\n    \
+              \"Label\" VARCHAR DEFAULT 'x',\n    PRIMARY KEY (id),\n    CHECK (id > 0),\n    \
+              FOREIGN KEY (id) REFERENCES parent (id)\n)"
+            ]
+        );
+        apply(&manager, &design, None);
+        manager
+            .execute_query("INSERT INTO items (id) VALUES (1), (2)")
+            .unwrap();
+
+        // 重命名、改类型和新增可空列可以直接修改
+        let original = load(&manager, "items");
+        assert_eq!(original.checks, ["(id > 0)"]);
+        assert_eq!(original.foreign_keys.len(), 1);
+        let mut design = original.clone();
+        design.columns[1].name = "label".to_string();
+        design.columns[1].data_type = "VARCHAR(20)".to_string();
+        design.columns[1].not_null = true;
+        design.columns.push(DesignColumn {
+            name: "qty".to_string(),
+            data_type: "INTEGER".to_string(),
+            ..DesignColumn::default()
+        });
+        let statements = design.statements("memory", Some(&original)).unwrap();
+        assert!(statements.iter().all(|s| s.starts_with("ALTER TABLE")));
+        apply(&manager, &design, Some(&original));
+
+        // 调整列的顺序和约束时重建表并保留数据
+        let original = load(&manager, "items");
+        let mut design = original.clone();
+        design.columns.swap(0, 2);
+        design.checks.clear();
+        design.foreign_keys.clear();
+        design.name = "goods".to_string();
+        let statements = design.statements("memory", Some(&original)).unwrap();
+        assert!(statements[0].starts_with("CREATE TABLE memory.main.goods__rebuild"));
+        apply(&manager, &design, Some(&original));
+        let rebuilt = load(&manager, "goods");
+        let names: Vec<_> = rebuilt.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["qty", "label", "id"]);
+        assert!(rebuilt.columns[2].primary_key && rebuilt.checks.is_empty());
+        let rows = manager
+            .fetch(
+                "SELECT label, id FROM goods ORDER BY id",
+                &QueryParams::None,
+                10,
+            )
+            .unwrap()
+            .rows;
+        assert_eq!(
+            rows,
+            [
+                [Value::Text("x".into()), Value::Int(1)],
+                [Value::Text("x".into()), Value::Int(2)]
+            ]
+        );
+    }
+
+    #[test]
+    fn rebuild_recreates_indexes() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        for sql in [
+            "CREATE TABLE t (id INTEGER, \"Na me\" VARCHAR, b INTEGER)",
+            "INSERT INTO t VALUES (1, 'x', 2)",
+            "CREATE INDEX ia ON t (\"Na me\")",
+            "CREATE INDEX ib ON t ((b + id))",
+            "CREATE UNIQUE INDEX iu ON t (id)",
+        ] {
+            manager.execute_query(sql).unwrap();
+        }
+
+        // 调整列的顺序并删除 b，用到 b 的索引不再建立
+        let original = load(&manager, "t");
+        assert_eq!(original.indexes.len(), 3);
+        assert_eq!(original.indexes[1].columns, ["b", "id"]);
+        let mut design = original.clone();
+        design.columns.swap(0, 1);
+        design.columns.pop();
+        let statements = design.statements("memory", Some(&original)).unwrap();
+        assert!(
+            statements
+                .iter()
+                .any(|s| s.starts_with("-- ") && s.contains("ib"))
+        );
+        assert_eq!(
+            statements[statements.len() - 2..],
+            [
+                "CREATE INDEX ia ON memory.main.t (\"Na me\")",
+                "CREATE UNIQUE INDEX iu ON memory.main.t (id)"
+            ]
+        );
+        apply(&manager, &design, Some(&original));
+
+        let rebuilt = load(&manager, "t");
+        let names: Vec<_> = rebuilt.indexes.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["ia", "iu"]);
+        assert!(rebuilt.indexes[1].unique);
+        assert!(
+            manager
+                .execute_query("INSERT INTO t VALUES ('y', 1)")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn indexed_tables_rebuild_when_alter_is_blocked() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        for sql in [
+            "CREATE TABLE t (id INTEGER, a INTEGER, b INTEGER)",
+            "INSERT INTO t VALUES (1, 2, 3)",
+            "CREATE INDEX ia ON t (a)",
+        ] {
+            manager.execute_query(sql).unwrap();
+        }
+
+        // 新增列和修改默认值可以直接修改
+        let original = load(&manager, "t");
+        let mut design = original.clone();
+        design.columns[2].default = "0".to_string();
+        design.columns.push(DesignColumn {
+            name: "c".to_string(),
+            data_type: "INTEGER".to_string(),
+            ..DesignColumn::default()
+        });
+        let statements = design.statements("memory", Some(&original)).unwrap();
+        assert!(statements.iter().all(|s| s.starts_with("ALTER TABLE")));
+        apply(&manager, &design, Some(&original));
+
+        // 重命名、删除列和修改索引列的类型会被索引阻止，改为重建表
+        let changes: [fn(&mut TableDesign); 3] = [
+            |design| design.columns[2].name = "b2".to_string(),
+            |design| {
+                design.columns.pop();
+            },
+            |design| design.columns[1].data_type = "BIGINT".to_string(),
+        ];
+        for change in changes {
+            let original = load(&manager, "t");
+            let mut design = original.clone();
+            change(&mut design);
+            let statements = design.statements("memory", Some(&original)).unwrap();
+            assert!(statements[0].starts_with("CREATE TABLE memory.main.t__rebuild"));
+            apply(&manager, &design, Some(&original));
+            let rebuilt = load(&manager, "t");
+            assert_eq!(rebuilt.indexes.len(), 1);
+            assert_eq!(rebuilt.indexes[0].name, "ia");
+        }
+
+        let rebuilt = load(&manager, "t");
+        let columns: Vec<_> = rebuilt
+            .columns
+            .iter()
+            .map(|c| (c.name.as_str(), c.data_type.as_str()))
+            .collect();
+        assert_eq!(
+            columns,
+            [("id", "INTEGER"), ("a", "BIGINT"), ("b2", "INTEGER")]
+        );
+        let rows = manager
+            .fetch("SELECT id, a, b2 FROM t", &QueryParams::None, 10)
+            .unwrap()
+            .rows;
+        assert_eq!(rows, [[Value::Int(1), Value::BigInt(2), Value::Int(3)]]);
+    }
+}
@@ -0,0 +1,657 @@
+//! 数据对比模块
+//! 比较两个查询的结果，两个查询可以来自不同的连接，按键列匹配行并列出新增、删除和修改的行
+
+use crate::app::MyApp;
+use crate::database::{
+    DatabaseError, DatabaseManager, QueryParams, QueryResult, format_value, quote_identifier,
+};
+use crate::export::{CopyData, CopyFormat, CopyOptions, QuoteMode};
+use crate::i18n::{tr, tr_args};
+use duckdb::types::Value;
+use eframe::egui::{self, Color32};
+use egui_extras::{Column, TableBuilder};
+
+/// 每个查询最多读取的行数
+const MAX_ROWS: usize = 100_000;
+
+/// 新增行的背景色
+const ADDED_COLOR: Color32 = Color32::from_rgb(80, 170, 80);
+
+/// 行的差异
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Added,   // 只在右侧结果中
+    Removed, // 只在左侧结果中
+    Changed, // 键相同，其他列的值不同
+}
+
+impl DiffKind {
+    pub const ALL: [DiffKind; 3] = [DiffKind::Added, DiffKind::Removed, DiffKind::Changed];
+
+    /// 翻译键
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiffKind::Added => "diff.added",
+            DiffKind::Removed => "diff.removed",
+            DiffKind::Changed => "diff.changed",
+        }
+    }
+}
+
+/// 有差异的一行，行号为两侧结果中的行号
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffRow {
+    pub kind: DiffKind,
+    pub left: Option<usize>,
+    pub right: Option<usize>,
+    pub changed: Vec<usize>, // 值不同的列，`DiffReport::columns` 中的位置
+}
+
+/// 两个结果的比较报告
+///
+/// 值按文本比较，只比较两侧都有的列。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiffReport {
+    pub left: QueryResult,
+    pub right: QueryResult,
+    pub columns: Vec<String>, // 两侧都有的列，按左侧的顺序
+    pub keys: Vec<String>,    // 匹配行的键列，为空时按所有列匹配
+    pub duplicate_keys: bool, // 选择的键列在某一侧有重复的值，同一行会匹配多次
+    pub left_only: Vec<String>,
+    pub right_only: Vec<String>,
+    pub rows: Vec<DiffRow>,
+    pub unchanged: usize,
+}
+
+impl DiffReport {
+    /// 在内存数据库中按键列连接两个结果
+    pub fn compute(
+        left: QueryResult,
+        right: QueryResult,
+        keys: &[String],
+    ) -> Result<Self, DatabaseError> {
+        let columns: Vec<String> = left
+            .columns
+            .iter()
+            .filter(|c| right.columns.contains(c))
+            .cloned()
+            .collect();
+        let keys: Vec<String> = keys
+            .iter()
+            .filter(|k| columns.contains(k))
+            .cloned()
+            .collect();
+        let left_only = left
+            .columns
+            .iter()
+            .filter(|c| !columns.contains(c))
+            .cloned()
+            .collect();
+        let right_only = right
+            .columns
+            .iter()
+            .filter(|c| !columns.contains(c))
+            .cloned()
+            .collect();
+
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:")?;
+        manager.create_text_table("left_rows", &left)?;
+        manager.create_text_table("right_rows", &right)?;
+
+        let column = |table: &str, name: &str| format!("{}.{}", table, quote_identifier(name));
+        let matching = if keys.is_empty() { &columns } else { &keys };
+        let condition = if matching.is_empty() {
+            "false".to_string()
+        } else {
+            matching
+                .iter()
+                .map(|k| format!("{} IS NOT DISTINCT FROM {}", column("l", k), column("r", k)))
+                .collect::<Vec<_>>()
+                .join(" AND ")
+        };
+        let mut select = vec!["l.rowid".to_string(), "r.rowid".to_string()];
+        select.extend(
+            columns
+                .iter()
+                .map(|c| format!("{} IS DISTINCT FROM {}", column("l", c), column("r", c))),
+        );
+        let sql = format!(
+            "SELECT {}
+             FROM left_rows l FULL OUTER JOIN right_rows r ON {}
+             ORDER BY l.rowid NULLS LAST, r.rowid",
+            select.join(", "),
+            condition
+        );
+        let joined = manager.fetch(&sql, &QueryParams::None, usize::MAX)?;
+
+        let duplicate_keys = !keys.is_empty() && {
+            let group = keys
+                .iter()
+                .map(|k| quote_identifier(k))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let duplicated = |table: &str| {
+                format!("EXISTS (SELECT 1 FROM {table} GROUP BY {group} HAVING count(*) > 1)")
+            };
+            let sql = format!(
+                "SELECT {} OR {}",
+                duplicated("left_rows"),
+                duplicated("right_rows")
+            );
+            let result = manager.fetch(&sql, &QueryParams::None, 1)?;
+            result.rows.first().map(|row| &row[0]) == Some(&Value::Boolean(true))
+        };
+
+        let row_number = |value: &Value| format_value(value).parse::<usize>().ok();
+        let mut rows = Vec::new();
+        let mut unchanged = 0;
+        for values in &joined.rows {
+            let (left_row, right_row) = (row_number(&values[0]), row_number(&values[1]));
+            let changed: Vec<usize> = values[2..]
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| matches!(v, Value::Boolean(true)))
+                .map(|(i, _)| i)
+                .collect();
+            let kind = match (left_row, right_row) {
+                (None, _) => DiffKind::Added,
+                (_, None) => DiffKind::Removed,
+                _ if changed.is_empty() => {
+                    unchanged += 1;
+                    continue;
+                }
+                _ => DiffKind::Changed,
+            };
+            rows.push(DiffRow {
+                kind,
+                left: left_row,
+                right: right_row,
+                changed: if kind == DiffKind::Changed {
+                    changed
+                } else {
+                    Vec::new()
+                },
+            });
+        }
+
+        Ok(Self {
+            left,
+            right,
+            columns,
+            keys,
+            duplicate_keys,
+            left_only,
+            right_only,
+            rows,
+            unchanged,
+        })
+    }
+
+    /// 某种差异的行数
+    pub fn count(&self, kind: DiffKind) -> usize {
+        self.rows.iter().filter(|row| row.kind == kind).count()
+    }
+
+    /// 单元格显示的文本，修改过的值写成 `旧值 → 新值`
+    pub fn cell(&self, row: &DiffRow, col: usize) -> String {
+        let name = &self.columns[col];
+        let value = |result: &QueryResult, index: Option<usize>| {
+            let index = index?;
+            let position = result.columns.iter().position(|c| c == name)?;
+            Some(format_value(&result.rows[index][position]))
+        };
+        let old = value(&self.left, row.left);
+        let new = value(&self.right, row.right);
+        match (old, new) {
+            (Some(old), Some(new)) if row.changed.contains(&col) => format!("{} → {}", old, new),
+            (Some(old), _) => old,
+            (None, new) => new.unwrap_or_default(),
+        }
+    }
+
+    /// 有差异的行转换为结果，第一列为差异类型
+    fn to_result(&self) -> QueryResult {
+        let mut columns = vec![tr("diff.status").to_string()];
+        columns.extend(self.columns.iter().cloned());
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let mut values = vec![Value::Text(tr(row.kind.label()).to_string())];
+                values.extend((0..self.columns.len()).map(|col| Value::Text(self.cell(row, col))));
+                values
+            })
+            .collect();
+        QueryResult {
+            columns,
+            rows,
+            ..QueryResult::default()
+        }
+    }
+
+    /// Markdown 报告，包括汇总和有差异的行
+    pub fn to_markdown(&self) -> String {
+        let mut lines = vec![format!("# {}", tr("diff.title")), String::new()];
+        for kind in DiffKind::ALL {
+            lines.push(format!("- {}: {}", tr(kind.label()), self.count(kind)));
+        }
+        lines.push(format!("- {}: {}", tr("diff.unchanged"), self.unchanged));
+        if !self.keys.is_empty() {
+            lines.push(format!("- {}: {}", tr("diff.keys"), self.keys.join(", ")));
+        }
+        if self.duplicate_keys {
+            lines.push(format!("- {}", tr("diff.duplicate_keys")));
+        }
+        for (key, columns) in [
+            ("diff.left_only", &self.left_only),
+            ("diff.right_only", &self.right_only),
+        ] {
+            if !columns.is_empty() {
+                lines.push(format!("- {}: {}", tr(key), columns.join(", ")));
+            }
+        }
+        if !self.rows.is_empty() {
+            lines.push(String::new());
+            lines.push(self.export(CopyFormat::Markdown));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// CSV 报告，只包括有差异的行
+    pub fn to_csv(&self) -> String {
+        self.export(CopyFormat::Csv) + "\n"
+    }
+
+    fn export(&self, format: CopyFormat) -> String {
+        let result = self.to_result();
+        let data = CopyData::new(&result, 0..result.rows.len(), 0..=result.columns.len() - 1);
+        let options = CopyOptions {
+            format,
+            headers: true,
+            quote: QuoteMode::Minimal,
+            ..CopyOptions::default()
+        };
+        crate::export::format(&data, &options)
+    }
+}
+
+/// 对比的一侧
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiffSide {
+    pub connection: Option<String>, // 数据库路径
+    pub sql: String,
+}
+
+/// 数据对比窗口
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultDiff {
+    pub left: DiffSide,
+    pub right: DiffSide,
+    pub keys: Vec<String>, // 选中的键列
+    pub report: Option<DiffReport>,
+    pub error: Option<String>,
+    pub shown: [bool; 3], // 显示的差异类型，顺序与 `DiffKind::ALL` 一致
+    pub message: Option<String>,
+}
+
+impl ResultDiff {
+    /// 两侧都使用当前的查询和连接
+    pub fn new(sql: &str, connection: Option<String>) -> Self {
+        let side = DiffSide {
+            connection,
+            sql: sql.to_string(),
+        };
+        Self {
+            left: side.clone(),
+            right: side,
+            keys: Vec::new(),
+            report: None,
+            error: None,
+            shown: [true; 3],
+            message: None,
+        }
+    }
+
+    /// 执行两个查询并比较结果
+    fn run(&mut self, app: &MyApp) {
+        let fetch = |side: &DiffSide| -> Result<QueryResult, String> {
+            let connection = app
+                .connections
+                .iter()
+                .find(|c| Some(&c.path) == side.connection.as_ref())
+                .ok_or_else(|| tr("diff.no_connection").to_string())?;
+            let sql = side.sql.trim().trim_end_matches(';');
+            let result = connection
+                .manager
+                .fetch(sql, &QueryParams::None, MAX_ROWS)
+                .map_err(|e| e.to_string())?;
+            if result.truncated {
+                return Err(tr_args("diff.too_many_rows", &[("max", &MAX_ROWS)]));
+            }
+            Ok(result)
+        };
+        let results = fetch(&self.left).and_then(|left| Ok((left, fetch(&self.right)?)));
+        match results {
+            Ok((left, right)) => self.compare(left, right),
+            Err(e) => {
+                self.error = Some(e);
+                self.report = None;
+            }
+        }
+    }
+
+    /// 按选中的键列比较，没有选择时按所有共同的列匹配
+    fn compare(&mut self, left: QueryResult, right: QueryResult) {
+        self.keys
+            .retain(|k| left.columns.contains(k) && right.columns.contains(k));
+        match DiffReport::compute(left, right, &self.keys) {
+            Ok(report) => {
+                self.report = Some(report);
+                self.error = None;
+            }
+            Err(e) => {
+                self.report = None;
+                self.error = Some(e.to_string());
+            }
+        }
+        self.message = None;
+    }
+
+    /// 渲染窗口
+    pub fn show(app: &mut MyApp, ctx: &egui::Context) {
+        let Some(mut diff) = app.result_diff.take() else {
+            return;
+        };
+
+        let mut is_open = true;
+        let mut run = false;
+        let mut recompare = false;
+        let mut export = None;
+        let connections: Vec<(String, String)> = app
+            .connections
+            .iter()
+            .map(|c| (c.path.clone(), c.name.clone()))
+            .collect();
+        egui::Window::new(tr("diff.title"))
+            .id(egui::Id::new("result_diff"))
+            .open(&mut is_open)
+            .default_size([900.0, 600.0])
+            .show(ctx, |ui| {
+                ui.columns(2, |columns| {
+                    for (ui, (side, label)) in columns.iter_mut().zip([
+                        (&mut diff.left, "diff.left"),
+                        (&mut diff.right, "diff.right"),
+                    ]) {
+                        side_editor(ui, side, label, &connections);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui.button(tr("diff.compare")).clicked() {
+                        run = true;
+                    }
+                    if let Some(report) = &diff.report {
+                        ui.separator();
+                        ui.label(tr("diff.keys"));
+                        for column in &report.columns {
+                            let mut key = diff.keys.contains(column);
+                            if ui.checkbox(&mut key, column).changed() {
+                                if key {
+                                    diff.keys.push(column.clone());
+                                } else {
+                                    diff.keys.retain(|k| k != column);
+                                }
+                                recompare = true;
+                            }
+                        }
+                    }
+                });
+                if let Some(error) = &diff.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                if diff.report.as_ref().is_some_and(|r| r.duplicate_keys) {
+                    ui.colored_label(ui.visuals().warn_fg_color, tr("diff.duplicate_keys"));
+                }
+                if let Some(report) = &diff.report {
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        for (i, kind) in DiffKind::ALL.iter().enumerate() {
+                            let text = format!("{}: {}", tr(kind.label()), report.count(*kind));
+                            ui.toggle_value(&mut diff.shown[i], text);
+                        }
+                        ui.label(format!("{}: {}", tr("diff.unchanged"), report.unchanged));
+                        ui.separator();
+                        if ui.button(tr("diff.export_markdown")).clicked() {
+                            export = Some("md");
+                        }
+                        if ui.button(tr("diff.export_csv")).clicked() {
+                            export = Some("csv");
+                        }
+                        if let Some(message) = &diff.message {
+                            ui.label(message);
+                        }
+                    });
+                    for (key, columns) in [
+                        ("diff.left_only", &report.left_only),
+                        ("diff.right_only", &report.right_only),
+                    ] {
+                        if !columns.is_empty() {
+                            ui.colored_label(
+                                ui.visuals().warn_fg_color,
+                                format!("{}: {}", tr(key), columns.join(", ")),
+                            );
+                        }
+                    }
+                    ui.separator();
+                    diff_table(ui, report, &diff.shown);
+                }
+            });
+
+        if run {
+            diff.run(app);
+        } else if recompare && let Some(report) = diff.report.take() {
+            diff.compare(report.left, report.right);
+        }
+        if let (Some(extension), Some(report)) = (export, &diff.report) {
+            diff.message = save_report(report, extension);
+        }
+        if is_open {
+            app.result_diff = Some(diff);
+        }
+    }
+}
+
+/// 一侧的连接和查询
+fn side_editor(
+    ui: &mut egui::Ui,
+    side: &mut DiffSide,
+    label: &str,
+    connections: &[(String, String)],
+) {
+    ui.horizontal(|ui| {
+        ui.strong(tr(label));
+        let selected = connections
+            .iter()
+            .find(|(path, _)| Some(path) == side.connection.as_ref())
+            .map_or_else(
+                || tr("diff.no_connection").to_string(),
+                |(_, name)| name.clone(),
+            );
+        egui::ComboBox::from_id_salt(("diff_connection", label))
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                for (path, name) in connections {
+                    ui.selectable_value(&mut side.connection, Some(path.clone()), name)
+                        .on_hover_text(path);
+                }
+            });
+    });
+    ui.add(
+        egui::TextEdit::multiline(&mut side.sql)
+            .code_editor()
+            .desired_rows(5)
+            .desired_width(f32::INFINITY),
+    );
+}
+
+/// 有差异的行，新增的行使用绿色背景，删除的行使用错误色，修改过的单元格使用警告色
+fn diff_table(ui: &mut egui::Ui, report: &DiffReport, shown: &[bool; 3]) {
+    let rows: Vec<&DiffRow> = report
+        .rows
+        .iter()
+        .filter(|row| {
+            DiffKind::ALL
+                .iter()
+                .position(|kind| *kind == row.kind)
+                .is_some_and(|i| shown[i])
+        })
+        .collect();
+    if rows.is_empty() {
+        ui.label(tr("diff.no_differences"));
+        return;
+    }
+
+    let row_height = ui.text_style_height(&egui::TextStyle::Body) + 4.0;
+    let mut table = TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto().at_least(48.0));
+    for _ in &report.columns {
+        table = table.column(Column::auto().at_least(40.0).at_most(320.0).clip(true));
+    }
+    table
+        .header(row_height, |mut header| {
+            header.col(|ui| {
+                ui.strong(tr("diff.status"));
+            });
+            for column in &report.columns {
+                header.col(|ui| {
+                    if report.keys.contains(column) {
+                        ui.strong(format!("🔑 {}", column));
+                    } else {
+                        ui.strong(column);
+                    }
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(row_height, rows.len(), |mut table_row| {
+                let row = rows[table_row.index()];
+                table_row.col(|ui| {
+                    ui.label(tr(row.kind.label()));
+                });
+                for col in 0..report.columns.len() {
+                    table_row.col(|ui| {
+                        let fill = match row.kind {
+                            _ if row.changed.contains(&col) => ui.visuals().warn_fg_color,
+                            DiffKind::Added => ADDED_COLOR,
+                            DiffKind::Removed => ui.visuals().error_fg_color,
+                            DiffKind::Changed => Color32::TRANSPARENT,
+                        };
+                        ui.painter()
+                            .rect_filled(ui.max_rect(), 0.0, fill.gamma_multiply(0.25));
+                        let text = report.cell(row, col);
+                        ui.add(egui::Label::new(&text).truncate().selectable(false))
+                            .on_hover_text(text);
+                    });
+                }
+            });
+        });
+}
+
+/// 把报告保存到文件，返回显示在工具栏中的消息
+fn save_report(report: &DiffReport, extension: &str) -> Option<String> {
+    let path = rfd::FileDialog::new()
+        .set_title(tr("dialog.export_diff"))
+        .add_filter(extension.to_uppercase(), &[extension])
+        .set_file_name(format!("diff.{}", extension))
+        .save_file()?;
+    let text = match extension {
+        "csv" => report.to_csv(),
+        _ => report.to_markdown(),
+    };
+    let path = path.display().to_string();
+    Some(match std::fs::write(&path, text) {
+        Ok(()) => tr_args("status.file_saved", &[("path", &path)]),
+        Err(e) => tr_args("status.file_save_failed", &[("error", &e)]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(columns: &[&str], rows: Vec<Vec<Value>>) -> QueryResult {
+        QueryResult {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows,
+            ..QueryResult::default()
+        }
+    }
+
+    #[test]
+    fn matches_rows_by_key_columns() {
+        let text = |s: &str| Value::Text(s.into());
+        let left = result(
+            &["id", "name", "old"],
+            vec![
+                vec![Value::Int(1), text("a"), Value::Null],
+                vec![Value::Int(2), text("b"), Value::Null],
+                vec![Value::Int(3), Value::Null, Value::Null],
+            ],
+        );
+        let right = result(
+            &["id", "name"],
+            vec![
+                vec![Value::Int(3), Value::Null],
+                vec![Value::Int(2), text("B")],
+                vec![Value::Int(4), text("d")],
+            ],
+        );
+        let report = DiffReport::compute(left, right, &["id".to_string()]).unwrap();
+        assert_eq!(report.columns, ["id", "name"]);
+        assert_eq!(report.left_only, ["old"]);
+        assert_eq!(report.unchanged, 1);
+
+        let kinds: Vec<(DiffKind, Option<usize>, Option<usize>)> = report
+            .rows
+            .iter()
+            .map(|row| (row.kind, row.left, row.right))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (DiffKind::Removed, Some(0), None),
+                (DiffKind::Changed, Some(1), Some(1)),
+                (DiffKind::Added, None, Some(2)),
+            ]
+        );
+        assert_eq!(report.rows[1].changed, [1]);
+        assert_eq!(report.cell(&report.rows[1], 1), "b → B");
+        assert_eq!(report.cell(&report.rows[2], 1), "d");
+        assert!(report.to_csv().contains("2,b → B"));
+    }
+
+    #[test]
+    fn warns_about_duplicate_keys() {
+        let text = |s: &str| Value::Text(s.into());
+        let left = result(
+            &["kind", "n"],
+            vec![
+                vec![text("a"), Value::Int(1)],
+                vec![text("a"), Value::Int(2)],
+            ],
+        );
+        let right = left.clone();
+
+        // 没有选择键列时按所有列匹配，不会把相同的行报告为修改
+        let report = DiffReport::compute(left.clone(), right.clone(), &[]).unwrap();
+        assert!(report.rows.is_empty() && !report.duplicate_keys);
+        assert_eq!(report.unchanged, 2);
+
+        let report = DiffReport::compute(left, right, &["kind".to_string()]).unwrap();
+        assert!(report.duplicate_keys);
+        assert!(report.to_markdown().contains(tr("diff.duplicate_keys")));
+    }
+}
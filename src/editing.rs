@@ -0,0 +1,602 @@
+//! 数据编辑模块
+//! 在单表查询的结果中修改、新增和删除行，生成 UPDATE/INSERT/DELETE 语句
+
+use crate::database::{
+    DatabaseManager, QueryParams, QueryResult, TableName, format_value, quote_identifier,
+    quote_string, sql_literal,
+};
+use crate::highlight::{TokenKind, tokenize};
+use crate::i18n::{tr, tr_args};
+use crate::results::{GridSelection, GridState};
+use duckdb::types::Value;
+use eframe::egui;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 查询中出现这些关键字时结果不能编辑
+const READ_ONLY_KEYWORDS: &[&str] = &[
+    "DISTINCT",
+    "EXCEPT",
+    "GROUP",
+    "HAVING",
+    "INTERSECT",
+    "JOIN",
+    "PIVOT",
+    "QUALIFY",
+    "UNION",
+    "UNPIVOT",
+    "WINDOW",
+];
+
+/// 暂存的单元格值
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellEdit {
+    Null,
+    Text(String), // 由 DuckDB 转换为列的类型
+}
+
+impl CellEdit {
+    /// SQL 字面量
+    fn to_sql(&self) -> String {
+        match self {
+            CellEdit::Null => "NULL".to_string(),
+            CellEdit::Text(text) => quote_string(text),
+        }
+    }
+}
+
+/// 正在编辑的单元格
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellEditing {
+    pub row: usize,
+    pub col: usize,
+    pub text: String,
+    pub focus: bool, // 下一帧把焦点交给输入框
+}
+
+/// 结果表格的编辑状态
+///
+/// 行号包括新增的行，新增行排在查询结果之后。
+#[derive(Debug, Clone, Default)]
+pub struct DataEditor {
+    pub table: TableName,
+    pub key: Vec<usize>,                             // 主键列在结果中的位置
+    pub editable: Vec<bool>,                         // 结果中的各列是否是表中的列
+    pub updates: BTreeMap<(usize, usize), CellEdit>, // 修改过的单元格
+    pub inserts: Vec<BTreeMap<usize, CellEdit>>,     // 新增的行，没有填写的列使用默认值
+    pub deletes: BTreeSet<usize>,                    // 标记删除的行
+    pub editing: Option<CellEditing>,
+    pub preview: bool, // 显示将要执行的语句
+}
+
+impl DataEditor {
+    /// 查询单个有主键的表，并且结果包含全部主键列时可以编辑
+    ///
+    /// 只有直接引用表中的列、没有改名的结果列可以编辑或作为主键，
+    /// 计算出的列即使与表中的列同名也不能编辑。
+    pub fn detect(manager: &DatabaseManager, sql: &str, result: &QueryResult) -> Option<Self> {
+        let table = TableName::parse(&single_table(sql)?)?;
+        let columns = manager.table_columns(&table).ok()?;
+        let names: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+        let sources = column_sources(manager, sql, &names)?;
+        if sources.len() != result.columns.len() {
+            return None;
+        }
+        let position = |name: &str| sources.iter().position(|source| *source == Some(name));
+
+        let key = columns
+            .iter()
+            .filter(|column| column.primary_key)
+            .map(|column| position(&column.name))
+            .collect::<Option<Vec<_>>>()?;
+        if key.is_empty() {
+            return None;
+        }
+        let editable = sources.iter().map(Option::is_some).collect();
+
+        Some(Self {
+            table,
+            key,
+            editable,
+            ..Self::default()
+        })
+    }
+
+    /// 是否有暂存的修改
+    pub fn has_changes(&self) -> bool {
+        !self.updates.is_empty() || !self.inserts.is_empty() || !self.deletes.is_empty()
+    }
+
+    /// 单元格暂存的值，`rows` 为查询结果的行数
+    pub fn staged(&self, rows: usize, row: usize, col: usize) -> Option<&CellEdit> {
+        if row < rows {
+            self.updates.get(&(row, col))
+        } else {
+            self.inserts.get(row - rows)?.get(&col)
+        }
+    }
+
+    /// 单元格是否可以编辑：列属于表并且所在行没有被删除
+    pub fn can_edit(&self, row: usize, col: usize) -> bool {
+        self.editable.get(col).copied().unwrap_or(false) && !self.deletes.contains(&row)
+    }
+
+    /// 开始编辑单元格
+    pub fn start_editing(&mut self, result: &QueryResult, row: usize, col: usize) {
+        let text = match self.staged(result.rows.len(), row, col) {
+            Some(CellEdit::Text(text)) => text.clone(),
+            Some(CellEdit::Null) => String::new(),
+            None => result
+                .rows
+                .get(row)
+                .map(|values| &values[col])
+                .filter(|value| !matches!(value, Value::Null))
+                .map(format_value)
+                .unwrap_or_default(),
+        };
+        self.editing = Some(CellEditing {
+            row,
+            col,
+            text,
+            focus: true,
+        });
+    }
+
+    /// 暂存单元格的新值，与原值相同时取消修改
+    pub fn set_cell(&mut self, result: &QueryResult, row: usize, col: usize, edit: CellEdit) {
+        let Some(values) = result.rows.get(row) else {
+            if let Some(values) = self.inserts.get_mut(row - result.rows.len()) {
+                values.insert(col, edit);
+            }
+            return;
+        };
+
+        let unchanged = match (&edit, &values[col]) {
+            (CellEdit::Null, Value::Null) => true,
+            (CellEdit::Text(_), Value::Null) | (CellEdit::Null, _) => false,
+            (CellEdit::Text(text), value) => format_value(value) == *text,
+        };
+        if unchanged {
+            self.updates.remove(&(row, col));
+        } else {
+            self.updates.insert((row, col), edit);
+        }
+    }
+
+    /// 撤销单元格的修改
+    pub fn revert_cell(&mut self, rows: usize, row: usize, col: usize) {
+        if row < rows {
+            self.updates.remove(&(row, col));
+        } else if let Some(values) = self.inserts.get_mut(row - rows) {
+            values.remove(&col);
+        }
+    }
+
+    /// 新增一行，返回它的行号
+    pub fn add_row(&mut self, rows: usize) -> usize {
+        self.inserts.push(BTreeMap::new());
+        rows + self.inserts.len() - 1
+    }
+
+    /// 标记或取消删除一行，新增的行直接移除
+    pub fn toggle_delete(&mut self, rows: usize, row: usize) {
+        if row < rows {
+            if !self.deletes.remove(&row) {
+                self.deletes.insert(row);
+            }
+        } else if row - rows < self.inserts.len() {
+            self.inserts.remove(row - rows);
+            self.editing = None;
+        }
+    }
+
+    /// 放弃所有暂存的修改
+    pub fn discard(&mut self) {
+        self.updates.clear();
+        self.inserts.clear();
+        self.deletes.clear();
+        self.editing = None;
+        self.preview = false;
+    }
+
+    /// 生成修改语句：先删除，再更新，最后插入
+    pub fn statements(&self, result: &QueryResult) -> Vec<String> {
+        let table = self.table.to_string();
+        let mut statements = Vec::new();
+
+        for values in self.deletes.iter().filter_map(|&row| result.rows.get(row)) {
+            statements.push(format!(
+                "DELETE FROM {} WHERE {}",
+                table,
+                self.key_condition(result, values)
+            ));
+        }
+
+        let mut assignments: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        for (&(row, col), edit) in &self.updates {
+            if !self.deletes.contains(&row) {
+                assignments.entry(row).or_default().push(format!(
+                    "{} = {}",
+                    quote_identifier(&result.columns[col]),
+                    edit.to_sql()
+                ));
+            }
+        }
+        for (row, assignments) in assignments {
+            statements.push(format!(
+                "UPDATE {} SET {} WHERE {}",
+                table,
+                assignments.join(", "),
+                self.key_condition(result, &result.rows[row])
+            ));
+        }
+
+        for values in &self.inserts {
+            if values.is_empty() {
+                statements.push(format!("INSERT INTO {} DEFAULT VALUES", table));
+                continue;
+            }
+            let columns: Vec<String> = values
+                .keys()
+                .map(|&col| quote_identifier(&result.columns[col]))
+                .collect();
+            let values: Vec<String> = values.values().map(CellEdit::to_sql).collect();
+            statements.push(format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                table,
+                columns.join(", "),
+                values.join(", ")
+            ));
+        }
+
+        statements
+    }
+
+    /// 按主键定位一行的条件
+    fn key_condition(&self, result: &QueryResult, values: &[Value]) -> String {
+        self.key
+            .iter()
+            .map(|&col| {
+                format!(
+                    "{} = {}",
+                    quote_identifier(&result.columns[col]),
+                    sql_literal(&values[col])
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
+}
+
+/// 只查询单个表的 `SELECT` 语句返回 SQL 中的表名，否则返回 `None`
+///
+/// 连接、分组、集合运算和表函数的结果不能对应到表中的行。
+pub fn single_table(sql: &str) -> Option<String> {
+    let tokens: Vec<(TokenKind, &str, std::ops::Range<usize>)> = tokenize(sql)
+        .into_iter()
+        .filter(|(kind, _)| !matches!(kind, TokenKind::Whitespace | TokenKind::Comment))
+        .map(|(kind, range)| (kind, &sql[range.clone()], range))
+        .collect();
+    let is_keyword = |i: usize, word: &str| {
+        tokens.get(i).is_some_and(|(kind, text, _)| {
+            *kind == TokenKind::Keyword && text.eq_ignore_ascii_case(word)
+        })
+    };
+    if !is_keyword(0, "SELECT") {
+        return None;
+    }
+
+    let mut depth = 0;
+    let mut from = None;
+    for (i, (kind, text, _)) in tokens.iter().enumerate() {
+        match *text {
+            "(" => depth += 1,
+            ")" => depth -= 1,
+            _ if depth > 0 || *kind != TokenKind::Keyword => {}
+            _ if READ_ONLY_KEYWORDS
+                .iter()
+                .any(|word| text.eq_ignore_ascii_case(word)) =>
+            {
+                return None;
+            }
+            _ if text.eq_ignore_ascii_case("FROM") => {
+                if from.is_some() {
+                    return None;
+                }
+                from = Some(i + 1);
+            }
+            _ => {}
+        }
+    }
+
+    // 表名由标识符和点组成
+    let start = from?;
+    let mut end = start;
+    while tokens
+        .get(end)
+        .is_some_and(|(kind, _, _)| *kind == TokenKind::Identifier)
+    {
+        end += 1;
+        if tokens.get(end).is_none_or(|(_, text, _)| *text != ".") {
+            break;
+        }
+        end += 1;
+    }
+    if end == start || tokens[end - 1].1 == "." {
+        return None;
+    }
+
+    // 表名之后不能是表函数的参数，也不能用逗号列出其它表
+    for (kind, text, _) in &tokens[end..] {
+        match *text {
+            "(" | "," => return None,
+            _ if *kind == TokenKind::Keyword && !text.eq_ignore_ascii_case("AS") => break,
+            _ => {}
+        }
+    }
+
+    Some(sql[tokens[start].2.start..tokens[end - 1].2.end].to_string())
+}
+
+/// 标记或取消删除选中的行，选中了新增的行时选区会被清除
+pub fn toggle_delete_selection(editor: &mut DataEditor, state: &mut GridState, rows: usize) {
+    let selected = state.selected_rows(rows);
+    // 从后往前处理，移除新增行不影响前面的行号
+    for &row in selected.iter().rev() {
+        editor.toggle_delete(rows, row);
+    }
+    if selected.last().is_some_and(|&row| row >= rows) {
+        state.selection = None;
+    }
+}
+
+/// 渲染编辑工具栏，点击“应用”时返回 true
+pub fn toolbar(
+    ui: &mut egui::Ui,
+    editor: &mut DataEditor,
+    result: &QueryResult,
+    state: &mut GridState,
+) -> bool {
+    let rows = result.rows.len();
+    let mut apply = false;
+
+    ui.horizontal(|ui| {
+        ui.label(
+            egui::RichText::new(tr_args("editing.table", &[("table", &editor.table.name)])).weak(),
+        );
+        if ui.button(tr("editing.add_row")).clicked() {
+            // 新增的行显示在筛选后的行之后
+            let row = state.visible_count(rows) + editor.add_row(rows) - rows;
+            state.selection = Some(GridSelection::rows_between(row, row));
+        }
+        if ui
+            .add_enabled(
+                state.selection.is_some(),
+                egui::Button::new(tr("editing.delete_row")),
+            )
+            .clicked()
+        {
+            toggle_delete_selection(editor, state, rows);
+        }
+
+        if editor.has_changes() {
+            ui.separator();
+            let count = editor.statements(result).len();
+            ui.label(tr_args("editing.changes", &[("count", &count)]));
+            ui.toggle_value(&mut editor.preview, tr("editing.preview"));
+            if ui.button(tr("editing.apply")).clicked() {
+                apply = true;
+            }
+            if ui.button(tr("editing.discard")).clicked() {
+                editor.discard();
+            }
+        }
+    });
+
+    if editor.preview && editor.has_changes() {
+        let sql = editor
+            .statements(result)
+            .into_iter()
+            .map(|statement| statement + ";")
+            .collect::<Vec<_>>()
+            .join("\n");
+        egui::ScrollArea::vertical()
+            .id_salt("edit_preview")
+            .max_height(120.0)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut sql.as_str())
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+            });
+    }
+
+    apply
+}
+
+/// 结果中各列对应的表中的列，按 DuckDB 解析出的选择列表计算
+///
+/// 直接引用的列和 `*` 展开的列有对应，表达式、改名的列和替换的列为 `None`。
+/// 无法确定结果的列数（如 `COLUMNS(...)`）或解析失败时返回 `None`。
+fn column_sources<'a>(
+    manager: &DatabaseManager,
+    sql: &str,
+    columns: &[&'a str],
+) -> Option<Vec<Option<&'a str>>> {
+    let params = QueryParams::Positional(vec![Value::Text(sql.to_string())]);
+    let result = manager
+        .fetch("SELECT json_serialize_sql($1::VARCHAR)", &params, 1)
+        .ok()?;
+    let tree: serde_json::Value =
+        serde_json::from_str(&format_value(result.rows.first()?.first()?)).ok()?;
+    let statements = tree["statements"].as_array()?;
+    if tree["error"].as_bool() != Some(false) || statements.len() != 1 {
+        return None;
+    }
+    let node = &statements[0]["node"];
+    if node["type"] != "SELECT_NODE" {
+        return None;
+    }
+    let find = |name: &str| {
+        columns
+            .iter()
+            .copied()
+            .find(|c| c.eq_ignore_ascii_case(name))
+    };
+    let contains = |list: &serde_json::Value, name: &str| {
+        list.as_array().is_some_and(|items| {
+            items.iter().any(|item| {
+                item.as_str()
+                    .is_some_and(|item| item.eq_ignore_ascii_case(name))
+            })
+        })
+    };
+
+    let mut sources = Vec::new();
+    for item in node["select_list"].as_array()? {
+        let alias = item["alias"].as_str().unwrap_or_default();
+        match item["class"].as_str()? {
+            "COLUMN_REF" => {
+                let name = item["column_names"].as_array()?.last()?.as_str()?;
+                let renamed = !alias.is_empty() && !alias.eq_ignore_ascii_case(name);
+                sources.push(find(name).filter(|_| !renamed));
+            }
+            "STAR" => {
+                let unsupported = item["columns"].as_bool() != Some(false)
+                    || !item["expr"].is_null()
+                    || item["rename_list"]
+                        .as_array()
+                        .is_some_and(|l| !l.is_empty())
+                    || item["qualified_exclude_list"]
+                        .as_array()
+                        .is_some_and(|l| !l.is_empty());
+                if unsupported {
+                    return None;
+                }
+                let replaced: Vec<&str> = item["replace_list"]
+                    .as_array()?
+                    .iter()
+                    .filter_map(|entry| entry["key"].as_str())
+                    .collect();
+                for &column in columns {
+                    if contains(&item["exclude_list"], column) {
+                        continue;
+                    }
+                    let replaced = replaced.iter().any(|r| r.eq_ignore_ascii_case(column));
+                    sources.push((!replaced).then_some(column));
+                }
+            }
+            _ => sources.push(None),
+        }
+    }
+    Some(sources)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_single_table_queries() {
+        assert_eq!(
+            single_table("SELECT * FROM main.\"My Items\" WHERE id > 1 ORDER BY a, b").as_deref(),
+            Some("main.\"My Items\"")
+        );
+        assert_eq!(
+            single_table("select id, (select max(x) from u) from t as x limit 5").as_deref(),
+            Some("t")
+        );
+        assert_eq!(single_table("SELECT * FROM a JOIN b USING (id)"), None);
+        assert_eq!(single_table("SELECT * FROM a, b"), None);
+        assert_eq!(single_table("SELECT count(*) FROM a GROUP BY x"), None);
+        assert_eq!(single_table("SELECT * FROM read_csv('a.csv')"), None);
+        assert_eq!(single_table("SELECT 1"), None);
+        assert_eq!(single_table("DELETE FROM t"), None);
+    }
+
+    #[test]
+    fn only_plain_column_references_are_editable() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        manager
+            .execute_query("CREATE TABLE t (id INTEGER PRIMARY KEY, name VARCHAR, note VARCHAR)")
+            .unwrap();
+        let detect = |sql: &str, columns: &[&str]| {
+            let result = QueryResult {
+                columns: columns.iter().map(|c| c.to_string()).collect(),
+                ..QueryResult::default()
+            };
+            DataEditor::detect(&manager, sql, &result).map(|e| (e.key, e.editable))
+        };
+
+        assert_eq!(
+            detect("SELECT * FROM t", &["id", "name", "note"]),
+            Some((vec![0], vec![true, true, true]))
+        );
+        assert_eq!(
+            detect("SELECT upper(name) AS name, t.id FROM t", &["name", "id"]),
+            Some((vec![1], vec![false, true]))
+        );
+        assert_eq!(
+            detect("SELECT id, name AS note FROM t", &["id", "note"]),
+            Some((vec![0], vec![true, false]))
+        );
+        assert_eq!(
+            detect(
+                "SELECT * EXCLUDE (note) REPLACE (lower(name) AS name) FROM t",
+                &["id", "name"]
+            ),
+            Some((vec![0], vec![true, false]))
+        );
+        // 主键是计算出来的列时不能定位行
+        assert_eq!(
+            detect("SELECT id + 1 AS id, name FROM t", &["id", "name"]),
+            None
+        );
+        assert_eq!(
+            detect("SELECT COLUMNS('n.*'), id FROM t", &["name", "note", "id"]),
+            None
+        );
+    }
+
+    #[test]
+    fn generates_dml_for_staged_changes() {
+        let result = QueryResult {
+            columns: vec!["id".into(), "name".into()],
+            rows: vec![
+                vec![Value::Int(1), Value::Text("a".into())],
+                vec![Value::Int(2), Value::Null],
+            ],
+            ..QueryResult::default()
+        };
+        let mut editor = DataEditor {
+            table: TableName::parse("items").unwrap(),
+            key: vec![0],
+            editable: vec![true, true],
+            ..DataEditor::default()
+        };
+
+        editor.set_cell(&result, 0, 1, CellEdit::Text("it's".into()));
+        editor.set_cell(&result, 1, 1, CellEdit::Null);
+        assert_eq!(editor.updates.len(), 1);
+        editor.toggle_delete(2, 1);
+        editor.add_row(2);
+        editor.set_cell(&result, 2, 0, CellEdit::Text("3".into()));
+        editor.add_row(2);
+
+        assert_eq!(
+            editor.statements(&result),
+            [
+                "DELETE FROM \"items\" WHERE \"id\" = 2",
+                "UPDATE \"items\" SET \"name\" = 'it''s' WHERE \"id\" = 1",
+                "INSERT INTO \"items\" (\"id\") VALUES ('3')",
+                "INSERT INTO \"items\" DEFAULT VALUES",
+            ]
+        );
+
+        editor.toggle_delete(2, 3);
+        editor.set_cell(&result, 0, 1, CellEdit::Text("a".into()));
+        editor.toggle_delete(2, 1);
+        assert_eq!(editor.statements(&result).len(), 1);
+    }
+}
@@ -0,0 +1,426 @@
+//! 执行计划模块
+//! 解析 `EXPLAIN (FORMAT JSON)` 的输出，并以算子树的形式绘制
+
+use crate::i18n::{tr, tr_args};
+use eframe::egui::{self, Color32};
+use serde_json::Value as Json;
+
+/// 算子框的宽度
+const NODE_WIDTH: f32 = 200.0;
+
+/// 算子之间的水平和垂直间距
+const NODE_SPACING: egui::Vec2 = egui::vec2(24.0, 36.0);
+
+/// 高亮显示耗时最多的算子个数
+const HOTSPOT_COUNT: usize = 3;
+
+/// 估计行数与实际行数相差多少倍时提示
+const ESTIMATE_ERROR_RATIO: f64 = 10.0;
+
+/// 执行计划中的算子
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlanNode {
+    pub name: String,
+    pub details: Vec<(String, String)>, // extra_info 中的其他信息
+    pub estimated_rows: Option<u64>,    // 优化器估计的行数
+    pub actual_rows: Option<u64>,       // EXPLAIN ANALYZE 统计的实际行数
+    pub timing: Option<f64>,            // 算子自身耗时（秒）
+    pub children: Vec<PlanNode>,
+}
+
+impl PlanNode {
+    /// 解析一个算子及其子算子
+    fn from_json(json: &Json) -> Self {
+        let name = json
+            .get("operator_name")
+            .or_else(|| json.get("name"))
+            .and_then(Json::as_str)
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+
+        let mut node = Self {
+            name,
+            actual_rows: json.get("operator_cardinality").and_then(Json::as_u64),
+            timing: json.get("operator_timing").and_then(Json::as_f64),
+            ..Self::default()
+        };
+
+        if let Some(extra) = json.get("extra_info").and_then(Json::as_object) {
+            for (key, value) in extra {
+                let text = match value {
+                    Json::String(s) => s.clone(),
+                    Json::Array(items) => items
+                        .iter()
+                        .map(|item| {
+                            item.as_str()
+                                .map_or_else(|| item.to_string(), str::to_string)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    other => other.to_string(),
+                };
+                if key == "Estimated Cardinality" {
+                    node.estimated_rows = text.trim().parse().ok();
+                } else {
+                    node.details.push((key.clone(), text));
+                }
+            }
+        }
+
+        node.children = json
+            .get("children")
+            .and_then(Json::as_array)
+            .map(|children| children.iter().map(Self::from_json).collect())
+            .unwrap_or_default();
+        node
+    }
+
+    /// 估计行数与实际行数是否相差过大
+    pub fn misestimated(&self) -> bool {
+        match (self.estimated_rows, self.actual_rows) {
+            (Some(estimated), Some(actual)) => {
+                let (estimated, actual) = (estimated.max(1) as f64, actual.max(1) as f64);
+                estimated / actual >= ESTIMATE_ERROR_RATIO
+                    || actual / estimated >= ESTIMATE_ERROR_RATIO
+            }
+            _ => false,
+        }
+    }
+
+    /// 遍历自身和所有子算子
+    fn walk<'a>(&'a self, nodes: &mut Vec<&'a PlanNode>) {
+        nodes.push(self);
+        for child in &self.children {
+            child.walk(nodes);
+        }
+    }
+}
+
+/// 执行计划
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExplainPlan {
+    pub analyzed: bool,       // 是否为 EXPLAIN ANALYZE 的结果
+    pub latency: Option<f64>, // 查询总耗时（秒）
+    pub roots: Vec<PlanNode>,
+}
+
+impl ExplainPlan {
+    /// 解析 `EXPLAIN (FORMAT JSON)` 或 `EXPLAIN (ANALYZE, FORMAT JSON)` 的输出
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let json: Json = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        match &json {
+            // EXPLAIN：算子数组
+            Json::Array(nodes) => Ok(Self {
+                analyzed: false,
+                latency: None,
+                roots: nodes.iter().map(PlanNode::from_json).collect(),
+            }),
+            // EXPLAIN ANALYZE：查询级的统计信息，算子在 children 中
+            Json::Object(root) => Ok(Self {
+                analyzed: true,
+                latency: root.get("latency").and_then(Json::as_f64),
+                roots: root
+                    .get("children")
+                    .and_then(Json::as_array)
+                    .map(|children| children.iter().map(PlanNode::from_json).collect())
+                    .unwrap_or_default(),
+            }),
+            _ => Err(tr("explain.invalid_output").to_string()),
+        }
+    }
+
+    /// 所有算子
+    pub fn nodes(&self) -> Vec<&PlanNode> {
+        let mut nodes = Vec::new();
+        for root in &self.roots {
+            root.walk(&mut nodes);
+        }
+        nodes
+    }
+
+    /// 所有算子耗时之和（秒）
+    pub fn total_timing(&self) -> f64 {
+        self.nodes().iter().filter_map(|node| node.timing).sum()
+    }
+
+    /// 耗时最多的几个算子的耗时下限，用于高亮
+    fn hotspot_threshold(&self) -> Option<f64> {
+        let mut timings: Vec<f64> = self
+            .nodes()
+            .iter()
+            .filter_map(|node| node.timing)
+            .filter(|&t| t > 0.0)
+            .collect();
+        timings.sort_by(|a, b| b.total_cmp(a));
+        timings
+            .get(HOTSPOT_COUNT.min(timings.len()).checked_sub(1)?)
+            .copied()
+    }
+}
+
+/// 算子在画布上的位置
+struct LaidOutNode<'a> {
+    node: &'a PlanNode,
+    rect: egui::Rect,
+    parent: Option<usize>,
+}
+
+/// 计算算子树的位置：子算子从左到右排列，父算子位于子算子上方正中
+fn layout<'a>(
+    node: &'a PlanNode,
+    depth: usize,
+    node_height: f32,
+    next_x: &mut f32,
+    parent: Option<usize>,
+    out: &mut Vec<LaidOutNode<'a>>,
+) {
+    let index = out.len();
+    let y = depth as f32 * (node_height + NODE_SPACING.y);
+    out.push(LaidOutNode {
+        node,
+        rect: egui::Rect::NOTHING,
+        parent,
+    });
+
+    let x = if node.children.is_empty() {
+        let x = *next_x;
+        *next_x += NODE_WIDTH + NODE_SPACING.x;
+        x
+    } else {
+        let first = out.len();
+        for child in &node.children {
+            layout(child, depth + 1, node_height, next_x, Some(index), out);
+        }
+        let children: Vec<f32> = out[first..]
+            .iter()
+            .filter(|n| n.parent == Some(index))
+            .map(|n| n.rect.min.x)
+            .collect();
+        (children[0] + children[children.len() - 1]) / 2.0
+    };
+
+    out[index].rect =
+        egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(NODE_WIDTH, node_height));
+}
+
+/// 格式化耗时
+fn format_seconds(seconds: f64) -> String {
+    if seconds >= 1.0 {
+        format!("{:.2} s", seconds)
+    } else {
+        format!("{:.2} ms", seconds * 1000.0)
+    }
+}
+
+/// 绘制执行计划
+pub fn show(ui: &mut egui::Ui, plan: &ExplainPlan) {
+    let total = plan.total_timing();
+    ui.horizontal(|ui| {
+        let kind = if plan.analyzed {
+            tr("explain.analyzed")
+        } else {
+            tr("explain.estimated")
+        };
+        ui.label(egui::RichText::new(kind).strong());
+        if let Some(latency) = plan.latency {
+            let text = tr_args("explain.latency", &[("time", &format_seconds(latency))]);
+            ui.label(egui::RichText::new(text).weak());
+        }
+    });
+
+    if plan.roots.is_empty() {
+        ui.label(egui::RichText::new(tr("explain.empty")).weak());
+        return;
+    }
+
+    let font = egui::TextStyle::Small.resolve(ui.style());
+    let line_height = ui.fonts_mut(|f| f.row_height(&font));
+    let lines = if plan.analyzed { 3.0 } else { 2.0 };
+    let node_height = line_height * lines + 12.0;
+
+    let mut nodes = Vec::new();
+    let mut next_x = 0.0;
+    for root in &plan.roots {
+        layout(root, 0, node_height, &mut next_x, None, &mut nodes);
+    }
+    let size = nodes
+        .iter()
+        .fold(egui::Vec2::ZERO, |size, n| size.max(n.rect.max.to_vec2()));
+
+    let hotspot = plan.hotspot_threshold();
+    let visuals = ui.visuals().clone();
+    egui::ScrollArea::both()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            let (response, painter) =
+                ui.allocate_painter(size + egui::vec2(16.0, 16.0), egui::Sense::hover());
+            let origin = response.rect.min.to_vec2() + egui::vec2(8.0, 8.0);
+
+            // 连线
+            for laid in &nodes {
+                if let Some(parent) = laid.parent {
+                    let from = nodes[parent].rect.center_bottom() + origin;
+                    let to = laid.rect.center_top() + origin;
+                    painter.line_segment(
+                        [from, to],
+                        egui::Stroke::new(1.0, visuals.weak_text_color()),
+                    );
+                }
+            }
+
+            for (i, laid) in nodes.iter().enumerate() {
+                let node = laid.node;
+                let rect = laid.rect.translate(origin);
+                let share = match (node.timing, total > 0.0) {
+                    (Some(timing), true) => (timing / total) as f32,
+                    _ => 0.0,
+                };
+                let is_hotspot = matches!((node.timing, hotspot), (Some(t), Some(h)) if t >= h);
+
+                // 耗时占比越高，背景越接近错误色
+                let fill = visuals
+                    .extreme_bg_color
+                    .lerp_to_gamma(visuals.error_fg_color, share.clamp(0.0, 1.0) * 0.6);
+                let stroke = if is_hotspot {
+                    egui::Stroke::new(2.0, visuals.error_fg_color)
+                } else {
+                    egui::Stroke::new(1.0, visuals.widgets.noninteractive.bg_stroke.color)
+                };
+                painter.rect(rect, 4.0, fill, stroke, egui::StrokeKind::Inside);
+
+                let mut y = rect.min.y + 6.0;
+                let mut text = |content: String, color: Color32| {
+                    let galley = painter.layout(content, font.clone(), color, NODE_WIDTH - 12.0);
+                    painter.galley(egui::pos2(rect.min.x + 6.0, y), galley, color);
+                    y += line_height;
+                };
+
+                text(node.name.clone(), visuals.strong_text_color());
+                if let Some(timing) = node.timing {
+                    text(
+                        format!("{} · {:.1}%", format_seconds(timing), share * 100.0),
+                        visuals.text_color(),
+                    );
+                }
+                let rows_color = if node.misestimated() {
+                    visuals.warn_fg_color
+                } else {
+                    visuals.weak_text_color()
+                };
+                let estimated = node
+                    .estimated_rows
+                    .map_or_else(|| "-".to_string(), |n| n.to_string());
+                let rows = match node.actual_rows {
+                    Some(actual) => tr_args(
+                        "explain.rows_actual",
+                        &[("estimated", &estimated), ("actual", &actual)],
+                    ),
+                    None => tr_args("explain.rows_estimated", &[("estimated", &estimated)]),
+                };
+                text(rows, rows_color);
+
+                let hover = ui.interact(rect, ui.id().with(("plan_node", i)), egui::Sense::hover());
+                if !node.details.is_empty() || node.misestimated() {
+                    hover.on_hover_ui(|ui| {
+                        ui.strong(&node.name);
+                        if node.misestimated() {
+                            ui.colored_label(visuals.warn_fg_color, tr("explain.misestimated"));
+                        }
+                        egui::Grid::new(("plan_details", i)).show(ui, |ui| {
+                            for (key, value) in &node.details {
+                                ui.label(egui::RichText::new(key).weak());
+                                ui.add(egui::Label::new(value).wrap());
+                                ui.end_row();
+                            }
+                        });
+                    });
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_estimated_plan() {
+        let plan = ExplainPlan::parse(
+            r#"[{
+                "name": "PROJECTION",
+                "children": [{
+                    "name": "SEQ_SCAN ",
+                    "children": [],
+                    "extra_info": { "Table": "t", "Projections": ["a", "b"], "Estimated Cardinality": "200" }
+                }],
+                "extra_info": {}
+            }]"#,
+        )
+        .unwrap();
+
+        assert!(!plan.analyzed);
+        let nodes = plan.nodes();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1].name, "SEQ_SCAN");
+        assert_eq!(nodes[1].estimated_rows, Some(200));
+        assert_eq!(
+            nodes[1].details,
+            [
+                ("Projections".to_string(), "a, b".to_string()),
+                ("Table".to_string(), "t".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn analyzed_plan_marks_hotspots_and_misestimates() {
+        let plan = ExplainPlan::parse(
+            r#"{
+                "latency": 0.5,
+                "children": [{
+                    "operator_name": "HASH_JOIN",
+                    "operator_timing": 0.3,
+                    "operator_cardinality": 5000,
+                    "extra_info": { "Estimated Cardinality": "10" },
+                    "children": [
+                        { "operator_name": "SEQ_SCAN", "operator_timing": 0.1, "operator_cardinality": 100, "children": [] },
+                        { "operator_name": "SEQ_SCAN", "operator_timing": 0.05, "operator_cardinality": 80, "children": [] },
+                        { "operator_name": "DUMMY_SCAN", "operator_timing": 0.01, "children": [] }
+                    ]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        assert!(plan.analyzed);
+        assert_eq!(plan.latency, Some(0.5));
+        assert!((plan.total_timing() - 0.46).abs() < 1e-9);
+        assert_eq!(plan.hotspot_threshold(), Some(0.05));
+        assert!(plan.nodes()[0].misestimated());
+        assert!(!plan.nodes()[1].misestimated());
+    }
+
+    #[test]
+    fn layout_centers_parents_over_children() {
+        let plan = ExplainPlan::parse(
+            r#"[{ "name": "JOIN", "children": [
+                { "name": "A", "children": [] },
+                { "name": "B", "children": [] }
+            ] }]"#,
+        )
+        .unwrap();
+
+        let mut nodes = Vec::new();
+        let mut next_x = 0.0;
+        layout(&plan.roots[0], 0, 40.0, &mut next_x, None, &mut nodes);
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[1].rect.min.x, 0.0);
+        assert_eq!(nodes[2].rect.min.x, NODE_WIDTH + NODE_SPACING.x);
+        assert_eq!(
+            nodes[0].rect.center().x,
+            (nodes[1].rect.center().x + nodes[2].rect.center().x) / 2.0
+        );
+        assert!(nodes[1].rect.min.y > nodes[0].rect.max.y);
+    }
+}
@@ -0,0 +1,528 @@
+//! 结果导出模块
+//! 把结果表格中选中的数据转换为 TSV、CSV、Markdown、JSON、INSERT 语句或 IN 列表
+
+use crate::app::MyApp;
+use crate::database::{QueryResult, format_value, quote_identifier, sql_literal};
+use crate::i18n::{tr, tr_args};
+use duckdb::types::Value;
+use eframe::egui;
+use std::ops::RangeInclusive;
+
+/// 复制对话框中预览的最大行数
+const PREVIEW_ROWS: usize = 20;
+
+/// 没有表名时 INSERT 语句使用的表名
+const DEFAULT_TABLE_NAME: &str = "table_name";
+
+/// 复制格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CopyFormat {
+    #[default]
+    Tsv, // 制表符分隔，可以直接粘贴到电子表格
+    Csv,
+    Markdown,
+    Json,
+    Insert, // INSERT 语句
+    InList, // `IN (...)` 中的值列表
+}
+
+impl CopyFormat {
+    /// 菜单中的全部格式
+    pub const ALL: [CopyFormat; 6] = [
+        CopyFormat::Tsv,
+        CopyFormat::Csv,
+        CopyFormat::Markdown,
+        CopyFormat::Json,
+        CopyFormat::Insert,
+        CopyFormat::InList,
+    ];
+
+    /// 显示名称的文本键
+    pub fn label(self) -> &'static str {
+        match self {
+            CopyFormat::Tsv => "copy.format_tsv",
+            CopyFormat::Csv => "copy.format_csv",
+            CopyFormat::Markdown => "copy.format_markdown",
+            CopyFormat::Json => "copy.format_json",
+            CopyFormat::Insert => "copy.format_insert",
+            CopyFormat::InList => "copy.format_in_list",
+        }
+    }
+
+    /// 对应的菜单动作
+    pub fn action(self) -> &'static str {
+        match self {
+            CopyFormat::Tsv => "copy_tsv",
+            CopyFormat::Csv => "copy_csv",
+            CopyFormat::Markdown => "copy_markdown",
+            CopyFormat::Json => "copy_json",
+            CopyFormat::Insert => "copy_insert",
+            CopyFormat::InList => "copy_in_list",
+        }
+    }
+
+    /// 是否使用分隔符和引号选项
+    fn delimited(self) -> bool {
+        matches!(self, CopyFormat::Tsv | CopyFormat::Csv)
+    }
+}
+
+/// TSV 和 CSV 中给值加引号的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuoteMode {
+    #[default]
+    Minimal, // 只在包含分隔符、引号或换行时加引号
+    All,
+    Never,
+}
+
+impl QuoteMode {
+    /// 选择框中的全部方式
+    pub const ALL: [QuoteMode; 3] = [QuoteMode::Minimal, QuoteMode::All, QuoteMode::Never];
+
+    /// 显示名称的文本键
+    pub fn label(self) -> &'static str {
+        match self {
+            QuoteMode::Minimal => "copy.quote_minimal",
+            QuoteMode::All => "copy.quote_all",
+            QuoteMode::Never => "copy.quote_never",
+        }
+    }
+}
+
+/// 复制选项
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyOptions {
+    pub format: CopyFormat,
+    pub headers: bool,     // TSV 和 CSV 是否包含列名
+    pub quote: QuoteMode,  // TSV 和 CSV 的引号
+    pub null_text: String, // TSV、CSV 和 Markdown 中 NULL 的写法
+    pub table: String,     // INSERT 语句的表名，为空时使用结果所属的表
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        Self {
+            format: CopyFormat::Tsv,
+            headers: false,
+            quote: QuoteMode::Minimal,
+            null_text: String::new(),
+            table: String::new(),
+        }
+    }
+}
+
+/// 要复制的数据
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CopyData<'a> {
+    pub columns: Vec<&'a str>,
+    pub rows: Vec<Vec<&'a Value>>,
+    pub table: Option<String>, // 结果所属的表
+}
+
+impl<'a> CopyData<'a> {
+    /// 取出结果中指定的行和列，超出结果的行和列会被忽略
+    pub fn new(
+        result: &'a QueryResult,
+        rows: impl IntoIterator<Item = usize>,
+        columns: RangeInclusive<usize>,
+    ) -> Self {
+        let columns: Vec<usize> = columns.filter(|&c| c < result.columns.len()).collect();
+        Self {
+            columns: columns
+                .iter()
+                .map(|&c| result.columns[c].as_str())
+                .collect(),
+            rows: rows
+                .into_iter()
+                .filter_map(|r| result.rows.get(r))
+                .map(|values| columns.iter().map(|&c| &values[c]).collect())
+                .collect(),
+            table: None,
+        }
+    }
+}
+
+/// 按选项把数据转换为文本
+pub fn format(data: &CopyData, options: &CopyOptions) -> String {
+    match options.format {
+        CopyFormat::Tsv => delimited(data, '\t', options),
+        CopyFormat::Csv => delimited(data, ',', options),
+        CopyFormat::Markdown => markdown(data, options),
+        CopyFormat::Json => json(data),
+        CopyFormat::Insert => insert_statements(data, options),
+        CopyFormat::InList => in_list(data),
+    }
+}
+
+/// 分隔符格式，NULL 不加引号，以便与空字符串区分
+fn delimited(data: &CopyData, separator: char, options: &CopyOptions) -> String {
+    let field = |text: &str| {
+        let special = text.contains(separator) || text.contains(['"', '\n', '\r']);
+        match options.quote {
+            QuoteMode::All => format!("\"{}\"", text.replace('"', "\"\"")),
+            QuoteMode::Minimal if special => format!("\"{}\"", text.replace('"', "\"\"")),
+            _ => text.to_string(),
+        }
+    };
+    let separator = separator.to_string();
+
+    let mut lines = Vec::new();
+    if options.headers {
+        let names: Vec<String> = data.columns.iter().map(|name| field(name)).collect();
+        lines.push(names.join(&separator));
+    }
+    for row in &data.rows {
+        let values: Vec<String> = row
+            .iter()
+            .map(|value| match value {
+                Value::Null => options.null_text.clone(),
+                value => field(&format_value(value)),
+            })
+            .collect();
+        lines.push(values.join(&separator));
+    }
+    lines.join("\n")
+}
+
+/// Markdown 表格
+fn markdown(data: &CopyData, options: &CopyOptions) -> String {
+    let escape = |text: &str| text.replace('|', "\\|").replace('\n', "<br>");
+    let line = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+
+    let mut lines = vec![
+        line(data.columns.iter().map(|name| escape(name)).collect()),
+        line(data.columns.iter().map(|_| "---".to_string()).collect()),
+    ];
+    for row in &data.rows {
+        lines.push(line(
+            row.iter()
+                .map(|value| match value {
+                    Value::Null => escape(&options.null_text),
+                    value => escape(&format_value(value)),
+                })
+                .collect(),
+        ));
+    }
+    lines.join("\n")
+}
+
+/// JSON 对象数组，每行一个对象，键的顺序与列一致
+fn json(data: &CopyData) -> String {
+    let rows: Vec<String> = data
+        .rows
+        .iter()
+        .map(|row| {
+            let fields: Vec<String> = data
+                .columns
+                .iter()
+                .zip(row)
+                .map(|(name, value)| format!("{}: {}", json_string(name), json_value(value)))
+                .collect();
+            format!("  {{{}}}", fields.join(", "))
+        })
+        .collect();
+    if rows.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", rows.join(",\n"))
+    }
+}
+
+/// 把值写成 JSON，嵌套的列表和结构保持原有顺序
+pub fn json_value(value: &Value) -> String {
+    let join = |items: Vec<String>| items.join(", ");
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Boolean(_)
+        | Value::TinyInt(_)
+        | Value::SmallInt(_)
+        | Value::Int(_)
+        | Value::BigInt(_)
+        | Value::HugeInt(_)
+        | Value::UHugeInt(_)
+        | Value::UTinyInt(_)
+        | Value::USmallInt(_)
+        | Value::UInt(_)
+        | Value::UBigInt(_)
+        | Value::Decimal(_) => format_value(value),
+        Value::Float(v) if v.is_finite() => format_value(value),
+        Value::Double(v) if v.is_finite() => format_value(value),
+        Value::List(items) | Value::Array(items) => {
+            format!("[{}]", join(items.iter().map(json_value).collect()))
+        }
+        Value::Struct(fields) => format!(
+            "{{{}}}",
+            join(
+                fields
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", json_string(k), json_value(v)))
+                    .collect()
+            )
+        ),
+        Value::Map(entries) => format!(
+            "{{{}}}",
+            join(
+                entries
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", json_string(&format_value(k)), json_value(v)))
+                    .collect()
+            )
+        ),
+        Value::Union(v) => json_value(v),
+        other => json_string(&format_value(other)),
+    }
+}
+
+/// JSON 字符串
+pub fn json_string(text: &str) -> String {
+    serde_json::Value::from(text).to_string()
+}
+
+/// 每行一条 INSERT 语句
+fn insert_statements(data: &CopyData, options: &CopyOptions) -> String {
+    let table = match options.table.trim() {
+        "" => data.table.as_deref().unwrap_or(DEFAULT_TABLE_NAME),
+        table => table,
+    };
+    let columns: Vec<String> = data
+        .columns
+        .iter()
+        .map(|name| quote_identifier(name))
+        .collect();
+    data.rows
+        .iter()
+        .map(|row| {
+            let values: Vec<String> = row.iter().map(|value| sql_literal(value)).collect();
+            format!(
+                "INSERT INTO {} ({}) VALUES ({});",
+                table,
+                columns.join(", "),
+                values.join(", ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 去重后的值列表，多列时每行是一个元组
+fn in_list(data: &CopyData) -> String {
+    let mut items: Vec<String> = Vec::new();
+    for row in &data.rows {
+        let values: Vec<String> = row.iter().map(|value| sql_literal(value)).collect();
+        let item = match values.as_slice() {
+            [value] => value.clone(),
+            _ => format!("({})", values.join(", ")),
+        };
+        if !items.contains(&item) {
+            items.push(item);
+        }
+    }
+    format!("({})", items.join(", "))
+}
+
+/// 高级复制对话框
+pub struct CopyDialog {
+    pub options: CopyOptions,
+}
+
+impl CopyDialog {
+    /// 渲染对话框
+    pub fn show(app: &mut MyApp, ctx: &egui::Context) {
+        let Some(mut dialog) = app.copy_dialog.take() else {
+            return;
+        };
+
+        let mut is_open = true;
+        let mut confirmed = false;
+        let mut cancelled = false;
+        let data = app.selected_data();
+        let table_hint = data
+            .as_ref()
+            .and_then(|data| data.table.clone())
+            .unwrap_or_else(|| DEFAULT_TABLE_NAME.to_string());
+        let preview = data.map(|mut data| {
+            let total = data.rows.len();
+            data.rows.truncate(PREVIEW_ROWS);
+            (format(&data, &dialog.options), total)
+        });
+
+        egui::Window::new(tr("copy.dialog_title"))
+            .id(egui::Id::new("copy_dialog"))
+            .open(&mut is_open)
+            .collapsible(false)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                let options = &mut dialog.options;
+                egui::Grid::new("copy_options_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("copy.format"));
+                        egui::ComboBox::from_id_salt("copy_format")
+                            .selected_text(tr(options.format.label()))
+                            .show_ui(ui, |ui| {
+                                for format in CopyFormat::ALL {
+                                    ui.selectable_value(
+                                        &mut options.format,
+                                        format,
+                                        tr(format.label()),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        let delimited = options.format.delimited();
+                        ui.label(tr("copy.headers"));
+                        ui.add_enabled(
+                            delimited,
+                            egui::Checkbox::without_text(&mut options.headers),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr("copy.quote"));
+                        ui.add_enabled_ui(delimited, |ui| {
+                            egui::ComboBox::from_id_salt("copy_quote")
+                                .selected_text(tr(options.quote.label()))
+                                .show_ui(ui, |ui| {
+                                    for quote in QuoteMode::ALL {
+                                        ui.selectable_value(
+                                            &mut options.quote,
+                                            quote,
+                                            tr(quote.label()),
+                                        );
+                                    }
+                                });
+                        });
+                        ui.end_row();
+
+                        ui.label(tr("copy.null_text"));
+                        ui.add_enabled(
+                            delimited || options.format == CopyFormat::Markdown,
+                            egui::TextEdit::singleline(&mut options.null_text)
+                                .hint_text(tr("copy.null_empty")),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr("copy.table"));
+                        ui.add_enabled(
+                            options.format == CopyFormat::Insert,
+                            egui::TextEdit::singleline(&mut options.table).hint_text(&table_hint),
+                        );
+                        ui.end_row();
+                    });
+
+                ui.separator();
+                match &preview {
+                    Some((text, total)) => {
+                        ui.label(
+                            egui::RichText::new(tr_args("copy.preview", &[("count", total)]))
+                                .weak(),
+                        );
+                        egui::ScrollArea::both()
+                            .id_salt("copy_preview")
+                            .max_height(200.0)
+                            .show(ui, |ui| {
+                                ui.add(egui::TextEdit::multiline(&mut text.as_str()).code_editor());
+                            });
+                    }
+                    None => {
+                        ui.label(egui::RichText::new(tr("status.nothing_selected")).weak());
+                    }
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(preview.is_some(), egui::Button::new(tr("copy.copy")))
+                        .clicked()
+                    {
+                        confirmed = true;
+                    }
+                    if ui.button(tr("copy.cancel")).clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+
+        if confirmed {
+            app.copy_options = dialog.options.clone();
+            app.copy_with_options(&dialog.options);
+        } else if is_open && !cancelled {
+            app.copy_dialog = Some(dialog);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> QueryResult {
+        QueryResult {
+            columns: vec!["id".into(), "name".into(), "tags".into()],
+            rows: vec![
+                vec![
+                    Value::Int(1),
+                    Value::Text("a,\"b\"".into()),
+                    Value::List(vec![Value::Text("x".into())]),
+                ],
+                vec![Value::Int(2), Value::Null, Value::List(Vec::new())],
+                vec![Value::Int(1), Value::Text("c|d".into()), Value::Null],
+            ],
+            ..QueryResult::default()
+        }
+    }
+
+    #[test]
+    fn formats_delimited_text() {
+        let result = sample();
+        let data = CopyData::new(&result, 0..=1, 0..=1);
+        let csv = CopyOptions {
+            format: CopyFormat::Csv,
+            headers: true,
+            null_text: "NULL".into(),
+            ..CopyOptions::default()
+        };
+        assert_eq!(format(&data, &csv), "id,name\n1,\"a,\"\"b\"\"\"\n2,NULL");
+
+        let tsv = CopyOptions {
+            quote: QuoteMode::All,
+            ..CopyOptions::default()
+        };
+        assert_eq!(format(&data, &tsv), "\"1\"\t\"a,\"\"b\"\"\"\n\"2\"\t");
+    }
+
+    #[test]
+    fn formats_markdown_json_and_sql() {
+        let result = sample();
+        let options = |format| CopyOptions {
+            format,
+            ..CopyOptions::default()
+        };
+
+        let data = CopyData::new(&result, 1..=2, 1..=1);
+        assert_eq!(
+            format(&data, &options(CopyFormat::Markdown)),
+            "| name |\n| --- |\n|  |\n| c\\|d |"
+        );
+
+        let data = CopyData::new(&result, 0..=1, 0..=5);
+        assert_eq!(
+            format(&data, &options(CopyFormat::Json)),
+            "[\n  {\"id\": 1, \"name\": \"a,\\\"b\\\"\", \"tags\": [\"x\"]},\n  {\"id\": 2, \"name\": null, \"tags\": []}\n]"
+        );
+
+        let data = CopyData {
+            table: Some("main.items".into()),
+            ..CopyData::new(&result, 1..=1, 0..=1)
+        };
+        assert_eq!(
+            format(&data, &options(CopyFormat::Insert)),
+            "INSERT INTO main.items (\"id\", \"name\") VALUES (2, NULL);"
+        );
+
+        let data = CopyData::new(&result, 0..=2, 0..=0);
+        assert_eq!(format(&data, &options(CopyFormat::InList)), "(1, 2)");
+    }
+}
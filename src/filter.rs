@@ -0,0 +1,396 @@
+//! 结果筛选模块
+//! 在客户端按列筛选结果行、快速搜索高亮单元格，或把筛选条件作为 WHERE 子句交给 DuckDB 重新执行
+
+use crate::database::{QueryResult, format_value, quote_identifier, quote_string};
+use crate::i18n::{tr, tr_args};
+use crate::results::GridState;
+use duckdb::types::Value;
+use eframe::egui;
+use regex::Regex;
+use std::cmp::Ordering;
+
+/// 筛选条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilterOp {
+    #[default]
+    Equals,
+    Contains, // 不区分大小写
+    Range,    // 闭区间，任意一端可以为空
+    IsNull,
+    Regex,
+}
+
+impl FilterOp {
+    /// 选择框中的全部条件
+    pub const ALL: [FilterOp; 5] = [
+        FilterOp::Equals,
+        FilterOp::Contains,
+        FilterOp::Range,
+        FilterOp::IsNull,
+        FilterOp::Regex,
+    ];
+
+    /// 显示名称的文本键
+    pub fn label(self) -> &'static str {
+        match self {
+            FilterOp::Equals => "filter.op_equals",
+            FilterOp::Contains => "filter.op_contains",
+            FilterOp::Range => "filter.op_range",
+            FilterOp::IsNull => "filter.op_is_null",
+            FilterOp::Regex => "filter.op_regex",
+        }
+    }
+}
+
+/// 单列的筛选条件
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnFilter {
+    pub column: usize, // 列在结果中的位置
+    pub op: FilterOp,
+    pub value: String, // 比较的值，范围条件的下限
+    pub upper: String, // 范围条件的上限
+}
+
+impl ColumnFilter {
+    /// 没有填写值的条件不参与筛选
+    fn is_active(&self) -> bool {
+        match self.op {
+            FilterOp::IsNull => true,
+            FilterOp::Range => !self.value.trim().is_empty() || !self.upper.trim().is_empty(),
+            _ => !self.value.is_empty(),
+        }
+    }
+
+    /// 判断一个值是否满足条件，`regex` 为编译好的正则表达式
+    fn matches(&self, value: &Value, regex: Option<&Regex>) -> bool {
+        if matches!(value, Value::Null) {
+            return self.op == FilterOp::IsNull;
+        }
+        let text = format_value(value);
+        match self.op {
+            FilterOp::Equals => compare(&text, &self.value) == Some(Ordering::Equal),
+            FilterOp::Contains => text.to_lowercase().contains(&self.value.to_lowercase()),
+            FilterOp::Range => {
+                let lower = self.value.trim();
+                let upper = self.upper.trim();
+                (lower.is_empty() || compare(&text, lower).is_some_and(Ordering::is_ge))
+                    && (upper.is_empty() || compare(&text, upper).is_some_and(Ordering::is_le))
+            }
+            FilterOp::IsNull => false,
+            FilterOp::Regex => regex.is_some_and(|regex| regex.is_match(&text)),
+        }
+    }
+
+    /// WHERE 中的条件，值写成字符串字面量，由 DuckDB 转换为列的类型
+    fn to_sql(&self, column: &str) -> String {
+        let column = quote_identifier(column);
+        let text = || format!("CAST({} AS VARCHAR)", column);
+        match self.op {
+            FilterOp::Equals => format!("{} = {}", column, quote_string(&self.value)),
+            FilterOp::Contains => format!(
+                "contains(lower({}), {})",
+                text(),
+                quote_string(&self.value.to_lowercase())
+            ),
+            FilterOp::Range => {
+                let lower = self.value.trim();
+                let upper = self.upper.trim();
+                match (lower.is_empty(), upper.is_empty()) {
+                    (false, false) => format!(
+                        "{} BETWEEN {} AND {}",
+                        column,
+                        quote_string(lower),
+                        quote_string(upper)
+                    ),
+                    (false, true) => format!("{} >= {}", column, quote_string(lower)),
+                    _ => format!("{} <= {}", column, quote_string(upper)),
+                }
+            }
+            FilterOp::IsNull => format!("{} IS NULL", column),
+            FilterOp::Regex => format!("regexp_matches({}, {})", text(), quote_string(&self.value)),
+        }
+    }
+}
+
+/// 比较显示的文本和输入的值，两边都是数字时按数值比较
+fn compare(text: &str, value: &str) -> Option<Ordering> {
+    let value = value.trim();
+    match (text.parse::<f64>(), value.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b),
+        _ => Some(text.cmp(value)),
+    }
+}
+
+/// 结果表格的筛选和快速搜索
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResultFilter {
+    pub columns: Vec<ColumnFilter>,
+    pub search: String, // 快速搜索，只高亮不筛选
+}
+
+impl ResultFilter {
+    /// 是否有生效的列筛选条件
+    pub fn is_active(&self) -> bool {
+        self.columns.iter().any(ColumnFilter::is_active)
+    }
+
+    /// 满足全部条件的行，正则表达式无效时返回错误
+    pub fn apply(&self, result: &QueryResult) -> Result<Vec<usize>, String> {
+        let filters = self
+            .columns
+            .iter()
+            .filter(|filter| filter.is_active() && filter.column < result.columns.len())
+            .map(|filter| {
+                let regex = if filter.op == FilterOp::Regex {
+                    Some(Regex::new(&filter.value).map_err(|e| e.to_string())?)
+                } else {
+                    None
+                };
+                Ok((filter, regex))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let rows = result
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, values)| {
+                filters
+                    .iter()
+                    .all(|(filter, regex)| filter.matches(&values[filter.column], regex.as_ref()))
+            })
+            .map(|(index, _)| index)
+            .collect();
+        Ok(rows)
+    }
+
+    /// 单元格是否包含快速搜索的文本（不区分大小写），`needle` 为小写的搜索文本
+    pub fn search_matches(needle: &str, value: &Value) -> bool {
+        !needle.is_empty()
+            && !matches!(value, Value::Null)
+            && format_value(value).to_lowercase().contains(needle)
+    }
+
+    /// 把原查询包装为带 WHERE 子句的查询，没有生效的条件时返回 `None`
+    pub fn wrap_query(&self, sql: &str, columns: &[String]) -> Option<String> {
+        let conditions: Vec<String> = self
+            .columns
+            .iter()
+            .filter(|filter| filter.is_active())
+            .filter_map(|filter| Some(filter.to_sql(columns.get(filter.column)?)))
+            .collect();
+        if conditions.is_empty() {
+            return None;
+        }
+        // 原查询单独成行，末尾的行注释不会注释掉右括号
+        Some(format!(
+            "SELECT * FROM (\n{}\n) AS filtered\nWHERE {}",
+            sql.trim().trim_end_matches(';'),
+            conditions.join("\n  AND ")
+        ))
+    }
+}
+
+/// 渲染筛选栏，点击“在数据库中筛选”时返回 true
+pub fn filter_bar(ui: &mut egui::Ui, result: &QueryResult, state: &mut GridState) -> bool {
+    let mut changed = false;
+    let mut push_down = false;
+    let filter = &mut state.filter;
+
+    ui.horizontal(|ui| {
+        changed |= ui
+            .add(
+                egui::TextEdit::singleline(&mut filter.search)
+                    .hint_text(tr("filter.search_hint"))
+                    .desired_width(180.0),
+            )
+            .changed();
+        if ui.button(tr("filter.add")).clicked() {
+            filter.columns.push(ColumnFilter::default());
+        }
+        if !filter.columns.is_empty() && ui.button(tr("filter.clear")).clicked() {
+            filter.columns.clear();
+            changed = true;
+        }
+        if ui
+            .add_enabled(
+                filter.is_active(),
+                egui::Button::new(tr("filter.push_down")),
+            )
+            .on_hover_text(tr("filter.push_down_hint"))
+            .clicked()
+        {
+            push_down = true;
+        }
+    });
+
+    let mut remove = None;
+    for (i, column_filter) in filter.columns.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            let column_name = result
+                .columns
+                .get(column_filter.column)
+                .map_or("", String::as_str);
+            egui::ComboBox::from_id_salt(("filter_column", i))
+                .selected_text(column_name)
+                .show_ui(ui, |ui| {
+                    for (index, name) in result.columns.iter().enumerate() {
+                        changed |= ui
+                            .selectable_value(&mut column_filter.column, index, name)
+                            .changed();
+                    }
+                });
+            egui::ComboBox::from_id_salt(("filter_op", i))
+                .selected_text(tr(column_filter.op.label()))
+                .show_ui(ui, |ui| {
+                    for op in FilterOp::ALL {
+                        changed |= ui
+                            .selectable_value(&mut column_filter.op, op, tr(op.label()))
+                            .changed();
+                    }
+                });
+            match column_filter.op {
+                FilterOp::IsNull => {}
+                FilterOp::Range => {
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut column_filter.value)
+                                .hint_text(tr("filter.range_from"))
+                                .desired_width(100.0),
+                        )
+                        .changed();
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut column_filter.upper)
+                                .hint_text(tr("filter.range_to"))
+                                .desired_width(100.0),
+                        )
+                        .changed();
+                }
+                _ => {
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut column_filter.value)
+                                .desired_width(200.0),
+                        )
+                        .changed();
+                }
+            }
+            if ui.small_button("×").clicked() {
+                remove = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove {
+        filter.columns.remove(i);
+        changed = true;
+    }
+
+    if changed {
+        state.invalidate();
+    }
+    state.refresh(result);
+
+    let status = match (&state.filter_error, state.filter.is_active()) {
+        (Some(error), _) => Some(
+            egui::RichText::new(tr_args("filter.invalid", &[("error", error)]))
+                .color(ui.visuals().error_fg_color),
+        ),
+        (None, true) => {
+            let mut text = tr_args(
+                "filter.summary",
+                &[
+                    ("shown", &state.visible_count(result.rows.len())),
+                    ("total", &result.rows.len()),
+                ],
+            );
+            if result.truncated {
+                text.push_str(tr("filter.truncated_hint"));
+            }
+            Some(egui::RichText::new(text).weak())
+        }
+        (None, false) => None,
+    };
+    ui.horizontal(|ui| {
+        if let Some(status) = status {
+            ui.label(status);
+        }
+        if !state.filter.search.is_empty() {
+            let matches = tr_args("filter.matches", &[("count", &state.search_matches)]);
+            ui.label(egui::RichText::new(matches).weak());
+        }
+    });
+
+    push_down
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> QueryResult {
+        QueryResult {
+            columns: vec!["id".into(), "name".into(), "day".into()],
+            rows: vec![
+                vec![
+                    Value::Int(1),
+                    Value::Text("Apple".into()),
+                    Value::Date32(19723),
+                ],
+                vec![Value::Int(10), Value::Null, Value::Date32(19724)],
+                vec![Value::Int(2), Value::Text("banana".into()), Value::Null],
+            ],
+            ..QueryResult::default()
+        }
+    }
+
+    fn filter(column: usize, op: FilterOp, value: &str, upper: &str) -> ResultFilter {
+        ResultFilter {
+            columns: vec![ColumnFilter {
+                column,
+                op,
+                value: value.into(),
+                upper: upper.into(),
+            }],
+            ..ResultFilter::default()
+        }
+    }
+
+    #[test]
+    fn filters_rows_on_the_client() {
+        let result = sample();
+        let rows = |f: ResultFilter| f.apply(&result).unwrap();
+
+        assert_eq!(rows(filter(0, FilterOp::Equals, "10", "")), [1]);
+        assert_eq!(rows(filter(1, FilterOp::Contains, "AN", "")), [2]);
+        assert_eq!(rows(filter(0, FilterOp::Range, "2", "10")), [1, 2]);
+        assert_eq!(rows(filter(2, FilterOp::Range, "2024-01-02", "")), [1]);
+        assert_eq!(rows(filter(1, FilterOp::IsNull, "", "")), [1]);
+        assert_eq!(rows(filter(1, FilterOp::Regex, "^[A-Z]", "")), [0]);
+        assert_eq!(rows(filter(1, FilterOp::Equals, "", "")), [0, 1, 2]);
+        assert!(filter(1, FilterOp::Regex, "(", "").apply(&result).is_err());
+    }
+
+    #[test]
+    fn wraps_query_with_where_clause() {
+        let result = sample();
+        let mut f = filter(0, FilterOp::Range, "2", "");
+        f.columns.push(ColumnFilter {
+            column: 1,
+            op: FilterOp::Contains,
+            value: "It's".into(),
+            upper: String::new(),
+        });
+
+        assert_eq!(
+            f.wrap_query("SELECT * FROM t -- all;", &result.columns)
+                .unwrap(),
+            "SELECT * FROM (\nSELECT * FROM t -- all\n) AS filtered\n\
+             WHERE \"id\" >= '2'\n  AND contains(lower(CAST(\"name\" AS VARCHAR)), 'it''s')"
+        );
+        assert_eq!(
+            ResultFilter::default().wrap_query("SELECT 1", &result.columns),
+            None
+        );
+    }
+}
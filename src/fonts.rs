@@ -0,0 +1,216 @@
+//! 字体模块
+//! 加载随应用分发的字体，并在系统中查找 CJK 字体
+
+use eframe::egui;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, OnceLock};
+
+/// 优先使用的内置界面字体
+const PREFERRED_BUNDLED_FONT: &str = "mm.ttf";
+
+/// 支持的字体文件扩展名
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc"];
+
+/// fontconfig 不可用时尝试的常见 CJK 字体路径
+const FALLBACK_CJK_FONTS: &[&str] = &[
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\simhei.ttf",
+    "C:\\Windows\\Fonts\\simsun.ttc",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+    "/usr/share/fonts/wenquanyi/wqy-microhei/wqy-microhei.ttc",
+];
+
+/// 可供选择的字体
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontSource {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// 内置字体目录：crate 下的 `assets/fonts`，以及可执行文件旁的 `assets/fonts`
+pub fn bundled_font_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/fonts")];
+    if let Some(exe_dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    {
+        dirs.push(exe_dir.join("assets/fonts"));
+    }
+    dirs
+}
+
+/// 是否为支持的字体文件
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| FONT_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// 以文件名（不含扩展名）作为字体名称
+fn font_source(path: PathBuf) -> FontSource {
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string());
+    FontSource { name, path }
+}
+
+/// 内置字体，优先字体排在最前面
+pub fn bundled_fonts() -> Vec<FontSource> {
+    let mut fonts: Vec<FontSource> = bundled_font_dirs()
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_font_file(path))
+        .map(font_source)
+        .collect();
+
+    fonts.sort_by_key(|font| {
+        let preferred =
+            font.path.file_name().and_then(|n| n.to_str()) == Some(PREFERRED_BUNDLED_FONT);
+        (!preferred, font.name.to_lowercase())
+    });
+    fonts.dedup_by(|a, b| a.path.file_name() == b.path.file_name());
+    fonts
+}
+
+/// 通过 fontconfig 列出字体，`pattern` 为 fc-list 的匹配条件
+fn fc_list(pattern: &str) -> Vec<FontSource> {
+    let output = match Command::new("fc-list")
+        .args([pattern, "-f", "%{family[0]}\t%{file}\n"])
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        _ => return Vec::new(),
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(family, file)| FontSource {
+            name: family.to_string(),
+            path: PathBuf::from(file),
+        })
+        .filter(|font| is_font_file(&font.path))
+        .collect()
+}
+
+/// 在系统中查找 CJK 字体，结果会被缓存
+///
+/// 先使用 fontconfig 查询支持中文的字体（无衬线优先），
+/// 不可用时检查各平台的常见字体路径。
+pub fn system_cjk_fonts() -> &'static [FontSource] {
+    static FONTS: OnceLock<Vec<FontSource>> = OnceLock::new();
+    FONTS.get_or_init(|| {
+        let mut fonts = fc_list(":lang=zh");
+        fonts.sort_by_key(|font| {
+            let name = font.name.to_lowercase();
+            (!name.contains("sans"), name)
+        });
+
+        if fonts.is_empty() {
+            fonts = FALLBACK_CJK_FONTS
+                .iter()
+                .map(PathBuf::from)
+                .filter(|path| path.exists())
+                .map(font_source)
+                .collect();
+        }
+        fonts
+    })
+}
+
+/// 所有可供选择的字体（内置字体 + 系统字体），结果会被缓存
+pub fn available_fonts() -> &'static [FontSource] {
+    static FONTS: OnceLock<Vec<FontSource>> = OnceLock::new();
+    FONTS.get_or_init(|| {
+        let mut fonts = bundled_fonts();
+        let mut system = fc_list(":");
+        system.sort_by_key(|font| font.name.to_lowercase());
+        for font in system.iter().chain(system_cjk_fonts()) {
+            if !fonts.iter().any(|f| f.path == font.path) {
+                fonts.push(font.clone());
+            }
+        }
+        fonts
+    })
+}
+
+/// 读取字体文件
+fn load_font_data(path: &Path) -> Option<egui::FontData> {
+    match std::fs::read(path) {
+        Ok(bytes) => Some(egui::FontData::from_owned(bytes)),
+        Err(e) => {
+            eprintln!("读取字体失败 {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// 配置界面字体和等宽编辑器字体
+///
+/// `ui_font` 和 `mono_font` 为字体文件路径，为空时自动选择：
+/// 界面使用内置字体，中文回退到系统 CJK 字体；都找不到时保留 egui 默认字体。
+pub fn setup_fonts(ctx: &egui::Context, ui_font: &str, mono_font: &str) {
+    let mut fonts = egui::FontDefinitions::default();
+    let mut add_font = |name: &str, path: &Path, families: &[egui::FontFamily], primary: bool| {
+        let Some(data) = load_font_data(path) else {
+            return false;
+        };
+        fonts.font_data.insert(name.to_owned(), Arc::new(data));
+        for family in families {
+            if let Some(list) = fonts.families.get_mut(family) {
+                if primary {
+                    list.insert(0, name.to_owned());
+                } else {
+                    list.push(name.to_owned());
+                }
+            }
+        }
+        true
+    };
+
+    let both = [egui::FontFamily::Proportional, egui::FontFamily::Monospace];
+
+    // 界面字体：用户选择 > 内置字体
+    let ui_loaded = if !ui_font.is_empty() {
+        add_font(
+            "ui",
+            Path::new(ui_font),
+            &[egui::FontFamily::Proportional],
+            true,
+        )
+    } else if let Some(font) = bundled_fonts().first() {
+        add_font("ui", &font.path, &both, true)
+    } else {
+        false
+    };
+
+    // 等宽编辑器字体
+    if !mono_font.is_empty() {
+        add_font(
+            "mono",
+            Path::new(mono_font),
+            &[egui::FontFamily::Monospace],
+            true,
+        );
+    }
+
+    // 系统 CJK 字体作为回退，保证中文可以显示
+    let cjk_loaded = system_cjk_fonts()
+        .iter()
+        .any(|font| add_font("cjk", &font.path, &both, false));
+
+    if !ui_loaded && !cjk_loaded {
+        eprintln!("未找到可用的中文字体，使用 egui 默认字体");
+    }
+
+    ctx.set_fonts(fonts);
+}
@@ -0,0 +1,332 @@
+//! 十六进制查看模块
+//! 以十六进制和 ASCII 显示 BLOB 值，识别常见的格式并预览图片和文本
+
+use crate::i18n::{tr, tr_args};
+use eframe::egui;
+use std::io::Read;
+
+/// 每行显示的字节数
+pub const BYTES_PER_LINE: usize = 16;
+
+/// 解压 gzip 时最多读取的字节数
+const MAX_DECOMPRESSED: u64 = 64 * 1024 * 1024;
+
+/// 识别出的二进制格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobFormat {
+    Png,
+    Jpeg,
+    Gzip,
+    Utf8,
+    Binary,
+}
+
+impl BlobFormat {
+    /// 按文件头识别格式，都不是时检查是否是 UTF-8 文本
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            BlobFormat::Png
+        } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            BlobFormat::Jpeg
+        } else if bytes.starts_with(&[0x1F, 0x8B]) {
+            BlobFormat::Gzip
+        } else if !bytes.is_empty() && std::str::from_utf8(bytes).is_ok() {
+            BlobFormat::Utf8
+        } else {
+            BlobFormat::Binary
+        }
+    }
+
+    /// 翻译键
+    pub fn label(&self) -> &'static str {
+        match self {
+            BlobFormat::Png => "hex.format_png",
+            BlobFormat::Jpeg => "hex.format_jpeg",
+            BlobFormat::Gzip => "hex.format_gzip",
+            BlobFormat::Utf8 => "hex.format_utf8",
+            BlobFormat::Binary => "hex.format_binary",
+        }
+    }
+
+    /// 保存文件时使用的扩展名
+    pub fn extension(&self) -> &'static str {
+        match self {
+            BlobFormat::Png => "png",
+            BlobFormat::Jpeg => "jpg",
+            BlobFormat::Gzip => "gz",
+            BlobFormat::Utf8 => "txt",
+            BlobFormat::Binary => "bin",
+        }
+    }
+
+    /// 是否可以预览为图片
+    fn is_image(&self) -> bool {
+        matches!(self, BlobFormat::Png | BlobFormat::Jpeg)
+    }
+}
+
+/// 一行的十六进制和 ASCII 文本，如 `00000010  48 65 6C 6C ...  |Hell...|`
+pub fn hex_line(bytes: &[u8], offset: usize) -> String {
+    let end = (offset + BYTES_PER_LINE).min(bytes.len());
+    let chunk = bytes.get(offset..end).unwrap_or_default();
+    let mut line = format!("{:08X} ", offset);
+    for i in 0..BYTES_PER_LINE {
+        // 每 8 个字节之间多留一个空格
+        if i % 8 == 0 {
+            line.push(' ');
+        }
+        match chunk.get(i) {
+            Some(byte) => line.push_str(&format!("{:02X} ", byte)),
+            None => line.push_str("   "),
+        }
+    }
+    line.push_str(" |");
+    line.extend(chunk.iter().map(|&b| {
+        if b.is_ascii_graphic() || b == b' ' {
+            b as char
+        } else {
+            '.'
+        }
+    }));
+    line.push('|');
+    line
+}
+
+/// 解析跳转的偏移量，`0x` 开头时为十六进制
+pub fn parse_offset(text: &str) -> Option<usize> {
+    let text = text.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// 解压 gzip 数据
+pub fn gunzip(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    flate2::read::GzDecoder::new(bytes)
+        .take(MAX_DECOMPRESSED)
+        .read_to_end(&mut data)
+        .map_err(|e| e.to_string())?;
+    Ok(data)
+}
+
+/// 查看方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HexMode {
+    #[default]
+    Hex,
+    Text,
+    Image,
+}
+
+/// 十六进制查看的状态
+#[derive(Default)]
+pub struct HexView {
+    pub mode: HexMode,
+    pub offset: String,                                // 跳转的偏移量输入
+    pub target: Option<usize>,                         // 高亮的偏移量
+    pub scroll: bool,                                  // 下一帧滚动到高亮的行
+    pub decompressed: Option<Result<Vec<u8>, String>>, // 显示 gzip 解压后的内容
+    pub preview: Option<Result<egui::TextureHandle, String>>,
+}
+
+impl HexView {
+    /// 图片默认显示预览，其他格式显示十六进制
+    pub fn new(bytes: &[u8]) -> Self {
+        let mode = if BlobFormat::detect(bytes).is_image() {
+            HexMode::Image
+        } else {
+            HexMode::Hex
+        };
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
+    /// 值被替换后清除缓存
+    pub fn reset(&mut self, bytes: &[u8]) {
+        *self = Self::new(bytes);
+    }
+
+    /// 图片的纹理，第一次显示时解码
+    fn texture(
+        &mut self,
+        ctx: &egui::Context,
+        bytes: &[u8],
+    ) -> &Result<egui::TextureHandle, String> {
+        self.preview.get_or_insert_with(|| {
+            let image = image::load_from_memory(bytes).map_err(|e| e.to_string())?;
+            let rgba = image.to_rgba8();
+            let size = [rgba.width() as usize, rgba.height() as usize];
+            let image = egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
+            Ok(ctx.load_texture("blob_preview", image, egui::TextureOptions::LINEAR))
+        })
+    }
+}
+
+/// 渲染二进制值
+pub fn show(ui: &mut egui::Ui, bytes: &[u8], view: &mut HexView) {
+    let format = BlobFormat::detect(bytes);
+    ui.horizontal(|ui| {
+        ui.label(tr_args(
+            "hex.summary",
+            &[("format", &tr(format.label())), ("size", &bytes.len())],
+        ));
+        ui.separator();
+        ui.selectable_value(&mut view.mode, HexMode::Hex, tr("hex.hex"));
+        if format == BlobFormat::Utf8 {
+            ui.selectable_value(&mut view.mode, HexMode::Text, tr("viewer.text"));
+        }
+        if format.is_image() {
+            ui.selectable_value(&mut view.mode, HexMode::Image, tr("hex.image"));
+        }
+        if format == BlobFormat::Gzip {
+            let mut decompress = view.decompressed.is_some();
+            if ui.checkbox(&mut decompress, tr("hex.decompress")).changed() {
+                view.decompressed = decompress.then(|| gunzip(bytes));
+            }
+        }
+    });
+
+    // 解压后的内容同样以十六进制显示
+    let decompressed = view.decompressed.take();
+    let bytes = match &decompressed {
+        Some(Ok(data)) => data.as_slice(),
+        Some(Err(e)) => {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                tr_args("hex.decompress_failed", &[("error", e)]),
+            );
+            bytes
+        }
+        None => bytes,
+    };
+
+    match view.mode {
+        HexMode::Image if format.is_image() => image_preview(ui, bytes, view),
+        HexMode::Text if format == BlobFormat::Utf8 => {
+            let text = String::from_utf8_lossy(bytes);
+            egui::ScrollArea::both()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut text.as_ref())
+                            .code_editor()
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+        }
+        _ => hex_dump(ui, bytes, view),
+    }
+    view.decompressed = decompressed;
+}
+
+/// 图片预览，按窗口大小缩小
+fn image_preview(ui: &mut egui::Ui, bytes: &[u8], view: &mut HexView) {
+    match view.texture(ui.ctx(), bytes) {
+        Ok(texture) => {
+            let size = texture.size_vec2();
+            ui.label(tr_args(
+                "hex.image_size",
+                &[("width", &size.x), ("height", &size.y)],
+            ));
+            egui::ScrollArea::both()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    ui.add(egui::Image::new(texture).max_size(ui.available_size()));
+                });
+        }
+        Err(e) => {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                tr_args("hex.image_failed", &[("error", e)]),
+            );
+        }
+    }
+}
+
+/// 十六进制和 ASCII 对照显示，可以跳转到指定的偏移量
+fn hex_dump(ui: &mut egui::Ui, bytes: &[u8], view: &mut HexView) {
+    ui.horizontal(|ui| {
+        ui.label(tr("hex.offset"));
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut view.offset)
+                .hint_text("0x0")
+                .desired_width(100.0),
+        );
+        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if submitted || ui.button(tr("hex.go")).clicked() {
+            view.target = parse_offset(&view.offset).filter(|&offset| offset < bytes.len());
+            view.scroll = view.target.is_some();
+        }
+        if let Some(target) = view.target {
+            ui.label(egui::RichText::new(format!("0x{:X} = {}", target, target)).weak());
+        }
+    });
+    ui.separator();
+
+    let lines = bytes.len().div_ceil(BYTES_PER_LINE);
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    let mut scroll_area = egui::ScrollArea::both().auto_shrink([false, false]);
+    if view.scroll {
+        let line = view.target.unwrap_or(0) / BYTES_PER_LINE;
+        scroll_area = scroll_area
+            .vertical_scroll_offset(line as f32 * (row_height + ui.spacing().item_spacing.y));
+        view.scroll = false;
+    }
+    let target_line = view.target.map(|offset| offset / BYTES_PER_LINE);
+    let highlight = ui.visuals().selection.bg_fill;
+    scroll_area.show_rows(ui, row_height, lines, |ui, range| {
+        for line in range {
+            let mut text = egui::RichText::new(hex_line(bytes, line * BYTES_PER_LINE)).monospace();
+            if target_line == Some(line) {
+                text = text.background_color(highlight);
+            }
+            ui.add(egui::Label::new(text).extend());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_lines_and_detects_formats() {
+        let bytes = b"Hello, world!\n\x00\xFFxyz";
+        assert_eq!(
+            hex_line(bytes, 0),
+            "00000000  48 65 6C 6C 6F 2C 20 77  6F 72 6C 64 21 0A 00 FF  |Hello, world!...|"
+        );
+        assert_eq!(
+            hex_line(bytes, 16),
+            format!("00000010  78 79 7A {}|xyz|", " ".repeat(3 * 13 + 2))
+        );
+        assert_eq!(parse_offset("0x1F"), Some(31));
+        assert_eq!(parse_offset(" 20 "), Some(20));
+        assert_eq!(parse_offset("zz"), None);
+
+        assert_eq!(BlobFormat::detect(bytes), BlobFormat::Binary);
+        assert_eq!(BlobFormat::detect("文本".as_bytes()), BlobFormat::Utf8);
+        assert_eq!(
+            BlobFormat::detect(b"\x89PNG\r\n\x1a\n...."),
+            BlobFormat::Png
+        );
+        assert_eq!(
+            BlobFormat::detect(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            BlobFormat::Jpeg
+        );
+    }
+
+    #[test]
+    fn decompresses_gzip() {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        std::io::Write::write_all(&mut encoder, b"compressed text").unwrap();
+        let bytes = encoder.finish().unwrap();
+        assert_eq!(BlobFormat::detect(&bytes), BlobFormat::Gzip);
+        assert_eq!(gunzip(&bytes).unwrap(), b"compressed text");
+        assert!(gunzip(&bytes[..4]).is_err());
+    }
+}
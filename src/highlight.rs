@@ -0,0 +1,313 @@
+//! SQL 语法高亮模块
+//! 把 SQL 文本切分为词法单元，并生成带颜色的排版任务
+
+use crate::theme::SyntaxColors;
+use eframe::egui::{self, text::LayoutJob};
+use std::ops::Range;
+
+/// 高亮的 SQL 关键字（大写）
+const KEYWORDS: &[&str] = &[
+    "ADD",
+    "ALL",
+    "ALTER",
+    "AND",
+    "ANY",
+    "AS",
+    "ASC",
+    "ATTACH",
+    "BEGIN",
+    "BETWEEN",
+    "BY",
+    "CASE",
+    "CAST",
+    "CHECK",
+    "COLUMN",
+    "COMMIT",
+    "CONSTRAINT",
+    "COPY",
+    "CREATE",
+    "CROSS",
+    "CURRENT",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DESCRIBE",
+    "DETACH",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "END",
+    "EXCEPT",
+    "EXISTS",
+    "EXPLAIN",
+    "FALSE",
+    "FILTER",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GROUP",
+    "HAVING",
+    "IF",
+    "ILIKE",
+    "IN",
+    "INDEX",
+    "INNER",
+    "INSERT",
+    "INSTALL",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "KEY",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "LOAD",
+    "NATURAL",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "OVER",
+    "PARTITION",
+    "PIVOT",
+    "PRAGMA",
+    "PRIMARY",
+    "QUALIFY",
+    "RECURSIVE",
+    "REFERENCES",
+    "RENAME",
+    "REPLACE",
+    "RETURNING",
+    "RIGHT",
+    "ROLLBACK",
+    "SCHEMA",
+    "SELECT",
+    "SEQUENCE",
+    "SET",
+    "SHOW",
+    "SUMMARIZE",
+    "TABLE",
+    "THEN",
+    "TO",
+    "TRANSACTION",
+    "TRUE",
+    "UNION",
+    "UNIQUE",
+    "UNPIVOT",
+    "UPDATE",
+    "USING",
+    "VALUES",
+    "VIEW",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+    "WITH",
+];
+
+/// 词法单元类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Keyword,
+    Identifier,
+    String,
+    Number,
+    Comment,
+    Operator,
+    Whitespace,
+}
+
+/// 把 SQL 文本切分为词法单元，返回类型和字节范围
+///
+/// 未闭合的字符串和块注释会一直延续到文本末尾。
+pub fn tokenize(text: &str) -> Vec<(TokenKind, Range<usize>)> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let mut end_at = |pred: &dyn Fn(char) -> bool| {
+            while let Some(&(_, next)) = chars.peek() {
+                if !pred(next) {
+                    break;
+                }
+                chars.next();
+            }
+            chars.peek().map_or(text.len(), |&(i, _)| i)
+        };
+
+        let (kind, end) = if c.is_whitespace() {
+            (TokenKind::Whitespace, end_at(&|c| c.is_whitespace()))
+        } else if c == '-' && text[start..].starts_with("--") {
+            (TokenKind::Comment, end_at(&|c| c != '\n'))
+        } else if c == '/' && text[start..].starts_with("/*") {
+            let end = text[start + 2..]
+                .find("*/")
+                .map_or(text.len(), |i| start + 2 + i + 2);
+            while chars.peek().is_some_and(|&(i, _)| i < end) {
+                chars.next();
+            }
+            (TokenKind::Comment, end)
+        } else if c == '\'' || c == '"' {
+            // 引号内的两个连续引号是转义
+            let mut end = text.len();
+            while let Some((i, next)) = chars.next() {
+                if next == c {
+                    if chars.peek().is_some_and(|&(_, after)| after == c) {
+                        chars.next();
+                    } else {
+                        end = i + 1;
+                        break;
+                    }
+                }
+            }
+            let kind = if c == '\'' {
+                TokenKind::String
+            } else {
+                TokenKind::Identifier
+            };
+            (kind, end)
+        } else if c.is_ascii_digit() {
+            (
+                TokenKind::Number,
+                end_at(&|c| c.is_ascii_alphanumeric() || c == '.' || c == '_'),
+            )
+        } else if c.is_alphabetic() || c == '_' {
+            let end = end_at(&|c| c.is_alphanumeric() || c == '_' || c == '$');
+            let word = text[start..end].to_uppercase();
+            let kind = if KEYWORDS.binary_search(&word.as_str()).is_ok() {
+                TokenKind::Keyword
+            } else {
+                TokenKind::Identifier
+            };
+            (kind, end)
+        } else {
+            (TokenKind::Operator, start + c.len_utf8())
+        };
+
+        tokens.push((kind, start..end));
+    }
+
+    tokens
+}
+
+/// 按分号拆分多条语句
+///
+/// 字符串和注释中的分号不算，只有空白和注释的部分会被丢弃。
+pub fn split_statements(text: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut has_code = false;
+
+    for (kind, range) in tokenize(text) {
+        match kind {
+            TokenKind::Operator if &text[range.clone()] == ";" => {
+                if has_code {
+                    statements.push(text[start..range.start].trim());
+                }
+                start = range.end;
+                has_code = false;
+            }
+            TokenKind::Whitespace | TokenKind::Comment => {}
+            _ => has_code = true,
+        }
+    }
+    if has_code {
+        statements.push(text[start..].trim());
+    }
+
+    statements
+}
+
+/// 生成 SQL 语法高亮的排版任务
+pub fn sql_layout_job(
+    text: &str,
+    font_id: egui::FontId,
+    colors: &SyntaxColors,
+    wrap_width: f32,
+) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    job.wrap.max_width = wrap_width;
+
+    for (kind, range) in tokenize(text) {
+        let color = match kind {
+            TokenKind::Keyword => colors.keyword,
+            TokenKind::String => colors.string,
+            TokenKind::Number => colors.number,
+            TokenKind::Comment => colors.comment,
+            TokenKind::Operator => colors.operator,
+            TokenKind::Identifier | TokenKind::Whitespace => colors.identifier,
+        };
+        job.append(
+            &text[range],
+            0.0,
+            egui::TextFormat::simple(font_id.clone(), color),
+        );
+    }
+
+    job
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(text: &str) -> Vec<(TokenKind, &str)> {
+        tokenize(text)
+            .into_iter()
+            .filter(|(kind, _)| *kind != TokenKind::Whitespace)
+            .map(|(kind, range)| (kind, &text[range]))
+            .collect()
+    }
+
+    #[test]
+    fn keywords_are_sorted_for_binary_search() {
+        assert!(KEYWORDS.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn tokenizes_basic_query() {
+        assert_eq!(
+            kinds("select \"名称\", 'it''s' from t -- 注释\nwhere x >= 1.5 /* 块 */"),
+            [
+                (TokenKind::Keyword, "select"),
+                (TokenKind::Identifier, "\"名称\""),
+                (TokenKind::Operator, ","),
+                (TokenKind::String, "'it''s'"),
+                (TokenKind::Keyword, "from"),
+                (TokenKind::Identifier, "t"),
+                (TokenKind::Comment, "-- 注释"),
+                (TokenKind::Keyword, "where"),
+                (TokenKind::Identifier, "x"),
+                (TokenKind::Operator, ">"),
+                (TokenKind::Operator, "="),
+                (TokenKind::Number, "1.5"),
+                (TokenKind::Comment, "/* 块 */"),
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_statements_outside_strings_and_comments() {
+        assert_eq!(
+            split_statements("select ';'; -- a;b\n;\n insert into t values (1);\n-- 结尾"),
+            ["select ';'", "insert into t values (1)"]
+        );
+        assert!(split_statements(" ; -- 只有注释").is_empty());
+    }
+
+    #[test]
+    fn unterminated_tokens_cover_rest_of_text() {
+        let text = "select 'abc";
+        let tokens = tokenize(text);
+        assert_eq!(tokens.last(), Some(&(TokenKind::String, 7..text.len())));
+
+        let text = "x /* open";
+        assert_eq!(
+            tokenize(text).last(),
+            Some(&(TokenKind::Comment, 2..text.len()))
+        );
+    }
+}
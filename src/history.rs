@@ -0,0 +1,532 @@
+//! 查询历史模块
+//! 把执行过的语句记录到本地 DuckDB 历史数据库
+
+use crate::app::MyApp;
+use crate::config::AppConfig;
+use crate::i18n::{tr, tr_args};
+use crate::profile::QueryProfile;
+use crate::utils::StringUtils;
+use chrono::{Local, NaiveDateTime};
+use duckdb::{Connection, params};
+use eframe::egui;
+use std::path::Path;
+use std::time::Duration;
+
+/// 历史数据库文件名
+const HISTORY_FILE_NAME: &str = "history.duckdb";
+
+/// 历史面板最多显示的记录数
+const PANEL_LIMIT: usize = 500;
+
+/// 历史表结构
+const HISTORY_SCHEMA: &str = "
+    CREATE SEQUENCE IF NOT EXISTS query_history_id;
+    CREATE TABLE IF NOT EXISTS query_history (
+        id BIGINT PRIMARY KEY DEFAULT nextval('query_history_id'),
+        executed_at TIMESTAMP NOT NULL,
+        connection VARCHAR NOT NULL,
+        sql VARCHAR NOT NULL,
+        duration_ms DOUBLE NOT NULL,
+        row_count BIGINT,
+        success BOOLEAN NOT NULL,
+        error VARCHAR
+    );
+    -- 性能信息，旧版本的历史数据库没有这些列
+    ALTER TABLE query_history ADD COLUMN IF NOT EXISTS cpu_ms DOUBLE;
+    ALTER TABLE query_history ADD COLUMN IF NOT EXISTS peak_memory BIGINT;
+";
+
+/// 查询历史记录的列
+const HISTORY_COLUMNS: &str =
+    "id, executed_at, connection, sql, duration_ms, row_count, success, error, cpu_ms, peak_memory";
+
+/// 历史记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub executed_at: NaiveDateTime,
+    pub connection: String, // 数据库路径
+    pub sql: String,
+    pub duration_ms: f64,
+    pub row_count: Option<i64>, // 返回或影响的行数
+    pub success: bool,
+    pub error: Option<String>,
+    pub cpu_ms: Option<f64>,      // DuckDB 统计的 CPU 时间
+    pub peak_memory: Option<i64>, // 缓冲区内存峰值（字节）
+}
+
+/// 查询历史
+///
+/// 历史数据库无法打开时（例如被另一个实例占用）不记录历史，
+/// 不影响查询本身。
+pub struct QueryHistory {
+    conn: Option<Connection>,
+}
+
+impl QueryHistory {
+    /// 打开配置目录中的历史数据库
+    pub fn open_default() -> Result<Self, duckdb::Error> {
+        let dir = AppConfig::config_dir();
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            eprintln!("创建配置目录失败: {}", e);
+        }
+        Self::open(&dir.join(HISTORY_FILE_NAME))
+    }
+
+    /// 打开指定路径的历史数据库
+    pub fn open(path: &Path) -> Result<Self, duckdb::Error> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// 使用内存数据库（不持久化）
+    pub fn open_in_memory() -> Result<Self, duckdb::Error> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    /// 不记录历史
+    pub fn disabled() -> Self {
+        Self { conn: None }
+    }
+
+    fn init(conn: Connection) -> Result<Self, duckdb::Error> {
+        conn.execute_batch(HISTORY_SCHEMA)?;
+        Ok(Self { conn: Some(conn) })
+    }
+
+    /// 是否可以记录历史
+    pub fn is_enabled(&self) -> bool {
+        self.conn.is_some()
+    }
+
+    /// 记录一条执行过的语句，`outcome` 为返回的行数或错误信息
+    pub fn record(
+        &self,
+        connection: &str,
+        sql: &str,
+        duration: Duration,
+        outcome: Result<Option<usize>, String>,
+    ) {
+        self.record_profiled(connection, sql, duration, outcome, None);
+    }
+
+    /// 记录一条执行过的语句和它的性能信息
+    pub fn record_profiled(
+        &self,
+        connection: &str,
+        sql: &str,
+        duration: Duration,
+        outcome: Result<Option<usize>, String>,
+        profile: Option<&QueryProfile>,
+    ) {
+        let Some(conn) = &self.conn else {
+            return;
+        };
+
+        let (row_count, error) = match outcome {
+            Ok(rows) => (rows.map(|n| n as i64), None),
+            Err(e) => (None, Some(e)),
+        };
+        let cpu_ms = profile.and_then(|p| p.cpu_time).map(|s| s * 1000.0);
+        let peak_memory = profile.and_then(|p| p.peak_memory).map(|n| n as i64);
+        let result = conn.execute(
+            "INSERT INTO query_history
+                (executed_at, connection, sql, duration_ms, row_count, success, error,
+                 cpu_ms, peak_memory)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                Local::now().naive_local(),
+                connection,
+                sql,
+                duration.as_secs_f64() * 1000.0,
+                row_count,
+                error.is_none(),
+                error,
+                cpu_ms,
+                peak_memory,
+            ],
+        );
+        if let Err(e) = result {
+            eprintln!("记录查询历史失败: {}", e);
+        }
+    }
+
+    /// 搜索历史记录，按时间倒序
+    ///
+    /// `filter` 按空白拆分为多个关键字，每个关键字都需要出现在
+    /// SQL、连接或错误信息中（不区分大小写）。
+    pub fn search(&self, filter: &str, limit: usize) -> Result<Vec<HistoryEntry>, duckdb::Error> {
+        let Some(conn) = &self.conn else {
+            return Ok(Vec::new());
+        };
+
+        let terms: Vec<String> = filter
+            .split_whitespace()
+            .map(|term| format!("%{}%", escape_like(term)))
+            .collect();
+        let mut sql = format!("SELECT {} FROM query_history WHERE true", HISTORY_COLUMNS);
+        for _ in &terms {
+            sql.push_str(
+                " AND (sql ILIKE ? ESCAPE '\\' OR connection ILIKE ? ESCAPE '\\'
+                       OR coalesce(error, '') ILIKE ? ESCAPE '\\')",
+            );
+        }
+        sql.push_str(&format!(" ORDER BY id DESC LIMIT {}", limit));
+
+        let params: Vec<&String> = terms.iter().flat_map(|t| [t, t, t]).collect();
+        let mut stmt = conn.prepare(&sql)?;
+        let entries = stmt
+            .query_map(duckdb::params_from_iter(params), entry_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    /// 同一条 SQL 的执行记录，按时间倒序，用于比较多次执行的性能
+    pub fn runs(&self, sql: &str, limit: usize) -> Result<Vec<HistoryEntry>, duckdb::Error> {
+        let Some(conn) = &self.conn else {
+            return Ok(Vec::new());
+        };
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM query_history WHERE sql = ? ORDER BY id DESC LIMIT {}",
+            HISTORY_COLUMNS, limit
+        ))?;
+        let entries = stmt
+            .query_map([sql], entry_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    /// 删除一条历史记录
+    pub fn delete(&self, id: i64) -> Result<(), duckdb::Error> {
+        if let Some(conn) = &self.conn {
+            conn.execute("DELETE FROM query_history WHERE id = ?", [id])?;
+        }
+        Ok(())
+    }
+
+    /// 清空历史记录
+    pub fn clear(&self) -> Result<(), duckdb::Error> {
+        if let Some(conn) = &self.conn {
+            conn.execute("DELETE FROM query_history", [])?;
+        }
+        Ok(())
+    }
+}
+
+/// 历史面板状态
+pub struct HistoryPanel {
+    pub filter: String,
+    pub entries: Vec<HistoryEntry>,
+    pub dirty: bool,             // 需要重新查询历史记录
+    pub compare: Option<String>, // 正在比较执行记录的 SQL
+    pub runs: Vec<HistoryEntry>, // 同一条 SQL 的执行记录
+}
+
+impl Default for HistoryPanel {
+    fn default() -> Self {
+        Self {
+            filter: String::new(),
+            entries: Vec::new(),
+            dirty: true,
+            compare: None,
+            runs: Vec::new(),
+        }
+    }
+}
+
+impl HistoryPanel {
+    /// 渲染历史面板
+    pub fn show(app: &mut MyApp, ui: &mut egui::Ui) {
+        let mut panel = std::mem::take(&mut app.history_panel);
+        if panel.compare.is_some() {
+            Self::show_runs(&mut panel, app, ui);
+            app.history_panel = panel;
+            return;
+        }
+
+        let mut open = None;
+        let mut delete = None;
+        let mut compare = None;
+
+        ui.heading(tr("history.title"));
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut panel.filter)
+                    .hint_text(tr("history.filter_hint"))
+                    .desired_width(ui.available_width() - 60.0),
+            );
+            if response.changed() {
+                panel.dirty = true;
+            }
+            if ui.button(tr("history.clear")).clicked() {
+                if let Err(e) = app.history.clear() {
+                    app.set_status_message(e.to_string());
+                }
+                panel.dirty = true;
+            }
+        });
+        if !app.history.is_enabled() {
+            ui.colored_label(ui.visuals().warn_fg_color, tr("history.disabled"));
+        }
+        ui.separator();
+
+        if panel.dirty {
+            panel.dirty = false;
+            panel.entries = app
+                .history
+                .search(&panel.filter, PANEL_LIMIT)
+                .unwrap_or_else(|e| {
+                    eprintln!("读取查询历史失败: {}", e);
+                    Vec::new()
+                });
+        }
+
+        if panel.entries.is_empty() {
+            ui.label(egui::RichText::new(tr("history.empty")).weak());
+        }
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for entry in &panel.entries {
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(
+                                entry.executed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                            )
+                            .weak(),
+                        );
+                        let connection = crate::utils::FileUtils::get_filename(&entry.connection)
+                            .unwrap_or_else(|| entry.connection.clone());
+                        ui.label(egui::RichText::new(connection).weak())
+                            .on_hover_text(&entry.connection);
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("×").clicked() {
+                                delete = Some(entry.id);
+                            }
+                            if ui.small_button(tr("history.open")).clicked() {
+                                open = Some(entry.clone());
+                            }
+                            if ui
+                                .small_button("📈")
+                                .on_hover_text(tr("history.compare"))
+                                .clicked()
+                            {
+                                compare = Some(entry.sql.clone());
+                            }
+                        });
+                    });
+
+                    let first_line = entry.sql.lines().next().unwrap_or_default();
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(first_line).monospace()).truncate(),
+                    )
+                    .on_hover_text(&entry.sql);
+
+                    let duration = tr_args(
+                        "history.duration",
+                        &[("ms", &format!("{:.1}", entry.duration_ms))],
+                    );
+                    match &entry.error {
+                        Some(error) => {
+                            ui.colored_label(
+                                ui.visuals().error_fg_color,
+                                format!("{} · {}", duration, error),
+                            );
+                        }
+                        None => {
+                            let rows = entry.row_count.unwrap_or_default();
+                            let mut text = format!(
+                                "{} · {}",
+                                duration,
+                                tr_args("history.rows", &[("count", &rows)])
+                            );
+                            if let Some(memory) = entry.peak_memory {
+                                text.push_str(" · ");
+                                text.push_str(&StringUtils::format_bytes(memory as u64));
+                            }
+                            ui.label(egui::RichText::new(text).weak());
+                        }
+                    }
+                });
+            }
+        });
+
+        if let Some(id) = delete {
+            if let Err(e) = app.history.delete(id) {
+                app.set_status_message(e.to_string());
+            }
+            panel.dirty = true;
+        }
+        if let Some(sql) = compare {
+            panel.runs = app.history.runs(&sql, PANEL_LIMIT).unwrap_or_default();
+            panel.compare = Some(sql);
+        }
+        app.history_panel = panel;
+
+        if let Some(entry) = open {
+            app.open_history_entry(&entry);
+        }
+    }
+
+    /// 渲染同一条 SQL 的执行记录比较
+    fn show_runs(panel: &mut HistoryPanel, app: &mut MyApp, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("⬅").on_hover_text(tr("history.back")).clicked() {
+                panel.compare = None;
+                panel.runs.clear();
+            }
+            ui.heading(tr("history.runs_title"));
+        });
+        if let Some(sql) = &panel.compare {
+            ui.add(egui::Label::new(egui::RichText::new(sql).monospace()).truncate())
+                .on_hover_text(sql);
+        }
+        ui.separator();
+
+        let slowest = panel
+            .runs
+            .iter()
+            .map(|run| run.duration_ms)
+            .fold(0.0, f64::max);
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("history_runs")
+                .striped(true)
+                .num_columns(5)
+                .show(ui, |ui| {
+                    for key in [
+                        "history.executed_at",
+                        "history.duration_header",
+                        "history.rows_header",
+                        "history.memory_header",
+                        "",
+                    ] {
+                        ui.strong(if key.is_empty() { "" } else { tr(key) });
+                    }
+                    ui.end_row();
+
+                    for run in &panel.runs {
+                        ui.label(run.executed_at.format("%m-%d %H:%M:%S").to_string());
+                        ui.label(format!("{:.1} ms", run.duration_ms));
+                        ui.label(
+                            run.row_count
+                                .map_or_else(|| "-".to_string(), |n| n.to_string()),
+                        );
+                        ui.label(run.peak_memory.map_or_else(
+                            || "-".to_string(),
+                            |n| StringUtils::format_bytes(n as u64),
+                        ));
+
+                        // 耗时条，以最慢的一次为满
+                        let share = if slowest > 0.0 {
+                            (run.duration_ms / slowest) as f32
+                        } else {
+                            0.0
+                        };
+                        let color = if run.success {
+                            ui.visuals().selection.bg_fill
+                        } else {
+                            ui.visuals().error_fg_color
+                        };
+                        let (rect, _) =
+                            ui.allocate_exact_size(egui::vec2(80.0, 10.0), egui::Sense::hover());
+                        let mut bar = rect;
+                        bar.set_width(rect.width() * share);
+                        ui.painter().rect_filled(bar, 2.0, color);
+                        ui.end_row();
+                    }
+                });
+        });
+
+        if !app.history.is_enabled() {
+            ui.colored_label(ui.visuals().warn_fg_color, tr("history.disabled"));
+        }
+    }
+}
+
+/// 读取一行历史记录，列顺序与 `HISTORY_COLUMNS` 一致
+fn entry_from_row(row: &duckdb::Row) -> duckdb::Result<HistoryEntry> {
+    Ok(HistoryEntry {
+        id: row.get(0)?,
+        executed_at: row.get(1)?,
+        connection: row.get(2)?,
+        sql: row.get(3)?,
+        duration_ms: row.get(4)?,
+        row_count: row.get(5)?,
+        success: row.get(6)?,
+        error: row.get(7)?,
+        cpu_ms: row.get(8)?,
+        peak_memory: row.get(9)?,
+    })
+}
+
+/// 转义 LIKE 模式中的特殊字符
+fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_and_filters_entries() {
+        let history = QueryHistory::open_in_memory().unwrap();
+        let ms = Duration::from_millis(12);
+        history.record("a.duckdb", "SELECT * FROM users", ms, Ok(Some(3)));
+        history.record("b.duckdb", "select 100%", ms, Err("语法错误".to_string()));
+        history.record("a.duckdb", "DELETE FROM orders", ms, Ok(None));
+
+        let all = history.search("", 10).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].sql, "DELETE FROM orders");
+        assert_eq!(all[2].row_count, Some(3));
+        assert!((all[2].duration_ms - 12.0).abs() < 1e-6);
+
+        let failed = history.search("100%", 10).unwrap();
+        assert_eq!(failed.len(), 1);
+        assert!(!failed[0].success);
+        assert_eq!(failed[0].error.as_deref(), Some("语法错误"));
+
+        let terms = history.search("from A.DUCKDB", 10).unwrap();
+        assert_eq!(terms.len(), 2);
+
+        history.delete(terms[0].id).unwrap();
+        assert_eq!(history.search("", 10).unwrap().len(), 2);
+        history.clear().unwrap();
+        assert!(history.search("", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn stores_profile_metrics_per_run() {
+        let history = QueryHistory::open_in_memory().unwrap();
+        let profile = QueryProfile {
+            cpu_time: Some(0.004),
+            peak_memory: Some(2048),
+            ..QueryProfile::new(Duration::from_millis(5), 2)
+        };
+        let sql = "SELECT * FROM t";
+        history.record_profiled(
+            "a.duckdb",
+            sql,
+            profile.wall_time,
+            Ok(Some(2)),
+            Some(&profile),
+        );
+        history.record("a.duckdb", sql, Duration::from_millis(9), Ok(Some(2)));
+        history.record(
+            "a.duckdb",
+            "SELECT 1",
+            Duration::from_millis(1),
+            Ok(Some(1)),
+        );
+
+        let runs = history.runs(sql, 10).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].peak_memory, None);
+        assert_eq!(runs[1].peak_memory, Some(2048));
+        assert!((runs[1].cpu_ms.unwrap() - 4.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,149 @@
+//! 国际化模块
+//! 根据 AppConfig::language 提供界面文本
+
+mod en_us;
+mod zh_cn;
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    ZhCn,
+    EnUs,
+}
+
+impl Language {
+    /// 从语言代码解析（如 `zh-CN`、`en-US`）
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "zh-CN" => Some(Language::ZhCn),
+            "en-US" => Some(Language::EnUs),
+            _ => None,
+        }
+    }
+
+    /// 语言代码
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::ZhCn => "zh-CN",
+            Language::EnUs => "en-US",
+        }
+    }
+
+    /// 语言的文本目录
+    fn messages(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::ZhCn => zh_cn::MESSAGES,
+            Language::EnUs => en_us::MESSAGES,
+        }
+    }
+
+    /// 按键索引的文本目录
+    fn catalog(self) -> &'static HashMap<&'static str, &'static str> {
+        static ZH_CN: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        static EN_US: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+
+        let cell = match self {
+            Language::ZhCn => &ZH_CN,
+            Language::EnUs => &EN_US,
+        };
+        cell.get_or_init(|| self.messages().iter().copied().collect())
+    }
+}
+
+/// 当前界面语言
+static CURRENT_LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+/// 切换界面语言，未知的语言代码会被忽略
+pub fn set_language(code: &str) {
+    if let Some(language) = Language::from_code(code) {
+        CURRENT_LANGUAGE.store(language as u8, Ordering::Relaxed);
+    }
+}
+
+/// 获取当前界面语言
+pub fn current_language() -> Language {
+    match CURRENT_LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::EnUs,
+        _ => Language::ZhCn,
+    }
+}
+
+/// 获取键对应的文本
+///
+/// 当前语言缺少该键时回退到中文，仍然找不到时返回键本身。
+pub fn tr(key: &str) -> &str {
+    let language = current_language();
+    language
+        .catalog()
+        .get(key)
+        .or_else(|| Language::ZhCn.catalog().get(key))
+        .copied()
+        .unwrap_or(key)
+}
+
+/// 获取文本并替换其中的 `{name}` 占位符
+pub fn tr_args(key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let mut text = tr(key).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// 提取文本中的占位符名称
+    fn placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_bundles_have_same_keys() {
+        let zh: BTreeSet<&str> = zh_cn::MESSAGES.iter().map(|(k, _)| *k).collect();
+        let en: BTreeSet<&str> = en_us::MESSAGES.iter().map(|(k, _)| *k).collect();
+
+        let missing_in_en: Vec<_> = zh.difference(&en).collect();
+        let missing_in_zh: Vec<_> = en.difference(&zh).collect();
+        assert!(missing_in_en.is_empty(), "en-US 缺少: {:?}", missing_in_en);
+        assert!(missing_in_zh.is_empty(), "zh-CN 缺少: {:?}", missing_in_zh);
+
+        // 重复的键会被后一个覆盖，通常是复制粘贴错误
+        assert_eq!(zh.len(), zh_cn::MESSAGES.len(), "zh-CN 有重复的键");
+        assert_eq!(en.len(), en_us::MESSAGES.len(), "en-US 有重复的键");
+    }
+
+    #[test]
+    fn test_bundles_have_same_placeholders() {
+        let en = Language::EnUs.catalog();
+        for (key, zh_text) in zh_cn::MESSAGES {
+            if let Some(en_text) = en.get(key) {
+                assert_eq!(
+                    placeholders(zh_text),
+                    placeholders(en_text),
+                    "占位符不一致: {}",
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tr_args_and_fallback() {
+        assert_eq!(tr("no.such.key"), "no.such.key");
+        assert_eq!(Language::from_code("en-US"), Some(Language::EnUs));
+        assert_eq!(Language::from_code("fr-FR"), None);
+
+        let text = tr_args("status.saved_files", &[("count", &3)]);
+        assert!(text.contains('3'));
+    }
+}
@@ -0,0 +1,587 @@
+//! English (US) messages
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    // 菜单栏
+    ("menubar.file", "File"),
+    ("menubar.edit", "Edit"),
+    ("menubar.navigate", "Navigate"),
+    ("menubar.search", "Search"),
+    ("menubar.sql_editor", "SQL Editor"),
+    ("menubar.database", "Database"),
+    ("menubar.window", "Window"),
+    ("menubar.help", "Help"),
+    // 菜单项
+    ("menu.open_folder", "Open Folder"),
+    ("menu.recent_edit", "Recent Edits"),
+    ("menu.find_file", "Find File..."),
+    ("menu.new_file", "New"),
+    ("menu.save_file", "Save"),
+    ("menu.save_as", "Save As"),
+    ("menu.save_all", "Save All"),
+    ("menu.close_file", "Close"),
+    ("menu.print_file", "Print"),
+    ("menu.rename_file", "Rename"),
+    ("menu.refresh_file", "Refresh"),
+    ("menu.import_file", "Import"),
+    ("menu.export_file", "Export"),
+    ("menu.file_property", "Properties"),
+    ("menu.exit", "Exit"),
+    ("menu.undo", "Undo"),
+    ("menu.redo", "Redo"),
+    ("menu.cut", "Cut"),
+    ("menu.copy", "Copy"),
+    ("menu.copy_advanced", "Advanced Copy..."),
+    ("menu.paste", "Paste"),
+    ("menu.delete", "Delete"),
+    ("menu.generate_uuid", "Generate UUID"),
+    ("menu.goto_line", "Go to Line"),
+    ("menu.find", "Find"),
+    ("menu.new_query", "New Query"),
+    ("menu.run_query", "Run Query"),
+    ("menu.explain", "Explain"),
+    ("menu.explain_analyze", "Explain Analyze"),
+    ("menu.save_to_library", "Save to Library"),
+    ("menu.reload_library", "Reload Library"),
+    ("menu.connect_db", "Connect Database"),
+    ("menu.compare_results", "Compare results..."),
+    ("menu.compare_schemas", "Compare schemas..."),
+    ("menu.toggle_auto_commit", "Toggle Auto-Commit"),
+    ("menu.commit", "Commit"),
+    ("menu.rollback", "Rollback"),
+    ("menu.new_window", "New Window"),
+    ("menu.toggle_sidebar", "Toggle Sidebar"),
+    ("menu.toggle_status_bar", "Toggle Status Bar"),
+    ("menu.toggle_history", "Toggle Query History"),
+    ("menu.open_settings", "Settings"),
+    ("menu.about", "About"),
+    // 状态栏
+    ("status.ready", "Ready"),
+    ("status.app_name", "egui app"),
+    ("status.config_reloaded", "Configuration reloaded"),
+    ("status.workspace_restored", "Restored previous workspace"),
+    (
+        "status.connections_restore_failed",
+        "Failed to restore connections: {paths}",
+    ),
+    ("status.opening_folder", "Opening folder..."),
+    ("status.folder_cancelled", "Folder selection cancelled"),
+    ("status.folder_opened", "Opened folder: {name}"),
+    ("status.file_created", "Created new file"),
+    ("status.file_cancelled", "File selection cancelled"),
+    ("status.file_opened", "Opened file: {path}"),
+    ("status.file_open_failed", "Failed to open file: {error}"),
+    ("status.no_open_file", "No open file"),
+    ("status.file_saved", "Saved: {path}"),
+    ("status.file_save_failed", "Failed to save file: {error}"),
+    ("status.save_cancelled", "Save cancelled"),
+    ("status.saved_files", "Saved {count} file(s)"),
+    ("status.closing_file", "Closing current file..."),
+    ("status.tab_closed", "Closed: {title}"),
+    ("status.tab_created", "Created: {title}"),
+    ("status.object_missing", "Object no longer exists: {name}"),
+    ("status.exiting", "Exiting..."),
+    ("status.dark_mode_on", "Switched to dark mode"),
+    ("status.dark_mode_off", "Switched to light mode"),
+    ("status.dark_mode_on_follow_off", "Switched to dark mode and stopped following the system theme"),
+    ("status.dark_mode_off_follow_off", "Switched to light mode and stopped following the system theme"),
+    ("status.printing", "Preparing to print..."),
+    ("status.renaming", "Renaming file..."),
+    ("status.refreshing", "Refreshing file..."),
+    ("status.importing", "Importing file..."),
+    ("status.exporting", "Exporting file..."),
+    ("status.showing_property", "Showing file properties..."),
+    ("status.undoing", "Undoing last action..."),
+    ("status.redoing", "Redoing next action..."),
+    ("status.cutting", "Cutting selection..."),
+    ("status.copied", "Copied {count} rows"),
+    ("status.copied_value", "Copied to clipboard"),
+    ("status.blob_loaded", "Loaded {size} bytes, apply edits to write them to the database"),
+    ("status.nothing_selected", "No cells selected in the results"),
+    ("status.pasting", "Pasting..."),
+    ("status.deleting", "Deleting selection..."),
+    ("status.generating_uuid", "Generating UUID..."),
+    ("status.goto_line", "Opening Go to Line dialog..."),
+    ("status.finding", "Opening Find dialog..."),
+    ("status.db_cancelled", "Database selection cancelled"),
+    ("status.db_connected", "Connected to database: {path}"),
+    (
+        "status.db_connect_failed",
+        "Failed to connect to database: {path}",
+    ),
+    (
+        "status.connection_switched",
+        "Switched to connection: {name}",
+    ),
+    ("status.no_connection", "Connect to a database first"),
+    ("status.no_statement", "Nothing to execute"),
+    (
+        "status.query_done",
+        "Executed {count} statement(s) in {ms} ms",
+    ),
+    ("status.query_failed", "Execution failed: {error}"),
+    ("status.explain_done", "Execution plan loaded"),
+    ("status.auto_commit_on", "Auto-commit enabled for {name}"),
+    ("status.auto_commit_off", "Manual commit enabled for {name}"),
+    ("status.no_transaction", "No pending transaction on this connection"),
+    ("status.committed", "Committed transaction on {name}"),
+    ("status.rolled_back", "Rolled back transaction on {name}"),
+    ("status.edits_applied", "Changes applied, {count} rows affected"),
+    ("status.pending_transactions", "{count} connection(s) with uncommitted changes"),
+    ("status.library_saved", "Saved to {path}"),
+    ("status.explorer_switched", "Switched to explorer: {name}"),
+    ("status.new_window", "Creating new window..."),
+    ("status.showing_about", "Showing about information..."),
+    ("status.settings_applied", "Settings applied"),
+    ("status.folder_collapsed", "Collapsed {name}"),
+    ("status.folder_expanded", "Expanded {name}"),
+    ("status.folder_clicked", "Folder: {name}"),
+    ("status.file_clicked", "File: {name}"),
+    // 通知
+    (
+        "notify.config_parse_failed",
+        "Failed to parse configuration file",
+    ),
+    (
+        "notify.config_keep_current",
+        "{error}\nKeeping the current configuration.",
+    ),
+    ("notify.config_load_failed", "Could not load the config file"),
+    ("notify.config_use_default", "{error}\nUsing the default configuration."),
+    ("notify.config_backed_up", "The original file was backed up to {path}"),
+    ("notify.config_backup_failed", "Failed to back up the original file: {error}"),
+    (
+        "notify.config_invalid",
+        "Invalid configuration values were replaced with defaults",
+    ),
+    ("notify.history_unavailable", "Query history is unavailable"),
+    ("notify.library_load_failed", "Failed to read library file"),
+    ("notify.library_save_failed", "Failed to save to library"),
+    ("notify.transaction_end_failed", "Failed to end transaction"),
+    ("notify.edits_failed", "Failed to apply changes"),
+    ("notify.transaction_left_open", "The open transaction was not rolled back; commit or roll it back manually"),
+    ("notify.stats_failed", "Failed to compute column statistics"),
+    ("notify.generate_sql_failed", "Failed to generate SQL"),
+    ("notify.designer_failed", "Failed to read the table structure"),
+    ("notify.theme_load_failed", "Failed to load theme file"),
+    (
+        "notify.theme_not_found",
+        "Theme \"{name}\" not found, using the built-in theme",
+    ),
+    // 对话框
+    ("dialog.pick_folder", "Select Folder"),
+    ("dialog.open_file", "Open File"),
+    ("dialog.save_as", "Save As"),
+    ("dialog.pick_database", "Select Database File"),
+    ("dialog.export_chart", "Export chart"),
+    ("dialog.save_blob", "Save binary value"),
+    ("dialog.load_blob", "Load file into cell"),
+    ("dialog.export_diff", "Export comparison report"),
+    ("dialog.pending_transactions_title", "Uncommitted Transactions"),
+    ("dialog.pending_transactions", "These connections have uncommitted changes:"),
+    ("dialog.commit_and_exit", "Commit and Exit"),
+    ("dialog.rollback_and_exit", "Roll Back and Exit"),
+    ("dialog.cancel", "Cancel"),
+    // 资源管理器与侧边栏
+    ("explorer.default_folder", "Folder"),
+    ("explorer.tab_name", "File Explorer"),
+    ("explorer.label", "Explorer:"),
+    ("sidebar.explorer", "Explorer"),
+    ("sidebar.database", "Database"),
+    ("sidebar.history", "History"),
+    ("sidebar.settings", "Settings"),
+    ("sidebar.connections", "Connections"),
+    ("sidebar.objects", "Objects"),
+    ("sidebar.refresh_tables", "⟳ Refresh"),
+    ("sidebar.column_stats", "Column statistics"),
+    ("sidebar.kind_tables", "Tables"),
+    ("sidebar.kind_views", "Views"),
+    ("sidebar.kind_indexes", "Indexes"),
+    ("sidebar.kind_sequences", "Sequences"),
+    ("sidebar.kind_macros", "Macros"),
+    ("sidebar.generate_sql", "Generate SQL"),
+    ("sidebar.new_table", "＋ New table"),
+    ("sidebar.design_table", "Design table"),
+    // 编辑器
+    ("editor.query_title", "Query {index}"),
+    ("editor.untitled", "Untitled"),
+    ("editor.connection", "Connection: {path}"),
+    ("editor.run", "▶ Run"),
+    ("editor.explain", "Explain"),
+    ("editor.explain_analyze", "Analyze"),
+    ("editor.auto_commit", "Auto-commit"),
+    ("editor.auto_commit_pending", "Commit or roll back the current transaction first"),
+    ("editor.commit", "Commit"),
+    ("editor.rollback", "Rollback"),
+    // 查询结果
+    ("results.rows", "{count} rows"),
+    ("results.truncated", "Showing first {count} rows only"),
+    ("results.grid", "Results"),
+    ("results.plan", "Plan"),
+    ("results.chart", "Chart"),
+    ("results.record", "Record"),
+    ("results.copy_as", "Copy as"),
+    ("results.copy_advanced", "Advanced copy..."),
+    ("results.select_all", "Select all"),
+    ("results.filter_by_value", "Filter by this value"),
+    ("results.view_value", "View value..."),
+    ("results.column_stats", "📊 Column stats"),
+    ("results.column_stats_hint", "Compute per-column statistics of the full query result with SUMMARIZE"),
+    // 执行计划
+    ("explain.estimated", "Estimated plan"),
+    ("explain.analyzed", "Actual plan"),
+    ("explain.latency", "Total {time}"),
+    ("explain.empty", "The plan is empty"),
+    ("explain.invalid_output", "Unrecognized EXPLAIN output"),
+    ("explain.rows_estimated", "Est. {estimated} rows"),
+    ("explain.rows_actual", "Est. {estimated} / actual {actual} rows"),
+    ("explain.misestimated", "Estimated and actual row counts differ widely"),
+    // 查询历史
+    ("history.title", "Query History"),
+    ("history.filter_hint", "Search SQL, connection or error"),
+    ("history.clear", "Clear"),
+    ("history.open", "Open"),
+    ("history.compare", "Compare runs of this query"),
+    ("history.back", "Back"),
+    ("history.runs_title", "Run comparison"),
+    ("history.executed_at", "Executed at"),
+    ("history.duration_header", "Duration"),
+    ("history.rows_header", "Rows"),
+    ("history.memory_header", "Peak memory"),
+    (
+        "history.disabled",
+        "History database unavailable; queries are not recorded",
+    ),
+    ("history.empty", "No history yet"),
+    ("history.duration", "{ms} ms"),
+    ("history.rows", "{count} rows"),
+    // 查询库
+    ("library.title", "Library"),
+    ("library.reload", "Reload"),
+    ("library.save_current", "Save current query"),
+    ("library.empty", "Library is empty, put .sql files in {dir}"),
+    ("library.open", "Open in new tab"),
+    ("library.insert", "Insert into editor"),
+    ("library.delete", "Delete"),
+    ("library.kind_query", "Query"),
+    ("library.kind_snippet", "Snippet"),
+    ("library.save_title", "Save to Library"),
+    ("library.name", "Name"),
+    ("library.folder", "Folder"),
+    ("library.folder_hint", "Separate nested folders with /"),
+    ("library.description", "Description"),
+    ("library.kind", "Kind"),
+    ("library.save", "Save"),
+    ("library.cancel", "Cancel"),
+    ("library.prompt_title", "Fill in Parameters"),
+    ("library.prompt_confirm", "OK"),
+    // 查询参数
+    ("params.title", "Query Parameters"),
+    ("params.run", "Run"),
+    ("params.cancel", "Cancel"),
+    ("params.statement_param", "Statement {index} · ?{name}"),
+    ("params.invalid_value", "Not a valid {type}"),
+    ("params.type_auto", "Auto"),
+    ("params.type_text", "Text"),
+    ("params.type_integer", "Integer"),
+    ("params.type_double", "Decimal"),
+    ("params.type_boolean", "Boolean"),
+    ("params.type_date", "Date"),
+    ("params.type_timestamp", "Timestamp"),
+    ("params.type_null", "NULL"),
+    // 欢迎页
+    ("welcome.name", "Your name: "),
+    ("welcome.age", "age"),
+    ("welcome.click_each_year", "Increment"),
+    ("welcome.greeting", "Hello '{name}', age {age}"),
+    ("welcome.current_file", "Current file: {path}"),
+    ("welcome.toggle_dark_mode", "Toggle dark mode"),
+    // 设置
+    ("settings.title", "Settings"),
+    ("settings.page.appearance", "Appearance"),
+    ("settings.page.editor", "Editor"),
+    ("settings.page.grid", "Results Grid"),
+    ("settings.page.connections", "Connections"),
+    ("settings.page.files", "Files"),
+    ("settings.page.keymap", "Keymap"),
+    ("settings.apply", "Apply"),
+    ("settings.revert", "Revert"),
+    ("settings.reset_all", "Reset All to Defaults"),
+    ("settings.reset", "Reset"),
+    ("settings.dark_mode", "Dark mode"),
+    ("settings.dark_mode_follows_system", "Decided by the system while following the system theme"),
+    ("settings.follow_system_theme", "Follow system light/dark"),
+    ("settings.light_theme", "Light theme"),
+    ("settings.dark_theme", "Dark theme"),
+    ("settings.theme_builtin", "Built-in"),
+    ("settings.themes_dir", "Custom themes folder"),
+    ("settings.font_size", "Font size"),
+    ("settings.ui_font", "UI font"),
+    ("settings.mono_font", "Editor monospace font"),
+    ("settings.font_auto", "Automatic"),
+    ("settings.language", "Language"),
+    ("settings.word_wrap", "Word wrap"),
+    ("settings.autosave_interval", "Autosave interval (s)"),
+    ("settings.max_rows", "Max rows fetched"),
+    ("settings.null_display", "Display NULL as"),
+    ("settings.column_max_width", "Max column width"),
+    (
+        "settings.restore_connections",
+        "Restore connections on startup",
+    ),
+    ("settings.read_only", "Open read-only"),
+    ("settings.profiling", "Record profiling information"),
+    ("settings.show_hidden_files", "Show hidden files"),
+    ("settings.max_recent_files", "Recent files limit"),
+    ("settings.library_dir", "Library folder"),
+    // 配置校验
+    (
+        "config.out_of_range",
+        "{value} is out of range {min}..={max}",
+    ),
+    (
+        "config.window_size",
+        "window size must be positive: {width}x{height}",
+    ),
+    (
+        "config.unknown_language",
+        "unknown language \"{language}\", supported: {supported}",
+    ),
+    ("config.below_minimum", "{value} is below the minimum {min}"),
+    ("config.must_be_positive", "{value} must be positive"),
+    (
+        "config.invalid_shortcut",
+        "unrecognized shortcut \"{shortcut}\"",
+    ),
+    (
+        "config.not_object",
+        "the top level of the config file must be an object",
+    ),
+    (
+        "config.version_too_new",
+        "config file version {version} is newer than the supported version {supported}",
+    ),
+    ("config.invalid_color", "invalid color value: {value}"),
+    // 性能分析
+    ("profile.summary", "Profile: {ms} ms, {rows} rows"),
+    ("profile.wall_time", "Wall time"),
+    ("profile.latency", "DuckDB latency"),
+    ("profile.cpu_time", "CPU time"),
+    ("profile.rows", "Rows returned"),
+    ("profile.rows_scanned", "Rows scanned"),
+    ("profile.peak_memory", "Peak memory"),
+    ("profile.memory_allocated", "Memory allocated"),
+    ("profile.disabled", "Profiling is off; enable it in Settings"),
+    ("profile.show_plan", "Show operator timings"),
+    // 数据编辑
+    ("editing.table", "Editing table {table}"),
+    ("editing.add_row", "Add row"),
+    ("editing.delete_row", "Delete row"),
+    ("editing.undelete_row", "Undo delete"),
+    ("editing.edit_cell", "Edit"),
+    ("editing.set_null", "Set NULL"),
+    ("editing.revert", "Revert change"),
+    ("editing.changes", "{count} pending statements"),
+    ("editing.preview", "Preview SQL"),
+    ("editing.apply", "Apply"),
+    ("editing.discard", "Discard"),
+    // 复制
+    ("copy.dialog_title", "Advanced Copy"),
+    ("copy.format", "Format"),
+    ("copy.format_tsv", "TSV (spreadsheet)"),
+    ("copy.format_csv", "CSV"),
+    ("copy.format_markdown", "Markdown table"),
+    ("copy.format_json", "JSON array"),
+    ("copy.format_insert", "INSERT statements"),
+    ("copy.format_in_list", "IN list"),
+    ("copy.headers", "Include column names"),
+    ("copy.quote", "Quoting"),
+    ("copy.quote_minimal", "Only when needed"),
+    ("copy.quote_all", "Quote all values"),
+    ("copy.quote_never", "Never"),
+    ("copy.null_text", "NULL as"),
+    ("copy.null_empty", "(empty)"),
+    ("copy.table", "Table name"),
+    ("copy.preview", "Preview ({count} rows)"),
+    ("copy.copy", "Copy"),
+    ("copy.cancel", "Cancel"),
+    // 结果筛选
+    ("filter.search_hint", "Quick search..."),
+    ("filter.add", "Add filter"),
+    ("filter.clear", "Clear filters"),
+    ("filter.push_down", "Filter in database"),
+    ("filter.push_down_hint", "Re-run the query with the filters as a WHERE clause to filter all data, not just the fetched rows"),
+    ("filter.op_equals", "Equals"),
+    ("filter.op_contains", "Contains"),
+    ("filter.op_range", "Range"),
+    ("filter.op_is_null", "Is NULL"),
+    ("filter.op_regex", "Regex"),
+    ("filter.range_from", "From"),
+    ("filter.range_to", "To"),
+    ("filter.invalid", "Invalid filter: {error}"),
+    ("filter.summary", "Showing {shown}/{total} rows"),
+    ("filter.truncated_hint", " (results truncated, only fetched rows are filtered)"),
+    ("filter.matches", "{count} matching cells"),
+    // 列统计
+    ("stats.title", "Column statistics: {name}"),
+    ("stats.column", "Column"),
+    ("stats.type", "Type"),
+    ("stats.nulls", "Nulls"),
+    ("stats.distinct", "Distinct"),
+    ("stats.min", "Min"),
+    ("stats.max", "Max"),
+    ("stats.avg", "Avg"),
+    ("stats.std", "Std dev"),
+    ("stats.quantiles", "25% / 50% / 75%"),
+    ("stats.distribution", "Distribution"),
+    ("stats.count", "{count} rows"),
+    // 图表
+    ("chart.kind_line", "Line"),
+    ("chart.kind_bar", "Bar"),
+    ("chart.kind_scatter", "Scatter"),
+    ("chart.kind_histogram", "Histogram"),
+    ("chart.kind_pie", "Pie"),
+    ("chart.agg_none", "None"),
+    ("chart.agg_sum", "Sum"),
+    ("chart.agg_avg", "Average"),
+    ("chart.agg_count", "Count"),
+    ("chart.x", "X:"),
+    ("chart.category", "Category:"),
+    ("chart.y", "Y:"),
+    ("chart.aggregation", "Aggregate:"),
+    ("chart.bins", "Bins:"),
+    ("chart.export_png", "Export PNG"),
+    ("chart.export_svg", "Export SVG"),
+    ("chart.exported", "Chart exported to {path}"),
+    ("chart.export_failed", "Failed to export chart: {error}"),
+    ("chart.truncated", "Results are truncated; the chart only includes the first {count} rows"),
+    ("chart.need_y", "Choose a Y column or use the count aggregate"),
+    ("chart.no_numeric", "The selected column has no numeric values"),
+    ("chart.invalid_columns", "The selected columns are not in the result"),
+    // 单元格查看
+    ("viewer.title", "{column} · row {row}"),
+    ("viewer.tree", "Tree"),
+    ("viewer.text", "Text"),
+    ("viewer.path", "Path:"),
+    ("viewer.copy_path", "Copy path"),
+    ("viewer.generate_query", "Generate query"),
+    ("viewer.generate_query_hint", "Open a query that unnests or extracts the selected element in a new tab"),
+    // 二进制查看
+    ("hex.summary", "{format}, {size} bytes"),
+    ("hex.format_png", "PNG image"),
+    ("hex.format_jpeg", "JPEG image"),
+    ("hex.format_gzip", "gzip data"),
+    ("hex.format_utf8", "UTF-8 text"),
+    ("hex.format_binary", "Binary data"),
+    ("hex.hex", "Hex"),
+    ("hex.image", "Image"),
+    ("hex.image_size", "{width} × {height} px"),
+    ("hex.image_failed", "Cannot decode image: {error}"),
+    ("hex.decompress", "Decompress"),
+    ("hex.decompress_failed", "Decompression failed: {error}"),
+    ("hex.offset", "Offset:"),
+    ("hex.go", "Go"),
+    ("hex.save", "Save to file..."),
+    ("hex.load", "Load from file..."),
+    ("hex.load_disabled", "Files can only be loaded into editable results"),
+    // 记录视图
+    ("record.empty", "No rows to show"),
+    ("record.previous", "Previous row"),
+    ("record.next", "Next row"),
+    ("record.position", "Row {row} of {count}"),
+    ("record.pin", "Pin to compare"),
+    ("record.pin_hint", "Pin the current row and compare it side by side with other rows; selecting two rows in the grid also compares them"),
+    ("record.differences_only", "Differences only"),
+    ("record.search", "Search fields or values"),
+    ("record.field", "Field"),
+    ("record.type", "Type"),
+    ("record.row", "Row {row}"),
+    // 数据对比
+    ("diff.title", "Compare results"),
+    ("diff.left", "Left (original)"),
+    ("diff.right", "Right (new)"),
+    ("diff.no_connection", "No connection selected"),
+    ("diff.compare", "Compare"),
+    ("diff.keys", "Key columns:"),
+    (
+        "diff.duplicate_keys",
+        "Key columns have duplicate values; rows may match more than once",
+    ),
+    ("diff.added", "Added"),
+    ("diff.removed", "Removed"),
+    ("diff.changed", "Changed"),
+    ("diff.unchanged", "Unchanged"),
+    ("diff.status", "Difference"),
+    ("diff.left_only", "Columns only on the left"),
+    ("diff.right_only", "Columns only on the right"),
+    ("diff.no_differences", "No differences"),
+    ("diff.too_many_rows", "The result has more than {max} rows, narrow the query"),
+    ("diff.export_markdown", "Export Markdown"),
+    ("diff.export_csv", "Export CSV"),
+    // 结构对比
+    ("schema.title", "Compare schemas"),
+    ("schema.target", "Target"),
+    ("schema.target_hint", "The database or schema to change; the migration script runs on this connection"),
+    ("schema.reference", "Reference"),
+    ("schema.reference_hint", "The desired structure the target is changed to match"),
+    ("schema.no_schema", "No schema selected"),
+    ("schema.compare", "Compare"),
+    ("schema.open_script", "Open migration script in editor"),
+    ("schema.identical", "The schemas are identical"),
+    ("schema.drop_view", "Drop view {name}"),
+    ("schema.drop_macro", "Drop macro {name}"),
+    ("schema.drop_table", "Drop table {name}"),
+    ("schema.create_table", "Create table {name}"),
+    ("schema.add_column", "Add column {name} {type}"),
+    ("schema.drop_column", "Drop column {name}"),
+    ("schema.alter_column", "Alter column {name}: {details}"),
+    ("schema.constraints", "Constraints of table {name} differ: {details}"),
+    ("schema.create_view", "Create view {name}"),
+    ("schema.replace_view", "Replace view {name}"),
+    ("schema.create_macro", "Create macro {name}"),
+    ("schema.replace_macro", "Replace macro {name}"),
+    ("schema.rebuild_required", "DuckDB cannot change constraints of an existing table, {name} must be rebuilt"),
+    ("schema.script_header", "Migrates {name} to match the reference, review before running"),
+    // SQL 生成
+    ("ddl.create", "CREATE statement"),
+    ("ddl.select", "SELECT template"),
+    ("ddl.insert", "INSERT template"),
+    ("ddl.update", "UPDATE template"),
+    ("ddl.delete", "DELETE template"),
+    // 表设计
+    ("designer.new_title", "New table"),
+    ("designer.edit_title", "Design table {name}"),
+    ("designer.schema", "Schema"),
+    ("designer.name", "Table name"),
+    ("designer.columns", "Columns"),
+    ("designer.column_name", "Name"),
+    ("designer.column_type", "Type"),
+    ("designer.not_null", "Not null"),
+    ("designer.primary_key", "Primary key"),
+    ("designer.default", "Default"),
+    ("designer.new_column", "New column"),
+    ("designer.add_column", "＋ Add column"),
+    ("designer.remove", "Remove"),
+    ("designer.checks", "Check constraints"),
+    ("designer.add_check", "＋ Add check"),
+    ("designer.foreign_keys", "Foreign keys"),
+    ("designer.fk_columns", "Columns"),
+    ("designer.fk_table", "Referenced table"),
+    ("designer.fk_references", "Referenced columns"),
+    ("designer.add_foreign_key", "＋ Add foreign key"),
+    ("designer.uniques", "Unique constraints"),
+    ("designer.uniques_hint", "Unique constraints cannot be edited here and are kept when the table is rebuilt"),
+    ("designer.preview", "Statement preview"),
+    ("designer.no_changes", "No changes"),
+    ("designer.apply", "Apply"),
+    ("designer.apply_hint", "Runs all statements in one transaction and rolls back if any fails"),
+    ("designer.open_script", "Open in editor"),
+    ("designer.reset", "Reset"),
+    ("designer.applied", "Table {name} updated"),
+    ("designer.apply_failed", "Failed, all changes were rolled back: {error}"),
+    ("designer.error_no_name", "Enter a table name"),
+    ("designer.error_no_columns", "A table needs at least one column"),
+    ("designer.error_column_name", "Column {index} has no name"),
+    ("designer.error_type", "Column {name} has no type"),
+    ("designer.error_duplicate", "Duplicate column name {name}"),
+    ("designer.index_dropped", "Index {name} uses a dropped or renamed column and is not recreated"),
+];
@@ -0,0 +1,551 @@
+//! 简体中文文本
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    // 菜单栏
+    ("menubar.file", "文件(F)"),
+    ("menubar.edit", "编辑(E)"),
+    ("menubar.navigate", "导航(N)"),
+    ("menubar.search", "搜索(A)"),
+    ("menubar.sql_editor", "SQL编辑器"),
+    ("menubar.database", "数据库(D)"),
+    ("menubar.window", "窗口(W)"),
+    ("menubar.help", "帮助(H)"),
+    // 菜单项
+    ("menu.open_folder", "打开文件夹"),
+    ("menu.recent_edit", "最近的编辑"),
+    ("menu.find_file", "查找指定文件..."),
+    ("menu.new_file", "新建"),
+    ("menu.save_file", "保存"),
+    ("menu.save_as", "另存为"),
+    ("menu.save_all", "保存全部"),
+    ("menu.close_file", "关闭"),
+    ("menu.print_file", "打印"),
+    ("menu.rename_file", "重命名"),
+    ("menu.refresh_file", "刷新"),
+    ("menu.import_file", "导入"),
+    ("menu.export_file", "导出"),
+    ("menu.file_property", "属性"),
+    ("menu.exit", "退出"),
+    ("menu.undo", "撤销"),
+    ("menu.redo", "重做"),
+    ("menu.cut", "剪切"),
+    ("menu.copy", "复制"),
+    ("menu.copy_advanced", "高级复制..."),
+    ("menu.paste", "粘贴"),
+    ("menu.delete", "删除"),
+    ("menu.generate_uuid", "生成UUID"),
+    ("menu.goto_line", "跳转到行"),
+    ("menu.find", "查找"),
+    ("menu.new_query", "新建查询"),
+    ("menu.run_query", "执行查询"),
+    ("menu.explain", "查看执行计划"),
+    ("menu.explain_analyze", "分析执行计划"),
+    ("menu.save_to_library", "保存到查询库"),
+    ("menu.reload_library", "重新加载查询库"),
+    ("menu.connect_db", "连接数据库"),
+    ("menu.compare_results", "数据对比..."),
+    ("menu.compare_schemas", "结构对比..."),
+    ("menu.toggle_auto_commit", "切换自动提交"),
+    ("menu.commit", "提交事务"),
+    ("menu.rollback", "回滚事务"),
+    ("menu.new_window", "新建窗口"),
+    ("menu.toggle_sidebar", "显示/隐藏侧边栏"),
+    ("menu.toggle_status_bar", "显示/隐藏状态栏"),
+    ("menu.toggle_history", "显示/隐藏查询历史"),
+    ("menu.open_settings", "设置"),
+    ("menu.about", "关于"),
+    // 状态栏
+    ("status.ready", "就绪"),
+    ("status.app_name", "egui 应用"),
+    ("status.config_reloaded", "配置已重新加载"),
+    ("status.workspace_restored", "已恢复上次的工作区"),
+    (
+        "status.connections_restore_failed",
+        "以下数据库连接恢复失败: {paths}",
+    ),
+    ("status.opening_folder", "正在打开文件夹..."),
+    ("status.folder_cancelled", "用户取消了文件夹选择"),
+    ("status.folder_opened", "已打开文件夹: {name}"),
+    ("status.file_created", "已创建新文件"),
+    ("status.file_cancelled", "用户取消了文件选择"),
+    ("status.file_opened", "已打开文件: {path}"),
+    ("status.file_open_failed", "打开文件失败: {error}"),
+    ("status.no_open_file", "没有打开的文件"),
+    ("status.file_saved", "已保存: {path}"),
+    ("status.file_save_failed", "保存文件失败: {error}"),
+    ("status.save_cancelled", "用户取消了保存"),
+    ("status.saved_files", "已保存 {count} 个文件"),
+    ("status.closing_file", "正在关闭当前文件..."),
+    ("status.tab_closed", "已关闭: {title}"),
+    ("status.tab_created", "已创建: {title}"),
+    ("status.object_missing", "对象已不存在: {name}"),
+    ("status.exiting", "正在退出应用..."),
+    ("status.dark_mode_on", "已切换到深色模式"),
+    ("status.dark_mode_off", "已切换到浅色模式"),
+    ("status.dark_mode_on_follow_off", "已切换到深色模式，不再跟随系统主题"),
+    ("status.dark_mode_off_follow_off", "已切换到浅色模式，不再跟随系统主题"),
+    ("status.printing", "正在准备打印..."),
+    ("status.renaming", "正在重命名文件..."),
+    ("status.refreshing", "正在刷新文件内容..."),
+    ("status.importing", "正在导入文件..."),
+    ("status.exporting", "正在导出文件..."),
+    ("status.showing_property", "正在显示文件属性..."),
+    ("status.undoing", "正在撤销上一步操作..."),
+    ("status.redoing", "正在重做下一步操作..."),
+    ("status.cutting", "正在剪切选中内容..."),
+    ("status.copied", "已复制 {count} 行"),
+    ("status.copied_value", "已复制到剪贴板"),
+    ("status.blob_loaded", "已载入 {size} 字节，应用修改后写入数据库"),
+    ("status.nothing_selected", "结果表格中没有选中的单元格"),
+    ("status.pasting", "正在粘贴内容..."),
+    ("status.deleting", "正在删除选中内容..."),
+    ("status.generating_uuid", "正在生成UUID..."),
+    ("status.goto_line", "正在打开跳转到行对话框..."),
+    ("status.finding", "正在打开查找对话框..."),
+    ("status.db_cancelled", "用户取消了数据库选择"),
+    ("status.db_connected", "已连接数据库: {path}"),
+    ("status.db_connect_failed", "连接数据库失败: {path}"),
+    ("status.connection_switched", "切换到连接: {name}"),
+    ("status.no_connection", "请先连接数据库"),
+    ("status.no_statement", "没有可执行的语句"),
+    (
+        "status.query_done",
+        "执行完成：{count} 条语句，耗时 {ms} ms",
+    ),
+    ("status.query_failed", "执行失败: {error}"),
+    ("status.explain_done", "已获取执行计划"),
+    ("status.auto_commit_on", "{name} 已开启自动提交"),
+    ("status.auto_commit_off", "{name} 已切换为手动提交"),
+    ("status.no_transaction", "当前连接没有未提交的事务"),
+    ("status.committed", "{name} 的事务已提交"),
+    ("status.rolled_back", "{name} 的事务已回滚"),
+    ("status.edits_applied", "已应用修改，影响 {count} 行"),
+    ("status.pending_transactions", "{count} 个连接有未提交的事务"),
+    ("status.library_saved", "已保存到 {path}"),
+    ("status.explorer_switched", "切换到资源管理器: {name}"),
+    ("status.new_window", "正在创建新窗口..."),
+    ("status.showing_about", "正在显示关于信息..."),
+    ("status.settings_applied", "设置已应用"),
+    ("status.folder_collapsed", "折叠 {name}"),
+    ("status.folder_expanded", "展开 {name}"),
+    ("status.folder_clicked", "打开文件夹: {name}"),
+    ("status.file_clicked", "打开文件: {name}"),
+    // 通知
+    ("notify.config_parse_failed", "配置文件解析失败"),
+    ("notify.config_keep_current", "{error}\n继续使用当前配置。"),
+    ("notify.config_load_failed", "无法加载配置文件"),
+    ("notify.config_use_default", "{error}\n已使用默认配置。"),
+    ("notify.config_backed_up", "原文件已备份到 {path}"),
+    ("notify.config_backup_failed", "备份原文件失败: {error}"),
+    (
+        "notify.config_invalid",
+        "配置中有无效的值，已使用默认值代替",
+    ),
+    ("notify.history_unavailable", "查询历史不可用"),
+    ("notify.library_load_failed", "读取查询库文件失败"),
+    ("notify.library_save_failed", "保存到查询库失败"),
+    ("notify.transaction_end_failed", "结束事务失败"),
+    ("notify.edits_failed", "应用修改失败"),
+    ("notify.transaction_left_open", "当前事务没有回滚，请手动提交或回滚"),
+    ("notify.stats_failed", "计算列统计失败"),
+    ("notify.generate_sql_failed", "生成 SQL 失败"),
+    ("notify.designer_failed", "读取表结构失败"),
+    ("notify.theme_load_failed", "主题文件加载失败"),
+    (
+        "notify.theme_not_found",
+        "找不到主题“{name}”，已使用内置主题",
+    ),
+    // 对话框
+    ("dialog.pick_folder", "选择文件夹"),
+    ("dialog.open_file", "打开文件"),
+    ("dialog.save_as", "另存为"),
+    ("dialog.pick_database", "选择数据库文件"),
+    ("dialog.export_chart", "导出图表"),
+    ("dialog.save_blob", "保存二进制值"),
+    ("dialog.load_blob", "载入文件到单元格"),
+    ("dialog.export_diff", "导出对比报告"),
+    ("dialog.pending_transactions_title", "未提交的事务"),
+    ("dialog.pending_transactions", "以下连接有未提交的修改："),
+    ("dialog.commit_and_exit", "提交并退出"),
+    ("dialog.rollback_and_exit", "回滚并退出"),
+    ("dialog.cancel", "取消"),
+    // 资源管理器与侧边栏
+    ("explorer.default_folder", "文件夹"),
+    ("explorer.tab_name", "文件资源管理器"),
+    ("explorer.label", "资源管理器:"),
+    ("sidebar.explorer", "文件管理器"),
+    ("sidebar.database", "数据库"),
+    ("sidebar.history", "历史"),
+    ("sidebar.settings", "设置"),
+    ("sidebar.connections", "数据库连接"),
+    ("sidebar.objects", "数据库对象"),
+    ("sidebar.refresh_tables", "⟳ 刷新"),
+    ("sidebar.column_stats", "列统计"),
+    ("sidebar.kind_tables", "表"),
+    ("sidebar.kind_views", "视图"),
+    ("sidebar.kind_indexes", "索引"),
+    ("sidebar.kind_sequences", "序列"),
+    ("sidebar.kind_macros", "宏"),
+    ("sidebar.generate_sql", "生成 SQL"),
+    ("sidebar.new_table", "＋ 新建表"),
+    ("sidebar.design_table", "设计表"),
+    // 编辑器
+    ("editor.query_title", "查询 {index}"),
+    ("editor.untitled", "新文件"),
+    ("editor.connection", "连接: {path}"),
+    ("editor.run", "▶ 执行"),
+    ("editor.explain", "执行计划"),
+    ("editor.explain_analyze", "分析"),
+    ("editor.auto_commit", "自动提交"),
+    ("editor.auto_commit_pending", "请先提交或回滚当前事务"),
+    ("editor.commit", "提交"),
+    ("editor.rollback", "回滚"),
+    // 查询结果
+    ("results.rows", "{count} 行"),
+    ("results.truncated", "仅显示前 {count} 行"),
+    ("results.grid", "结果"),
+    ("results.plan", "执行计划"),
+    ("results.chart", "图表"),
+    ("results.record", "记录"),
+    ("results.copy_as", "复制为"),
+    ("results.copy_advanced", "高级复制..."),
+    ("results.select_all", "全选"),
+    ("results.filter_by_value", "按此值筛选"),
+    ("results.view_value", "查看值..."),
+    ("results.column_stats", "📊 列统计"),
+    ("results.column_stats_hint", "使用 SUMMARIZE 计算完整查询结果中各列的统计信息"),
+    // 执行计划
+    ("explain.estimated", "估计的执行计划"),
+    ("explain.analyzed", "实际执行计划"),
+    ("explain.latency", "总耗时 {time}"),
+    ("explain.empty", "执行计划为空"),
+    ("explain.invalid_output", "无法识别的 EXPLAIN 输出"),
+    ("explain.rows_estimated", "估计 {estimated} 行"),
+    ("explain.rows_actual", "估计 {estimated} / 实际 {actual} 行"),
+    ("explain.misestimated", "估计行数与实际行数相差较大"),
+    // 查询历史
+    ("history.title", "查询历史"),
+    ("history.filter_hint", "搜索 SQL、连接或错误"),
+    ("history.clear", "清空"),
+    ("history.open", "打开"),
+    ("history.compare", "比较此查询的历次执行"),
+    ("history.back", "返回"),
+    ("history.runs_title", "执行记录比较"),
+    ("history.executed_at", "执行时间"),
+    ("history.duration_header", "耗时"),
+    ("history.rows_header", "行数"),
+    ("history.memory_header", "内存峰值"),
+    ("history.disabled", "历史数据库不可用，不会记录查询"),
+    ("history.empty", "没有历史记录"),
+    ("history.duration", "{ms} ms"),
+    ("history.rows", "{count} 行"),
+    // 查询库
+    ("library.title", "查询库"),
+    ("library.reload", "重新加载"),
+    ("library.save_current", "保存当前查询"),
+    ("library.empty", "查询库为空，.sql 文件放在 {dir}"),
+    ("library.open", "在新标签页中打开"),
+    ("library.insert", "插入到编辑器"),
+    ("library.delete", "删除"),
+    ("library.kind_query", "查询"),
+    ("library.kind_snippet", "代码片段"),
+    ("library.save_title", "保存到查询库"),
+    ("library.name", "名称"),
+    ("library.folder", "文件夹"),
+    ("library.folder_hint", "使用 / 分隔多级文件夹"),
+    ("library.description", "描述"),
+    ("library.kind", "类型"),
+    ("library.save", "保存"),
+    ("library.cancel", "取消"),
+    ("library.prompt_title", "填写参数"),
+    ("library.prompt_confirm", "确定"),
+    // 查询参数
+    ("params.title", "输入参数"),
+    ("params.run", "执行"),
+    ("params.cancel", "取消"),
+    ("params.statement_param", "语句 {index} · ?{name}"),
+    ("params.invalid_value", "不是有效的{type}"),
+    ("params.type_auto", "自动"),
+    ("params.type_text", "文本"),
+    ("params.type_integer", "整数"),
+    ("params.type_double", "小数"),
+    ("params.type_boolean", "布尔"),
+    ("params.type_date", "日期"),
+    ("params.type_timestamp", "时间戳"),
+    ("params.type_null", "NULL"),
+    // 欢迎页
+    ("welcome.name", "您的姓名: "),
+    ("welcome.age", "年龄"),
+    ("welcome.click_each_year", "每年点击"),
+    ("welcome.greeting", "你好 '{name}', 年龄 {age}"),
+    ("welcome.current_file", "当前文件: {path}"),
+    ("welcome.toggle_dark_mode", "切换深色模式"),
+    // 设置
+    ("settings.title", "设置"),
+    ("settings.page.appearance", "外观"),
+    ("settings.page.editor", "编辑器"),
+    ("settings.page.grid", "结果表格"),
+    ("settings.page.connections", "连接"),
+    ("settings.page.files", "文件"),
+    ("settings.page.keymap", "快捷键"),
+    ("settings.apply", "应用"),
+    ("settings.revert", "还原"),
+    ("settings.reset_all", "全部恢复默认"),
+    ("settings.reset", "恢复默认"),
+    ("settings.dark_mode", "深色模式"),
+    ("settings.dark_mode_follows_system", "跟随系统主题时由系统决定"),
+    ("settings.follow_system_theme", "跟随系统浅色/深色"),
+    ("settings.light_theme", "浅色主题"),
+    ("settings.dark_theme", "深色主题"),
+    ("settings.theme_builtin", "内置"),
+    ("settings.themes_dir", "自定义主题目录"),
+    ("settings.font_size", "字体大小"),
+    ("settings.ui_font", "界面字体"),
+    ("settings.mono_font", "编辑器等宽字体"),
+    ("settings.font_auto", "自动"),
+    ("settings.language", "界面语言"),
+    ("settings.word_wrap", "自动换行"),
+    ("settings.autosave_interval", "自动保存间隔（秒）"),
+    ("settings.max_rows", "最大读取行数"),
+    ("settings.null_display", "NULL 显示为"),
+    ("settings.column_max_width", "列最大宽度"),
+    ("settings.restore_connections", "启动时恢复连接"),
+    ("settings.read_only", "只读模式打开"),
+    ("settings.profiling", "记录性能分析信息"),
+    ("settings.show_hidden_files", "显示隐藏文件"),
+    ("settings.max_recent_files", "最近文件数量"),
+    ("settings.library_dir", "查询库目录"),
+    // 配置校验
+    ("config.out_of_range", "{value} 超出范围 {min}..={max}"),
+    ("config.window_size", "窗口尺寸必须为正数: {width}x{height}"),
+    (
+        "config.unknown_language",
+        "未知语言 \"{language}\"，可选: {supported}",
+    ),
+    ("config.below_minimum", "{value} 小于最小值 {min}"),
+    ("config.must_be_positive", "{value} 必须为正数"),
+    ("config.invalid_shortcut", "无法识别的快捷键 \"{shortcut}\""),
+    ("config.not_object", "配置文件顶层必须是对象"),
+    ("config.version_too_new", "配置文件版本 {version} 高于当前支持的版本 {supported}"),
+    ("config.invalid_color", "无效的颜色值: {value}"),
+    // 性能分析
+    ("profile.summary", "性能: {ms} ms, {rows} 行"),
+    ("profile.wall_time", "总耗时"),
+    ("profile.latency", "DuckDB 耗时"),
+    ("profile.cpu_time", "CPU 时间"),
+    ("profile.rows", "返回行数"),
+    ("profile.rows_scanned", "扫描行数"),
+    ("profile.peak_memory", "内存峰值"),
+    ("profile.memory_allocated", "分配内存"),
+    ("profile.disabled", "性能分析未开启，可在设置中开启"),
+    ("profile.show_plan", "查看算子耗时"),
+    // 数据编辑
+    ("editing.table", "编辑表 {table}"),
+    ("editing.add_row", "新增行"),
+    ("editing.delete_row", "删除行"),
+    ("editing.undelete_row", "取消删除"),
+    ("editing.edit_cell", "编辑"),
+    ("editing.set_null", "设为 NULL"),
+    ("editing.revert", "撤销修改"),
+    ("editing.changes", "{count} 条待执行的修改"),
+    ("editing.preview", "预览 SQL"),
+    ("editing.apply", "应用"),
+    ("editing.discard", "放弃"),
+    // 复制
+    ("copy.dialog_title", "高级复制"),
+    ("copy.format", "格式"),
+    ("copy.format_tsv", "TSV（电子表格）"),
+    ("copy.format_csv", "CSV"),
+    ("copy.format_markdown", "Markdown 表格"),
+    ("copy.format_json", "JSON 数组"),
+    ("copy.format_insert", "INSERT 语句"),
+    ("copy.format_in_list", "IN 列表"),
+    ("copy.headers", "包含列名"),
+    ("copy.quote", "引号"),
+    ("copy.quote_minimal", "需要时加引号"),
+    ("copy.quote_all", "全部加引号"),
+    ("copy.quote_never", "不加引号"),
+    ("copy.null_text", "NULL 显示为"),
+    ("copy.null_empty", "（空）"),
+    ("copy.table", "表名"),
+    ("copy.preview", "预览（共 {count} 行）"),
+    ("copy.copy", "复制"),
+    ("copy.cancel", "取消"),
+    // 结果筛选
+    ("filter.search_hint", "快速搜索..."),
+    ("filter.add", "添加筛选"),
+    ("filter.clear", "清除筛选"),
+    ("filter.push_down", "在数据库中筛选"),
+    ("filter.push_down_hint", "把筛选条件作为 WHERE 子句重新执行查询，筛选全部数据而不只是已读取的行"),
+    ("filter.op_equals", "等于"),
+    ("filter.op_contains", "包含"),
+    ("filter.op_range", "范围"),
+    ("filter.op_is_null", "为 NULL"),
+    ("filter.op_regex", "正则表达式"),
+    ("filter.range_from", "从"),
+    ("filter.range_to", "到"),
+    ("filter.invalid", "筛选条件无效: {error}"),
+    ("filter.summary", "显示 {shown}/{total} 行"),
+    ("filter.truncated_hint", "（结果已截断，只筛选了已读取的行）"),
+    ("filter.matches", "{count} 个单元格匹配"),
+    // 列统计
+    ("stats.title", "列统计: {name}"),
+    ("stats.column", "列"),
+    ("stats.type", "类型"),
+    ("stats.nulls", "空值"),
+    ("stats.distinct", "不同值"),
+    ("stats.min", "最小值"),
+    ("stats.max", "最大值"),
+    ("stats.avg", "平均值"),
+    ("stats.std", "标准差"),
+    ("stats.quantiles", "25% / 50% / 75%"),
+    ("stats.distribution", "分布"),
+    ("stats.count", "共 {count} 行"),
+    // 图表
+    ("chart.kind_line", "折线图"),
+    ("chart.kind_bar", "柱状图"),
+    ("chart.kind_scatter", "散点图"),
+    ("chart.kind_histogram", "直方图"),
+    ("chart.kind_pie", "饼图"),
+    ("chart.agg_none", "不汇总"),
+    ("chart.agg_sum", "求和"),
+    ("chart.agg_avg", "平均值"),
+    ("chart.agg_count", "计数"),
+    ("chart.x", "X:"),
+    ("chart.category", "分类:"),
+    ("chart.y", "Y:"),
+    ("chart.aggregation", "汇总:"),
+    ("chart.bins", "分组数:"),
+    ("chart.export_png", "导出 PNG"),
+    ("chart.export_svg", "导出 SVG"),
+    ("chart.exported", "图表已导出到 {path}"),
+    ("chart.export_failed", "导出图表失败: {error}"),
+    ("chart.truncated", "结果已截断，图表只包含前 {count} 行"),
+    ("chart.need_y", "请选择 Y 列，或使用计数汇总"),
+    ("chart.no_numeric", "所选的列没有数值"),
+    ("chart.invalid_columns", "所选的列不在结果中"),
+    // 单元格查看
+    ("viewer.title", "{column} · 第 {row} 行"),
+    ("viewer.tree", "树"),
+    ("viewer.text", "文本"),
+    ("viewer.path", "路径:"),
+    ("viewer.copy_path", "复制路径"),
+    ("viewer.generate_query", "生成查询"),
+    ("viewer.generate_query_hint", "在新标签页中打开展开或提取选中元素的查询"),
+    // 二进制查看
+    ("hex.summary", "{format}，{size} 字节"),
+    ("hex.format_png", "PNG 图片"),
+    ("hex.format_jpeg", "JPEG 图片"),
+    ("hex.format_gzip", "gzip 压缩数据"),
+    ("hex.format_utf8", "UTF-8 文本"),
+    ("hex.format_binary", "二进制数据"),
+    ("hex.hex", "十六进制"),
+    ("hex.image", "图片"),
+    ("hex.image_size", "{width} × {height} 像素"),
+    ("hex.image_failed", "无法解码图片: {error}"),
+    ("hex.decompress", "解压"),
+    ("hex.decompress_failed", "解压失败: {error}"),
+    ("hex.offset", "偏移量:"),
+    ("hex.go", "跳转"),
+    ("hex.save", "保存到文件..."),
+    ("hex.load", "从文件载入..."),
+    ("hex.load_disabled", "只有可以编辑的结果才能载入文件"),
+    // 记录视图
+    ("record.empty", "没有可以显示的行"),
+    ("record.previous", "上一行"),
+    ("record.next", "下一行"),
+    ("record.position", "第 {row} / {count} 行"),
+    ("record.pin", "固定比较"),
+    ("record.pin_hint", "固定当前行，翻到其他行时并排比较；也可以在表格中选中两行比较"),
+    ("record.differences_only", "只显示不同的字段"),
+    ("record.search", "搜索字段或值"),
+    ("record.field", "字段"),
+    ("record.type", "类型"),
+    ("record.row", "第 {row} 行"),
+    // 数据对比
+    ("diff.title", "数据对比"),
+    ("diff.left", "左侧（原始）"),
+    ("diff.right", "右侧（新）"),
+    ("diff.no_connection", "未选择连接"),
+    ("diff.compare", "比较"),
+    ("diff.keys", "键列:"),
+    ("diff.duplicate_keys", "键列的值有重复，同一行可能匹配多次"),
+    ("diff.added", "新增"),
+    ("diff.removed", "删除"),
+    ("diff.changed", "修改"),
+    ("diff.unchanged", "相同"),
+    ("diff.status", "差异"),
+    ("diff.left_only", "只在左侧的列"),
+    ("diff.right_only", "只在右侧的列"),
+    ("diff.no_differences", "没有差异"),
+    ("diff.too_many_rows", "结果超过 {max} 行，请缩小查询范围"),
+    ("diff.export_markdown", "导出 Markdown"),
+    ("diff.export_csv", "导出 CSV"),
+    // 结构对比
+    ("schema.title", "结构对比"),
+    ("schema.target", "目标"),
+    ("schema.target_hint", "要修改的数据库或模式，迁移脚本在这个连接中执行"),
+    ("schema.reference", "参照"),
+    ("schema.reference_hint", "期望的结构，目标会被修改为与它一致"),
+    ("schema.no_schema", "未选择模式"),
+    ("schema.compare", "比较"),
+    ("schema.open_script", "在编辑器中打开迁移脚本"),
+    ("schema.identical", "结构相同"),
+    ("schema.drop_view", "删除视图 {name}"),
+    ("schema.drop_macro", "删除宏 {name}"),
+    ("schema.drop_table", "删除表 {name}"),
+    ("schema.create_table", "新建表 {name}"),
+    ("schema.add_column", "新增列 {name} {type}"),
+    ("schema.drop_column", "删除列 {name}"),
+    ("schema.alter_column", "修改列 {name}: {details}"),
+    ("schema.constraints", "表 {name} 的约束不同: {details}"),
+    ("schema.create_view", "新建视图 {name}"),
+    ("schema.replace_view", "修改视图 {name}"),
+    ("schema.create_macro", "新建宏 {name}"),
+    ("schema.replace_macro", "修改宏 {name}"),
+    ("schema.rebuild_required", "DuckDB 不能修改已有表的约束，需要重建 {name}"),
+    ("schema.script_header", "把 {name} 迁移为与参照一致，执行前请检查"),
+    // SQL 生成
+    ("ddl.create", "CREATE 语句"),
+    ("ddl.select", "SELECT 模板"),
+    ("ddl.insert", "INSERT 模板"),
+    ("ddl.update", "UPDATE 模板"),
+    ("ddl.delete", "DELETE 模板"),
+    // 表设计
+    ("designer.new_title", "新建表"),
+    ("designer.edit_title", "设计表 {name}"),
+    ("designer.schema", "模式"),
+    ("designer.name", "表名"),
+    ("designer.columns", "列"),
+    ("designer.column_name", "名称"),
+    ("designer.column_type", "类型"),
+    ("designer.not_null", "非空"),
+    ("designer.primary_key", "主键"),
+    ("designer.default", "默认值"),
+    ("designer.new_column", "新增的列"),
+    ("designer.add_column", "＋ 添加列"),
+    ("designer.remove", "删除"),
+    ("designer.checks", "检查约束"),
+    ("designer.add_check", "＋ 添加检查约束"),
+    ("designer.foreign_keys", "外键"),
+    ("designer.fk_columns", "列"),
+    ("designer.fk_table", "引用的表"),
+    ("designer.fk_references", "引用的列"),
+    ("designer.add_foreign_key", "＋ 添加外键"),
+    ("designer.uniques", "唯一约束"),
+    ("designer.uniques_hint", "唯一约束不能在这里修改，重建表时保留"),
+    ("designer.preview", "语句预览"),
+    ("designer.no_changes", "没有修改"),
+    ("designer.apply", "应用"),
+    ("designer.apply_hint", "在一个事务中执行全部语句，任何一条失败时回滚"),
+    ("designer.open_script", "在编辑器中打开"),
+    ("designer.reset", "还原"),
+    ("designer.applied", "已修改表 {name}"),
+    ("designer.apply_failed", "执行失败，已回滚全部修改: {error}"),
+    ("designer.error_no_name", "请输入表名"),
+    ("designer.error_no_columns", "表至少需要一列"),
+    ("designer.error_column_name", "第 {index} 列没有名称"),
+    ("designer.error_type", "列 {name} 没有类型"),
+    ("designer.error_duplicate", "列名 {name} 重复"),
+    ("designer.index_dropped", "索引 {name} 用到了删除或重命名的列，重建后不再保留"),
+];
@@ -0,0 +1,201 @@
+//! 单元格查看模块
+//! 在独立的窗口中查看单元格的完整内容，嵌套值显示为可以展开的树
+
+use crate::app::MyApp;
+use crate::database::{QueryResult, format_value};
+use crate::hex::{BlobFormat, HexView};
+use crate::i18n::{tr, tr_args};
+use crate::nested::{NestedValue, Segment};
+use duckdb::types::Value;
+use eframe::egui;
+
+/// 单元格查看窗口
+pub struct CellViewer {
+    pub column: String,
+    pub row: usize, // 结果中的行号
+    pub col: usize,
+    pub value: Value,
+    pub nested: Option<NestedValue>,
+    pub selected: Vec<Segment>, // 树中选中的元素
+    pub as_text: bool,          // 以缩进的 JSON 文本显示嵌套值
+    pub source: Option<String>, // 产生结果的语句，用于生成展开查询
+    pub editable: bool,         // 可以从文件载入新的值
+    pub hex: HexView,           // BLOB 值的查看状态
+}
+
+impl CellViewer {
+    /// 查看结果中的一个单元格
+    pub fn new(
+        result: &QueryResult,
+        row: usize,
+        col: usize,
+        source: Option<String>,
+        editable: bool,
+    ) -> Option<Self> {
+        let value = result.rows.get(row)?.get(col)?.clone();
+        let hex = match &value {
+            Value::Blob(bytes) => HexView::new(bytes),
+            _ => HexView::default(),
+        };
+        Some(Self {
+            column: result.columns[col].clone(),
+            row,
+            col,
+            nested: NestedValue::new(&value),
+            value,
+            selected: Vec::new(),
+            as_text: false,
+            source,
+            editable,
+            hex,
+        })
+    }
+
+    /// 渲染窗口
+    pub fn show(app: &mut MyApp, ctx: &egui::Context) {
+        let Some(mut viewer) = app.cell_viewer.take() else {
+            return;
+        };
+
+        let mut is_open = true;
+        let mut copy = None;
+        let mut query = None;
+        let mut load = false;
+        let mut status = None;
+        let title = tr_args(
+            "viewer.title",
+            &[("column", &viewer.column), ("row", &(viewer.row + 1))],
+        );
+        egui::Window::new(title)
+            .id(egui::Id::new("cell_viewer"))
+            .open(&mut is_open)
+            .default_size([520.0, 420.0])
+            .show(ctx, |ui| match (&viewer.nested, &viewer.value) {
+                (Some(nested), _) => {
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut viewer.as_text, false, tr("viewer.tree"));
+                        ui.selectable_value(&mut viewer.as_text, true, tr("viewer.text"));
+                    });
+                    let path = nested.path(&viewer.column, &viewer.selected);
+                    ui.horizontal(|ui| {
+                        ui.label(tr("viewer.path"));
+                        ui.code(&path);
+                        if ui.button(tr("viewer.copy_path")).clicked() {
+                            copy = Some(path.clone());
+                        }
+                        let button = ui
+                            .add_enabled(
+                                viewer.source.is_some(),
+                                egui::Button::new(tr("viewer.generate_query")),
+                            )
+                            .on_hover_text(tr("viewer.generate_query_hint"));
+                        if button.clicked()
+                            && let Some(sql) = &viewer.source
+                        {
+                            query = Some(nested.query(sql, &viewer.column, &viewer.selected));
+                        }
+                    });
+                    ui.separator();
+
+                    egui::ScrollArea::both()
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| {
+                            if viewer.as_text {
+                                let text = nested.root.pretty();
+                                ui.add(
+                                    egui::TextEdit::multiline(&mut text.as_str())
+                                        .code_editor()
+                                        .desired_width(f32::INFINITY),
+                                );
+                            } else {
+                                crate::nested::tree(ui, &nested.root, &mut viewer.selected);
+                            }
+                        });
+                }
+                (None, Value::Blob(bytes)) => {
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("hex.save")).clicked() {
+                            status = save_blob(&viewer.column, bytes);
+                        }
+                        load |= blob_load_button(ui, viewer.editable);
+                    });
+                    crate::hex::show(ui, bytes, &mut viewer.hex);
+                }
+                (None, value) => {
+                    let text = format_value(value);
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("menu.copy")).clicked() {
+                            copy = Some(text.clone());
+                        }
+                        // NULL 的列类型未知，也可以载入文件
+                        if matches!(value, Value::Null) {
+                            load |= blob_load_button(ui, viewer.editable);
+                        }
+                    });
+                    egui::ScrollArea::both()
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| {
+                            ui.add(
+                                egui::TextEdit::multiline(&mut text.as_str())
+                                    .code_editor()
+                                    .desired_width(f32::INFINITY),
+                            );
+                        });
+                }
+            });
+
+        if let Some(text) = copy {
+            app.clipboard = Some(text);
+            app.set_status_message(tr("status.copied_value").to_owned());
+        }
+        if let Some(sql) = query {
+            app.open_query_tab(sql, None);
+        }
+        if load {
+            match load_blob() {
+                Some(Ok(bytes)) if app.stage_cell_blob(viewer.row, viewer.col, &bytes) => {
+                    viewer.hex.reset(&bytes);
+                    viewer.nested = None;
+                    viewer.value = Value::Blob(bytes);
+                }
+                Some(Err(message)) => status = Some(message),
+                _ => {}
+            }
+        }
+        if let Some(message) = status {
+            app.set_status_message(message);
+        }
+        if is_open {
+            app.cell_viewer = Some(viewer);
+        }
+    }
+}
+
+/// 载入文件的按钮，不能编辑时禁用
+fn blob_load_button(ui: &mut egui::Ui, editable: bool) -> bool {
+    ui.add_enabled(editable, egui::Button::new(tr("hex.load")))
+        .on_disabled_hover_text(tr("hex.load_disabled"))
+        .clicked()
+}
+
+/// 把 BLOB 值保存到文件，扩展名按识别出的格式选择，返回状态栏消息
+fn save_blob(column: &str, bytes: &[u8]) -> Option<String> {
+    let extension = BlobFormat::detect(bytes).extension();
+    let path = rfd::FileDialog::new()
+        .set_title(tr("dialog.save_blob"))
+        .set_file_name(format!("{}.{}", column, extension))
+        .save_file()?;
+    let path = path.display().to_string();
+    Some(match std::fs::write(&path, bytes) {
+        Ok(()) => tr_args("status.file_saved", &[("path", &path)]),
+        Err(e) => tr_args("status.file_save_failed", &[("error", &e)]),
+    })
+}
+
+/// 选择要载入单元格的文件，取消时返回 `None`
+fn load_blob() -> Option<Result<Vec<u8>, String>> {
+    let path = rfd::FileDialog::new()
+        .set_title(tr("dialog.load_blob"))
+        .pick_file()?;
+    Some(std::fs::read(&path).map_err(|e| tr_args("status.file_open_failed", &[("error", &e)])))
+}
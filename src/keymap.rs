@@ -0,0 +1,140 @@
+//! 快捷键模块
+//! 解析快捷键字符串并分发菜单动作
+
+use eframe::egui;
+
+/// 在文本输入框获得焦点时交给输入框处理的编辑类动作
+const TEXT_EDIT_ACTIONS: &[&str] = &["undo", "redo", "cut", "copy", "paste", "delete"];
+
+/// 解析形如 `Ctrl+Shift+O` 的快捷键字符串
+pub fn parse_shortcut(text: &str) -> Option<egui::KeyboardShortcut> {
+    let mut modifiers = egui::Modifiers::NONE;
+    let mut key = None;
+
+    for part in text.split('+').map(str::trim) {
+        match part.to_lowercase().as_str() {
+            "ctrl" | "control" | "cmd" => modifiers |= egui::Modifiers::COMMAND,
+            "shift" => modifiers |= egui::Modifiers::SHIFT,
+            "alt" | "option" => modifiers |= egui::Modifiers::ALT,
+            "" => return None,
+            _ => {
+                // 只允许一个非修饰键
+                if key.is_some() {
+                    return None;
+                }
+                let name = match part {
+                    "Del" => "Delete",
+                    "Esc" => "Escape",
+                    _ => part,
+                };
+                key = Some(
+                    egui::Key::from_name(name)
+                        .or_else(|| egui::Key::from_name(&name.to_uppercase()))?,
+                );
+            }
+        }
+    }
+
+    key.map(|key| egui::KeyboardShortcut::new(modifiers, key))
+}
+
+/// 检查快捷键字符串是否有效
+pub fn is_valid_shortcut(text: &str) -> bool {
+    parse_shortcut(text).is_some()
+}
+
+/// 按下的修饰键个数，Ctrl 和 Command 算作一个
+fn modifier_count(modifiers: egui::Modifiers) -> usize {
+    [
+        modifiers.alt,
+        modifiers.shift,
+        modifiers.ctrl || modifiers.command || modifiers.mac_cmd,
+    ]
+    .into_iter()
+    .filter(|&pressed| pressed)
+    .count()
+}
+
+/// 从输入中找出被按下的快捷键对应的动作
+///
+/// `bindings` 为 (动作, 快捷键) 列表；文本框获得焦点时跳过编辑类动作。
+/// 系统的复制快捷键只产生 `Copy` 事件，没有按键事件，直接对应 `copy` 动作。
+pub fn triggered_action<'a>(
+    ctx: &egui::Context,
+    bindings: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Option<String> {
+    let text_has_focus = ctx.wants_keyboard_input();
+    let bindings: Vec<(&str, &str)> = bindings.into_iter().collect();
+
+    if !text_has_focus
+        && bindings.iter().any(|(action, _)| *action == "copy")
+        && ctx.input(|i| i.events.contains(&egui::Event::Copy))
+    {
+        return Some("copy".to_owned());
+    }
+
+    // `consume_shortcut` 忽略多按的 Shift 和 Alt，修饰键多的先匹配，
+    // 否则 Ctrl+S 会抢走 Ctrl+Shift+S
+    let mut shortcuts: Vec<(&str, egui::KeyboardShortcut)> = bindings
+        .into_iter()
+        .filter(|(action, _)| !(text_has_focus && TEXT_EDIT_ACTIONS.contains(action)))
+        .filter_map(|(action, shortcut)| Some((action, parse_shortcut(shortcut)?)))
+        .collect();
+    shortcuts.sort_by_key(|(_, shortcut)| std::cmp::Reverse(modifier_count(shortcut.modifiers)));
+
+    for (action, shortcut) in shortcuts {
+        if ctx.input_mut(|i| i.consume_shortcut(&shortcut)) {
+            return Some(action.to_owned());
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 在一帧中按下快捷键，返回触发的动作
+    fn press(shortcut: &str, bindings: &[(&str, &str)]) -> Option<String> {
+        let shortcut = parse_shortcut(shortcut).unwrap();
+        let ctx = egui::Context::default();
+        let input = egui::RawInput {
+            modifiers: shortcut.modifiers,
+            events: vec![egui::Event::Key {
+                key: shortcut.logical_key,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers: shortcut.modifiers,
+            }],
+            ..egui::RawInput::default()
+        };
+        let mut action = None;
+        let _ = ctx.run(input, |ctx| {
+            action = triggered_action(ctx, bindings.iter().copied());
+        });
+        action
+    }
+
+    #[test]
+    fn more_specific_shortcut_wins() {
+        let bindings = [
+            ("save_file", "Ctrl+S"),
+            ("save_all", "Ctrl+Shift+S"),
+            ("open_file", "Ctrl+O"),
+        ];
+        for _ in 0..2 {
+            assert_eq!(
+                press("Ctrl+Shift+S", &bindings).as_deref(),
+                Some("save_all")
+            );
+            assert_eq!(press("Ctrl+S", &bindings).as_deref(), Some("save_file"));
+        }
+        let reversed: Vec<_> = bindings.iter().rev().copied().collect();
+        assert_eq!(
+            press("Ctrl+Shift+S", &reversed).as_deref(),
+            Some("save_all")
+        );
+    }
+}
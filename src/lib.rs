@@ -2,8 +2,34 @@
 //! 定义应用的核心结构和功能
 
 pub mod app;
+pub mod chart;
 pub mod config;
 pub mod database;
+pub mod ddl;
+pub mod designer;
+pub mod diff;
+pub mod editing;
+pub mod explain;
+pub mod export;
+pub mod filter;
+pub mod fonts;
+pub mod hex;
+pub mod highlight;
+pub mod history;
+pub mod i18n;
+pub mod inspector;
+pub mod keymap;
+pub mod library;
+pub mod nested;
+pub mod params;
+pub mod profile;
+pub mod record;
+pub mod results;
+pub mod schema;
+pub mod session;
+pub mod settings;
+pub mod stats;
+pub mod theme;
 pub mod ui;
 pub mod utils;
 
@@ -11,3 +37,4 @@ pub mod utils;
 pub use app::{MenuItem, MenuType, MyApp};
 pub use config::AppConfig;
 pub use database::DatabaseManager;
+pub use session::SessionState;
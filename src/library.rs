@@ -0,0 +1,710 @@
+//! 查询库模块
+//! 保存的查询和代码片段，以 `.sql` 文件的形式存放在目录中
+//!
+//! 每个文件开头可以有几行元数据注释：
+//!
+//! ```sql
+//! -- name: 表的前 N 行
+//! -- description: 快速查看表内容
+//! -- kind: snippet
+//! SELECT * FROM ${table} LIMIT ${n:10}
+//! ```
+//!
+//! 子目录即文件夹，整个目录可以直接放进 git 与团队共享。
+
+use crate::app::MyApp;
+use crate::config::AppConfig;
+use crate::i18n::{tr, tr_args};
+use eframe::egui;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 默认的查询库子目录
+const LIBRARY_DIR_NAME: &str = "library";
+
+/// 查询库条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EntryKind {
+    #[default]
+    Query, // 完整的查询，在新标签页中打开
+    Snippet, // 代码片段，插入到当前编辑器
+}
+
+impl EntryKind {
+    /// 元数据中的名称
+    pub fn code(self) -> &'static str {
+        match self {
+            EntryKind::Query => "query",
+            EntryKind::Snippet => "snippet",
+        }
+    }
+
+    /// 显示名称的文本键
+    pub fn label(self) -> &'static str {
+        match self {
+            EntryKind::Query => "library.kind_query",
+            EntryKind::Snippet => "library.kind_snippet",
+        }
+    }
+}
+
+/// 保存的查询或代码片段
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LibraryEntry {
+    pub name: String,
+    pub description: String,
+    pub kind: EntryKind,
+    pub folder: String, // 相对于查询库根目录的文件夹，使用 `/` 分隔
+    pub sql: String,
+    pub path: PathBuf, // 文件路径
+}
+
+impl LibraryEntry {
+    /// 解析 `.sql` 文件内容，没有 `name` 元数据时使用 `fallback_name`
+    pub fn parse(content: &str, fallback_name: &str) -> Self {
+        let mut entry = Self {
+            name: fallback_name.to_string(),
+            ..Self::default()
+        };
+
+        let mut body_start = 0;
+        for line in content.split_inclusive('\n') {
+            let meta = line
+                .trim()
+                .strip_prefix("--")
+                .and_then(|rest| rest.split_once(':'))
+                .map(|(key, value)| (key.trim(), value.trim()));
+            match meta {
+                Some(("name", value)) if !value.is_empty() => entry.name = value.to_string(),
+                Some(("name", _)) => {}
+                Some(("description", value)) => entry.description = value.to_string(),
+                Some(("kind", value)) => {
+                    entry.kind = if value == EntryKind::Snippet.code() {
+                        EntryKind::Snippet
+                    } else {
+                        EntryKind::Query
+                    }
+                }
+                _ => break,
+            }
+            body_start += line.len();
+        }
+
+        entry.sql = content[body_start..].to_string();
+        entry
+    }
+
+    /// 生成 `.sql` 文件内容
+    pub fn to_file_content(&self) -> String {
+        let mut content = format!("-- name: {}\n", self.name);
+        if !self.description.is_empty() {
+            content.push_str(&format!("-- description: {}\n", self.description));
+        }
+        content.push_str(&format!("-- kind: {}\n", self.kind.code()));
+        content.push_str(&self.sql);
+        if !self.sql.ends_with('\n') {
+            content.push('\n');
+        }
+        content
+    }
+}
+
+/// SQL 中的占位符 `${name}` 或 `${name:默认值}`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    pub name: String,
+    pub default: Option<String>,
+}
+
+/// 找出 SQL 中的占位符，同名占位符只返回第一次出现的
+pub fn placeholders(sql: &str) -> Vec<Placeholder> {
+    let mut result: Vec<Placeholder> = Vec::new();
+    for (_, inner) in placeholder_spans(sql) {
+        let (name, default) = match inner.split_once(':') {
+            Some((name, default)) => (name.trim(), Some(default.to_string())),
+            None => (inner.trim(), None),
+        };
+        if !result.iter().any(|p| p.name == name) {
+            result.push(Placeholder {
+                name: name.to_string(),
+                default,
+            });
+        }
+    }
+    result
+}
+
+/// 用给定的值替换占位符，没有提供值的占位符使用默认值或保持原样
+pub fn fill_placeholders(sql: &str, values: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(sql.len());
+    let mut last = 0;
+    for (range, inner) in placeholder_spans(sql) {
+        result.push_str(&sql[last..range.start]);
+        let (name, default) = match inner.split_once(':') {
+            Some((name, default)) => (name.trim(), Some(default)),
+            None => (inner.trim(), None),
+        };
+        match values.get(name).map(String::as_str).or(default) {
+            Some(value) => result.push_str(value),
+            None => result.push_str(&sql[range.clone()]),
+        }
+        last = range.end;
+    }
+    result.push_str(&sql[last..]);
+    result
+}
+
+/// 占位符的位置和花括号内的文本
+fn placeholder_spans(sql: &str) -> Vec<(std::ops::Range<usize>, &str)> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = sql[offset..].find("${").map(|i| offset + i) {
+        let Some(end) = sql[start + 2..].find('}').map(|i| start + 2 + i) else {
+            break;
+        };
+        let inner = &sql[start + 2..end];
+        let name = inner.split(':').next().unwrap_or_default().trim();
+        if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            spans.push((start..end + 1, inner));
+        }
+        offset = end + 1;
+    }
+    spans
+}
+
+/// 查询库
+#[derive(Debug, Clone, Default)]
+pub struct Library {
+    pub root: PathBuf,
+    pub entries: Vec<LibraryEntry>, // 按文件夹和名称排序
+}
+
+impl Library {
+    /// 查询库目录，`configured` 为空时使用配置目录下的 `library`
+    pub fn dir(configured: &str) -> PathBuf {
+        if configured.trim().is_empty() {
+            AppConfig::config_dir().join(LIBRARY_DIR_NAME)
+        } else {
+            PathBuf::from(configured.trim())
+        }
+    }
+
+    /// 加载目录中所有的 `.sql` 文件，返回查询库和读取失败的错误信息
+    pub fn load(root: &Path) -> (Self, Vec<String>) {
+        let mut library = Self {
+            root: root.to_path_buf(),
+            entries: Vec::new(),
+        };
+        let mut errors = Vec::new();
+        library.load_dir(root, &mut errors);
+        library
+            .entries
+            .sort_by_key(|e| (e.folder.to_lowercase(), e.name.to_lowercase()));
+        (library, errors)
+    }
+
+    fn load_dir(&mut self, dir: &Path, errors: &mut Vec<String>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let hidden = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+            if hidden {
+                continue;
+            }
+            if path.is_dir() {
+                self.load_dir(&path, errors);
+                continue;
+            }
+            if path.extension().and_then(|ext| ext.to_str()) != Some("sql") {
+                continue;
+            }
+
+            match fs::read_to_string(&path) {
+                Ok(content) => {
+                    let stem = path
+                        .file_stem()
+                        .map(|stem| stem.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    let mut entry = LibraryEntry::parse(&content, &stem);
+                    entry.folder = self.folder_of(&path);
+                    entry.path = path;
+                    self.entries.push(entry);
+                }
+                Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+    }
+
+    /// 文件所在的文件夹（相对于根目录）
+    fn folder_of(&self, path: &Path) -> String {
+        path.parent()
+            .and_then(|dir| dir.strip_prefix(&self.root).ok())
+            .map(|dir| {
+                dir.components()
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .unwrap_or_default()
+    }
+
+    /// 按文件夹分组的条目下标
+    pub fn folders(&self) -> BTreeMap<&str, Vec<usize>> {
+        let mut folders: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (i, entry) in self.entries.iter().enumerate() {
+            folders.entry(entry.folder.as_str()).or_default().push(i);
+        }
+        folders
+    }
+
+    /// 保存条目到 `根目录/文件夹/名称.sql`，返回文件路径
+    pub fn save(&self, entry: &LibraryEntry) -> std::io::Result<PathBuf> {
+        let mut dir = self.root.clone();
+        for part in entry.folder.split('/').map(str::trim) {
+            if !part.is_empty() && part != "." && part != ".." {
+                dir.push(safe_file_name(part));
+            }
+        }
+        fs::create_dir_all(&dir)?;
+
+        let path = dir.join(format!("{}.sql", safe_file_name(&entry.name)));
+        fs::write(&path, entry.to_file_content())?;
+        Ok(path)
+    }
+}
+
+/// 把名称转换为可以作为文件名的文本
+fn safe_file_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    if name.is_empty() {
+        "untitled".to_string()
+    } else {
+        name
+    }
+}
+
+/// 填写占位符后要执行的操作
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptAction {
+    Run(usize), // 执行指定编辑器标签页
+    Insert,     // 插入到当前编辑器
+}
+
+/// 占位符填写对话框
+#[derive(Debug, Clone)]
+pub struct PlaceholderPrompt {
+    pub sql: String,
+    pub action: PromptAction,
+    pub values: Vec<(Placeholder, String)>,
+}
+
+impl PlaceholderPrompt {
+    /// 为 SQL 中的占位符创建对话框，没有占位符时返回 `None`
+    ///
+    /// 输入框预先填入上次使用的值或默认值。
+    pub fn new(
+        sql: &str,
+        action: PromptAction,
+        last_values: &HashMap<String, String>,
+    ) -> Option<Self> {
+        let values: Vec<_> = placeholders(sql)
+            .into_iter()
+            .map(|p| {
+                let value = last_values
+                    .get(&p.name)
+                    .cloned()
+                    .or_else(|| p.default.clone())
+                    .unwrap_or_default();
+                (p, value)
+            })
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+        Some(Self {
+            sql: sql.to_string(),
+            action,
+            values,
+        })
+    }
+
+    /// 填写后的 SQL
+    pub fn filled_sql(&self) -> String {
+        fill_placeholders(&self.sql, &self.value_map())
+    }
+
+    fn value_map(&self) -> HashMap<String, String> {
+        self.values
+            .iter()
+            .map(|(p, value)| (p.name.clone(), value.clone()))
+            .collect()
+    }
+
+    /// 渲染对话框
+    pub fn show(app: &mut MyApp, ctx: &egui::Context) {
+        let Some(mut prompt) = app.placeholder_prompt.take() else {
+            return;
+        };
+
+        let mut is_open = true;
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(tr("library.prompt_title"))
+            .id(egui::Id::new("placeholder_prompt"))
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("placeholder_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 6.0])
+                    .show(ui, |ui| {
+                        for (i, (placeholder, value)) in prompt.values.iter_mut().enumerate() {
+                            ui.label(&placeholder.name);
+                            let response = ui.text_edit_singleline(value);
+                            if i == 0 && ui.memory(|m| m.focused().is_none()) {
+                                response.request_focus();
+                            }
+                            if response.lost_focus()
+                                && ui.input(|input| input.key_pressed(egui::Key::Enter))
+                            {
+                                confirmed = true;
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                ui.separator();
+                ui.add(
+                    egui::Label::new(egui::RichText::new(prompt.filled_sql()).monospace()).wrap(),
+                );
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("library.prompt_confirm")).clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button(tr("library.cancel")).clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+
+        if confirmed {
+            app.library_panel.last_values.extend(prompt.value_map());
+            let sql = prompt.filled_sql();
+            match prompt.action {
+                PromptAction::Run(index) => app.execute_tab_sql(index, &sql),
+                PromptAction::Insert => app.insert_into_editor(&sql),
+            }
+        } else if is_open && !cancelled {
+            app.placeholder_prompt = Some(prompt);
+        }
+    }
+}
+
+/// 保存到查询库的对话框
+#[derive(Debug, Clone, Default)]
+pub struct SaveDialog {
+    pub entry: LibraryEntry,
+}
+
+/// 查询库面板状态
+#[derive(Debug, Default)]
+pub struct LibraryPanel {
+    pub library: Library,
+    pub loaded: bool,                         // 是否已经加载过
+    pub last_values: HashMap<String, String>, // 上次填写的占位符值
+    pub save_dialog: Option<SaveDialog>,
+}
+
+impl LibraryPanel {
+    /// 重新加载查询库
+    pub fn reload(app: &mut MyApp) {
+        let root = Library::dir(&app.config.files.library_dir);
+        let (library, errors) = Library::load(&root);
+        for error in errors {
+            app.notify(
+                crate::app::NotificationLevel::Warning,
+                tr("notify.library_load_failed"),
+                &error,
+            );
+        }
+        app.library_panel.library = library;
+        app.library_panel.loaded = true;
+    }
+
+    /// 在侧边栏中渲染查询库
+    pub fn show(app: &mut MyApp, ui: &mut egui::Ui) {
+        if !app.library_panel.loaded {
+            Self::reload(app);
+        }
+
+        let mut open = None;
+        let mut insert = None;
+        let mut delete = None;
+        let mut reload = false;
+
+        ui.horizontal(|ui| {
+            ui.strong(tr("library.title"));
+            if ui
+                .small_button("⟳")
+                .on_hover_text(tr("library.reload"))
+                .clicked()
+            {
+                reload = true;
+            }
+            if ui
+                .small_button("＋")
+                .on_hover_text(tr("library.save_current"))
+                .clicked()
+            {
+                app.handle_menu_action("save_to_library");
+            }
+        });
+
+        let library = &app.library_panel.library;
+        if library.entries.is_empty() {
+            ui.label(
+                egui::RichText::new(tr_args(
+                    "library.empty",
+                    &[("dir", &library.root.display())],
+                ))
+                .weak(),
+            );
+        }
+
+        for (folder, indices) in library.folders() {
+            let mut render_entries = |ui: &mut egui::Ui| {
+                for &i in &indices {
+                    let entry = &library.entries[i];
+                    ui.horizontal(|ui| {
+                        let icon = match entry.kind {
+                            EntryKind::Query => "📄",
+                            EntryKind::Snippet => "✂",
+                        };
+                        let mut response =
+                            ui.selectable_label(false, format!("{} {}", icon, entry.name));
+                        if !entry.description.is_empty() {
+                            response = response.on_hover_text(&entry.description);
+                        }
+                        if response.double_clicked() {
+                            match entry.kind {
+                                EntryKind::Query => open = Some(i),
+                                EntryKind::Snippet => insert = Some(i),
+                            }
+                        }
+                        response.context_menu(|ui| {
+                            if ui.button(tr("library.open")).clicked() {
+                                open = Some(i);
+                                ui.close();
+                            }
+                            if ui.button(tr("library.insert")).clicked() {
+                                insert = Some(i);
+                                ui.close();
+                            }
+                            if ui.button(tr("library.delete")).clicked() {
+                                delete = Some(i);
+                                ui.close();
+                            }
+                        });
+                    });
+                }
+            };
+
+            if folder.is_empty() {
+                render_entries(ui);
+            } else {
+                egui::CollapsingHeader::new(format!("📁 {}", folder))
+                    .id_salt(("library_folder", folder))
+                    .show(ui, render_entries);
+            }
+        }
+
+        if let Some(i) = open {
+            let entry = app.library_panel.library.entries[i].clone();
+            app.open_library_entry(&entry);
+        }
+        if let Some(i) = insert {
+            let sql = app.library_panel.library.entries[i].sql.clone();
+            let last_values = &app.library_panel.last_values;
+            match PlaceholderPrompt::new(&sql, PromptAction::Insert, last_values) {
+                Some(prompt) => app.placeholder_prompt = Some(prompt),
+                None => app.insert_into_editor(&sql),
+            }
+        }
+        if let Some(i) = delete {
+            let entry = &app.library_panel.library.entries[i];
+            match fs::remove_file(&entry.path) {
+                Ok(()) => reload = true,
+                Err(e) => app.set_status_message(e.to_string()),
+            }
+        }
+        if reload {
+            Self::reload(app);
+        }
+    }
+
+    /// 渲染“保存到查询库”对话框
+    pub fn show_save_dialog(app: &mut MyApp, ctx: &egui::Context) {
+        let Some(mut dialog) = app.library_panel.save_dialog.take() else {
+            return;
+        };
+
+        let mut is_open = true;
+        let mut save = false;
+        let mut cancelled = false;
+        egui::Window::new(tr("library.save_title"))
+            .id(egui::Id::new("library_save_dialog"))
+            .open(&mut is_open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                let entry = &mut dialog.entry;
+                egui::Grid::new("library_save_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("library.name"));
+                        ui.text_edit_singleline(&mut entry.name);
+                        ui.end_row();
+
+                        ui.label(tr("library.folder"));
+                        ui.text_edit_singleline(&mut entry.folder)
+                            .on_hover_text(tr("library.folder_hint"));
+                        ui.end_row();
+
+                        ui.label(tr("library.description"));
+                        ui.text_edit_singleline(&mut entry.description);
+                        ui.end_row();
+
+                        ui.label(tr("library.kind"));
+                        ui.horizontal(|ui| {
+                            for kind in [EntryKind::Query, EntryKind::Snippet] {
+                                ui.radio_value(&mut entry.kind, kind, tr(kind.label()));
+                            }
+                        });
+                        ui.end_row();
+                    });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !entry.name.trim().is_empty(),
+                            egui::Button::new(tr("library.save")),
+                        )
+                        .clicked()
+                    {
+                        save = true;
+                    }
+                    if ui.button(tr("library.cancel")).clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+
+        if save {
+            match app.library_panel.library.save(&dialog.entry) {
+                Ok(path) => {
+                    let message = tr_args("status.library_saved", &[("path", &path.display())]);
+                    app.set_status_message(message);
+                    Self::reload(app);
+                }
+                Err(e) => {
+                    app.notify(
+                        crate::app::NotificationLevel::Error,
+                        tr("notify.library_save_failed"),
+                        &e.to_string(),
+                    );
+                    app.library_panel.save_dialog = Some(dialog);
+                }
+            }
+        } else if is_open && !cancelled {
+            app.library_panel.save_dialog = Some(dialog);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_metadata_header() {
+        let entry = LibraryEntry::parse(
+            "-- name: 前 N 行\n-- kind: snippet\n-- 普通注释\nSELECT 1;\n",
+            "file",
+        );
+        assert_eq!(entry.name, "前 N 行");
+        assert_eq!(entry.kind, EntryKind::Snippet);
+        assert_eq!(entry.sql, "-- 普通注释\nSELECT 1;\n");
+
+        let reparsed = LibraryEntry::parse(&entry.to_file_content(), "other");
+        assert_eq!(reparsed.name, entry.name);
+        assert_eq!(reparsed.kind, entry.kind);
+        assert_eq!(reparsed.sql, entry.sql);
+
+        let plain = LibraryEntry::parse("SELECT 2", "plain");
+        assert_eq!(plain.name, "plain");
+        assert_eq!(plain.kind, EntryKind::Query);
+        assert_eq!(plain.sql, "SELECT 2");
+    }
+
+    #[test]
+    fn finds_and_fills_placeholders() {
+        let sql =
+            "SELECT * FROM ${table} WHERE a = ${ value } LIMIT ${n:10} -- ${table} ${not valid}";
+        let found = placeholders(sql);
+        let names: Vec<_> = found.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["table", "value", "n"]);
+        assert_eq!(found[2].default.as_deref(), Some("10"));
+
+        let values = HashMap::from([("table".to_string(), "users".to_string())]);
+        assert_eq!(
+            fill_placeholders(sql, &values),
+            "SELECT * FROM users WHERE a = ${ value } LIMIT 10 -- users ${not valid}"
+        );
+    }
+
+    #[test]
+    fn saves_and_loads_folders() {
+        let root = std::env::temp_dir().join(format!("e-library-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let library = Library {
+            root: root.clone(),
+            entries: Vec::new(),
+        };
+
+        let entry = LibraryEntry {
+            name: "a/b".to_string(),
+            folder: "报表/月度".to_string(),
+            sql: "SELECT 1".to_string(),
+            ..LibraryEntry::default()
+        };
+        let path = library.save(&entry).unwrap();
+        assert_eq!(path, root.join("报表").join("月度").join("a_b.sql"));
+        fs::write(root.join("top.sql"), "SELECT 2").unwrap();
+
+        let (loaded, errors) = Library::load(&root);
+        assert!(errors.is_empty());
+        let summary: Vec<_> = loaded
+            .entries
+            .iter()
+            .map(|e| (e.folder.as_str(), e.name.as_str()))
+            .collect();
+        assert_eq!(summary, [("", "top"), ("报表/月度", "a/b")]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,336 @@
+//! 嵌套值模块
+//! 把 LIST、STRUCT、MAP 和 JSON 值转换为树，生成访问其中元素的路径表达式和展开查询
+
+use crate::database::{identifier, is_plain_identifier, quote_string, sql_literal};
+use crate::export::{json_string, json_value};
+use duckdb::types::Value;
+use eframe::egui;
+
+/// 路径中的一段
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Segment {
+    Field(String), // STRUCT 的字段或 JSON 对象的键
+    Index(usize),  // 列表中的位置，从 0 开始
+    Key(String),   // MAP 的键，写成 SQL 字面量
+}
+
+impl Segment {
+    /// 树中显示的名称
+    fn label(&self) -> String {
+        match self {
+            Segment::Field(name) => name.clone(),
+            Segment::Index(i) => format!("[{}]", i),
+            Segment::Key(key) => key.clone(),
+        }
+    }
+}
+
+/// 树中的节点
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Scalar(String), // 写成 JSON 的值
+    List(Vec<Node>),
+    Fields(Vec<(Segment, Node)>), // STRUCT、MAP 和 JSON 对象
+}
+
+impl Node {
+    /// 由 DuckDB 的值创建，MAP 的键保留为 SQL 字面量
+    fn from_value(value: &Value) -> Self {
+        match value {
+            Value::List(items) | Value::Array(items) => {
+                Node::List(items.iter().map(Node::from_value).collect())
+            }
+            Value::Struct(fields) => Node::Fields(
+                fields
+                    .iter()
+                    .map(|(k, v)| (Segment::Field(k.clone()), Node::from_value(v)))
+                    .collect(),
+            ),
+            Value::Map(entries) => Node::Fields(
+                entries
+                    .iter()
+                    .map(|(k, v)| (Segment::Key(sql_literal(k)), Node::from_value(v)))
+                    .collect(),
+            ),
+            Value::Union(v) => Node::from_value(v),
+            other => Node::Scalar(json_value(other)),
+        }
+    }
+
+    /// 由解析后的 JSON 创建
+    fn from_json(json: &serde_json::Value) -> Self {
+        match json {
+            serde_json::Value::Array(items) => {
+                Node::List(items.iter().map(Node::from_json).collect())
+            }
+            serde_json::Value::Object(fields) => Node::Fields(
+                fields
+                    .iter()
+                    .map(|(k, v)| (Segment::Field(k.clone()), Node::from_json(v)))
+                    .collect(),
+            ),
+            other => Node::Scalar(other.to_string()),
+        }
+    }
+
+    /// 按路径找到子节点
+    pub fn get(&self, path: &[Segment]) -> Option<&Node> {
+        let Some((first, rest)) = path.split_first() else {
+            return Some(self);
+        };
+        let child = match (self, first) {
+            (Node::List(items), Segment::Index(i)) => items.get(*i)?,
+            (Node::Fields(fields), _) => &fields.iter().find(|(s, _)| s == first)?.1,
+            _ => return None,
+        };
+        child.get(rest)
+    }
+
+    /// 缩进的 JSON 文本，字段保持原有顺序
+    pub fn pretty(&self) -> String {
+        let mut text = String::new();
+        self.write_pretty(&mut text, 0);
+        text
+    }
+
+    fn write_pretty(&self, text: &mut String, depth: usize) {
+        let indent = |text: &mut String, depth: usize| text.push_str(&"  ".repeat(depth));
+        match self {
+            Node::Scalar(value) => text.push_str(value),
+            Node::List(items) if items.is_empty() => text.push_str("[]"),
+            Node::Fields(fields) if fields.is_empty() => text.push_str("{}"),
+            Node::List(items) => {
+                text.push_str("[\n");
+                for (i, item) in items.iter().enumerate() {
+                    indent(text, depth + 1);
+                    item.write_pretty(text, depth + 1);
+                    text.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+                }
+                indent(text, depth);
+                text.push(']');
+            }
+            Node::Fields(fields) => {
+                text.push_str("{\n");
+                for (i, (segment, value)) in fields.iter().enumerate() {
+                    indent(text, depth + 1);
+                    let key = match segment {
+                        Segment::Key(key) => key.trim_matches('\'').replace("''", "'"),
+                        other => other.label(),
+                    };
+                    text.push_str(&json_string(&key));
+                    text.push_str(": ");
+                    value.write_pretty(text, depth + 1);
+                    text.push_str(if i + 1 < fields.len() { ",\n" } else { "\n" });
+                }
+                indent(text, depth);
+                text.push('}');
+            }
+        }
+    }
+}
+
+/// 单元格中的嵌套值
+#[derive(Debug, Clone, PartialEq)]
+pub struct NestedValue {
+    pub root: Node,
+    pub json: bool, // 来自 JSON 文本，路径使用 JSONPath
+}
+
+impl NestedValue {
+    /// 嵌套类型的值或 JSON 文本，其他值返回 `None`
+    pub fn new(value: &Value) -> Option<Self> {
+        match value {
+            Value::List(_) | Value::Array(_) | Value::Struct(_) | Value::Map(_) => Some(Self {
+                root: Node::from_value(value),
+                json: false,
+            }),
+            Value::Union(v) => Self::new(v),
+            Value::Text(text) if text.trim_start().starts_with(['{', '[']) => {
+                let json = serde_json::from_str(text).ok()?;
+                Some(Self {
+                    root: Node::from_json(&json),
+                    json: true,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// 访问元素的表达式，如 `col.field[3]` 或 `col->'$.field[2]'`
+    ///
+    /// DuckDB 的列表下标从 1 开始，JSONPath 的下标从 0 开始。
+    pub fn path(&self, column: &str, path: &[Segment]) -> String {
+        let mut expression = identifier(column);
+        if self.json {
+            if path.is_empty() {
+                return expression;
+            }
+            let mut json_path = "$".to_string();
+            for segment in path {
+                match segment {
+                    Segment::Index(i) => json_path.push_str(&format!("[{}]", i)),
+                    Segment::Field(name) | Segment::Key(name) => {
+                        if is_plain_identifier(name) {
+                            json_path.push('.');
+                            json_path.push_str(name);
+                        } else {
+                            json_path.push_str(&format!(".{}", json_string(name)));
+                        }
+                    }
+                }
+            }
+            expression.push_str("->");
+            expression.push_str(&quote_string(&json_path));
+        } else {
+            for segment in path {
+                match segment {
+                    Segment::Field(name) => {
+                        expression.push('.');
+                        expression.push_str(&identifier(name));
+                    }
+                    Segment::Index(i) => expression.push_str(&format!("[{}]", i + 1)),
+                    Segment::Key(key) => expression.push_str(&format!("[{}]", key)),
+                }
+            }
+        }
+        expression
+    }
+
+    /// 从原查询中提取或展开选中元素的查询
+    ///
+    /// 列表展开为多行，STRUCT 展开为多列，MAP 和 JSON 对象展开为键值对，其他元素直接提取。
+    pub fn query(&self, sql: &str, column: &str, path: &[Segment]) -> String {
+        let expression = self.path(column, path);
+        let select = match (self.root.get(path), self.json) {
+            (Some(Node::List(_)), false) => format!("unnest({}) AS value", expression),
+            (Some(Node::List(_)), true) => {
+                format!("unnest(CAST({} AS JSON[])) AS value", expression)
+            }
+            (Some(Node::Fields(fields)), false)
+                if fields.iter().all(|(s, _)| matches!(s, Segment::Field(_))) =>
+            {
+                format!("unnest({})", expression)
+            }
+            (Some(Node::Fields(_)), false) => format!(
+                "unnest(map_keys({0})) AS key, unnest(map_values({0})) AS value",
+                expression
+            ),
+            (Some(Node::Fields(_)), true) => format!(
+                "unnest(json_keys({0})) AS key, unnest(CAST(json_extract({0}, '$.*') AS JSON[])) AS value",
+                expression
+            ),
+            _ => format!("{} AS value", expression),
+        };
+        format!(
+            "SELECT {}\nFROM (\n{}\n) AS source",
+            select,
+            sql.trim().trim_end_matches(';')
+        )
+    }
+}
+
+/// 渲染嵌套值的树，点击节点时更新 `selected`
+pub fn tree(ui: &mut egui::Ui, node: &Node, selected: &mut Vec<Segment>) {
+    let mut path = Vec::new();
+    tree_node(ui, node, &mut path, None, selected);
+}
+
+fn tree_node(
+    ui: &mut egui::Ui,
+    node: &Node,
+    path: &mut Vec<Segment>,
+    label: Option<String>,
+    selected: &mut Vec<Segment>,
+) {
+    let is_selected = *path == *selected;
+    let children: Vec<(Segment, &Node)> = match node {
+        Node::Scalar(value) => {
+            let text = match label {
+                Some(label) => format!("{}: {}", label, value),
+                None => value.clone(),
+            };
+            if ui.selectable_label(is_selected, text).clicked() {
+                *selected = path.clone();
+            }
+            return;
+        }
+        Node::List(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (Segment::Index(i), item))
+            .collect(),
+        Node::Fields(fields) => fields.iter().map(|(s, n)| (s.clone(), n)).collect(),
+    };
+
+    let summary = match node {
+        Node::List(items) => format!("[{}]", items.len()),
+        _ => format!("{{{}}}", children.len()),
+    };
+    let text = match label {
+        Some(label) => format!("{} {}", label, summary),
+        None => summary,
+    };
+    let id = ui.make_persistent_id(("nested", path.clone()));
+    egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, path.len() < 2)
+        .show_header(ui, |ui| {
+            if ui.selectable_label(is_selected, text).clicked() {
+                *selected = path.clone();
+            }
+        })
+        .body(|ui| {
+            for (segment, child) in children {
+                let label = segment.label();
+                path.push(segment);
+                tree_node(ui, child, path, Some(label), selected);
+                path.pop();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use duckdb::types::OrderedMap;
+
+    #[test]
+    fn builds_paths_and_queries_for_nested_values() {
+        let value = Value::Struct(OrderedMap::from(vec![
+            ("id".to_string(), Value::Int(1)),
+            (
+                "Tags".to_string(),
+                Value::List(vec![Value::Text("a".into()), Value::Text("b".into())]),
+            ),
+        ]));
+        let nested = NestedValue::new(&value).unwrap();
+        let path = [Segment::Field("Tags".into()), Segment::Index(1)];
+        assert_eq!(nested.root.get(&path), Some(&Node::Scalar("\"b\"".into())));
+        assert_eq!(nested.path("order", &path), "\"order\".\"Tags\"[2]");
+        assert_eq!(
+            nested.query("SELECT * FROM t;", "col", &path[..1]),
+            "SELECT unnest(col.\"Tags\") AS value\nFROM (\nSELECT * FROM t\n) AS source"
+        );
+        assert_eq!(
+            nested.root.pretty(),
+            "{\n  \"id\": 1,\n  \"Tags\": [\n    \"a\",\n    \"b\"\n  ]\n}"
+        );
+        assert!(NestedValue::new(&Value::Text("plain".into())).is_none());
+    }
+
+    #[test]
+    fn reads_json_text() {
+        let nested = NestedValue::new(&Value::Text(r#"{"items": [{"sku": 7}]}"#.into())).unwrap();
+        assert!(nested.json);
+        let path = [
+            Segment::Field("items".into()),
+            Segment::Index(0),
+            Segment::Field("sku".into()),
+        ];
+        assert_eq!(nested.root.get(&path), Some(&Node::Scalar("7".into())));
+        assert_eq!(nested.path("doc", &path), "doc->'$.items[0].sku'");
+        assert!(
+            nested
+                .query("SELECT doc FROM t", "doc", &path[..1])
+                .starts_with("SELECT unnest(CAST(doc->'$.items' AS JSON[])) AS value")
+        );
+    }
+}
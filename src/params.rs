@@ -0,0 +1,499 @@
+//! 查询参数模块
+//! 识别 SQL 中的绑定参数（`?`、`$1`、`$name`），在执行前提示输入参数值
+
+use crate::app::MyApp;
+use crate::database::QueryParams;
+use crate::highlight::{TokenKind, tokenize};
+use crate::i18n::{tr, tr_args};
+use chrono::{NaiveDate, NaiveDateTime};
+use duckdb::types::{TimeUnit, Value};
+use eframe::egui;
+use std::collections::HashMap;
+
+/// 参数值类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParamType {
+    #[default]
+    Auto, // 按输入内容推断
+    Text,
+    Integer,
+    Double,
+    Boolean,
+    Date,
+    Timestamp,
+    Null,
+}
+
+impl ParamType {
+    /// 类型选择框中的全部类型
+    pub const ALL: [ParamType; 8] = [
+        ParamType::Auto,
+        ParamType::Text,
+        ParamType::Integer,
+        ParamType::Double,
+        ParamType::Boolean,
+        ParamType::Date,
+        ParamType::Timestamp,
+        ParamType::Null,
+    ];
+
+    /// 显示名称的文本键
+    pub fn label(self) -> &'static str {
+        match self {
+            ParamType::Auto => "params.type_auto",
+            ParamType::Text => "params.type_text",
+            ParamType::Integer => "params.type_integer",
+            ParamType::Double => "params.type_double",
+            ParamType::Boolean => "params.type_boolean",
+            ParamType::Date => "params.type_date",
+            ParamType::Timestamp => "params.type_timestamp",
+            ParamType::Null => "params.type_null",
+        }
+    }
+
+    /// 由 SQL 类型名称得到参数类型，不认识的类型返回 `None`
+    pub fn from_sql_type(name: &str) -> Option<Self> {
+        let kind = match name.to_uppercase().as_str() {
+            "TINYINT" | "SMALLINT" | "INTEGER" | "INT" | "BIGINT" | "HUGEINT" | "UTINYINT"
+            | "USMALLINT" | "UINTEGER" | "UBIGINT" | "INT2" | "INT4" | "INT8" => ParamType::Integer,
+            "DOUBLE" | "FLOAT" | "REAL" | "DECIMAL" | "NUMERIC" | "FLOAT4" | "FLOAT8" => {
+                ParamType::Double
+            }
+            "BOOLEAN" | "BOOL" => ParamType::Boolean,
+            "DATE" => ParamType::Date,
+            "TIMESTAMP" | "DATETIME" | "TIMESTAMPTZ" => ParamType::Timestamp,
+            "VARCHAR" | "TEXT" | "STRING" | "CHAR" | "UUID" => ParamType::Text,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// 把输入的文本转换为参数值
+    pub fn parse(self, text: &str) -> Result<Value, String> {
+        let trimmed = text.trim();
+        let invalid = || tr_args("params.invalid_value", &[("type", &tr(self.label()))]);
+        match self {
+            ParamType::Auto => Ok(if let Ok(v) = trimmed.parse::<i64>() {
+                Value::BigInt(v)
+            } else if let Ok(v) = trimmed.parse::<f64>() {
+                Value::Double(v)
+            } else if let Ok(v) = ParamType::Boolean.parse(trimmed) {
+                v
+            } else {
+                Value::Text(text.to_string())
+            }),
+            ParamType::Text => Ok(Value::Text(text.to_string())),
+            ParamType::Integer => trimmed.parse().map(Value::BigInt).map_err(|_| invalid()),
+            ParamType::Double => trimmed.parse().map(Value::Double).map_err(|_| invalid()),
+            ParamType::Boolean => match trimmed.to_lowercase().as_str() {
+                "true" | "t" => Ok(Value::Boolean(true)),
+                "false" | "f" => Ok(Value::Boolean(false)),
+                _ => Err(invalid()),
+            },
+            ParamType::Date => NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+                .map(|date| Value::Date32(date.to_epoch_days()))
+                .map_err(|_| invalid()),
+            ParamType::Timestamp => ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(trimmed, format).ok())
+                .or_else(|| {
+                    NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                })
+                .map(|t| Value::Timestamp(TimeUnit::Microsecond, t.and_utc().timestamp_micros()))
+                .ok_or_else(invalid),
+            ParamType::Null => Ok(Value::Null),
+        }
+    }
+}
+
+/// 语句中的绑定参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlParam {
+    pub name: String,     // `$name` 不带 `$`，`?` 和 `$1` 为序号
+    pub positional: bool, // 是否按序号绑定
+    pub hint: ParamType,  // 从 `::类型` 或 `CAST(? AS 类型)` 推断的类型
+}
+
+/// 找出一条语句中的绑定参数，同名参数只返回一次
+///
+/// 字符串和注释中的 `?`、`$` 不算参数。
+pub fn statement_params(sql: &str) -> Vec<SqlParam> {
+    let tokens: Vec<_> = tokenize(sql)
+        .into_iter()
+        .filter(|(kind, _)| !matches!(kind, TokenKind::Whitespace | TokenKind::Comment))
+        .collect();
+    let text = |i: usize| tokens.get(i).map(|(_, range)| &sql[range.clone()]);
+    let upper = |i: usize| text(i).map(str::to_uppercase);
+
+    let mut params: Vec<SqlParam> = Vec::new();
+    let mut question_marks = 0;
+    let mut i = 0;
+    while i < tokens.len() {
+        let (kind, range) = &tokens[i];
+        let start = i;
+        let param = match (kind, &sql[range.clone()]) {
+            (TokenKind::Operator, "?") => {
+                question_marks += 1;
+                Some((question_marks.to_string(), true))
+            }
+            (TokenKind::Operator, "$") => match tokens.get(i + 1) {
+                Some((next, next_range)) if next_range.start == range.end => {
+                    let name = &sql[next_range.clone()];
+                    match next {
+                        TokenKind::Number if name.chars().all(|c| c.is_ascii_digit()) => {
+                            i += 1;
+                            Some((name.to_string(), true))
+                        }
+                        TokenKind::Identifier | TokenKind::Keyword => {
+                            i += 1;
+                            Some((name.to_string(), false))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        if let Some((name, positional)) = param {
+            let cast_type = if text(i + 1) == Some(":") && text(i + 2) == Some(":") {
+                text(i + 3)
+            } else if start >= 2
+                && upper(start - 2).as_deref() == Some("CAST")
+                && text(start - 1) == Some("(")
+                && upper(i + 1).as_deref() == Some("AS")
+            {
+                text(i + 2)
+            } else {
+                None
+            };
+            let hint = cast_type
+                .and_then(ParamType::from_sql_type)
+                .unwrap_or_default();
+
+            if !params.iter().any(|p| p.name == name) {
+                params.push(SqlParam {
+                    name,
+                    positional,
+                    hint,
+                });
+            }
+        }
+        i += 1;
+    }
+    params
+}
+
+/// 参数输入框
+#[derive(Debug, Clone)]
+pub struct ParamInput {
+    pub statement: Option<usize>, // 序号参数所属的语句，命名参数为 `None`
+    pub param: SqlParam,
+    pub kind: ParamType,
+    pub text: String,
+    pub error: Option<String>,
+}
+
+impl ParamInput {
+    /// 显示的参数名称
+    fn label(&self, statement_count: usize) -> String {
+        match self.statement {
+            Some(index) if statement_count > 1 => tr_args(
+                "params.statement_param",
+                &[("index", &(index + 1)), ("name", &self.param.name)],
+            ),
+            Some(_) => format!("?{}", self.param.name),
+            None => format!("${}", self.param.name),
+        }
+    }
+}
+
+/// 参数输入对话框
+#[derive(Debug, Clone)]
+pub struct ParamPrompt {
+    pub tab: usize, // 要执行的编辑器标签页
+    pub statements: Vec<(String, Vec<SqlParam>)>,
+    pub inputs: Vec<ParamInput>,
+}
+
+impl ParamPrompt {
+    /// 为语句中的参数创建对话框，没有参数时返回 `None`
+    ///
+    /// 命名参数在多条语句间共用一个输入框，并预先填入上次使用的值。
+    pub fn new(
+        tab: usize,
+        statements: &[&str],
+        last_values: &HashMap<String, (ParamType, String)>,
+    ) -> Option<Self> {
+        let statements: Vec<_> = statements
+            .iter()
+            .map(|sql| (sql.to_string(), statement_params(sql)))
+            .collect();
+
+        let mut inputs: Vec<ParamInput> = Vec::new();
+        for (index, (_, params)) in statements.iter().enumerate() {
+            for param in params {
+                let statement = param.positional.then_some(index);
+                let exists = inputs
+                    .iter()
+                    .any(|input| input.statement == statement && input.param.name == param.name);
+                if exists {
+                    continue;
+                }
+
+                let (kind, text) = match last_values.get(&param.name) {
+                    Some((kind, text)) if !param.positional => (*kind, text.clone()),
+                    _ => (param.hint, String::new()),
+                };
+                inputs.push(ParamInput {
+                    statement,
+                    param: param.clone(),
+                    kind,
+                    text,
+                    error: None,
+                });
+            }
+        }
+
+        if inputs.is_empty() {
+            return None;
+        }
+        Some(Self {
+            tab,
+            statements,
+            inputs,
+        })
+    }
+
+    /// 转换输入的参数值，得到每条语句的绑定参数
+    ///
+    /// 有无效输入时在对应输入框记录错误并返回 `None`。
+    pub fn bind(&mut self) -> Option<Vec<(String, QueryParams)>> {
+        let mut values = Vec::with_capacity(self.inputs.len());
+        let mut valid = true;
+        for input in &mut self.inputs {
+            match input.kind.parse(&input.text) {
+                Ok(value) => {
+                    input.error = None;
+                    values.push(value);
+                }
+                Err(e) => {
+                    input.error = Some(e);
+                    valid = false;
+                    values.push(Value::Null);
+                }
+            }
+        }
+        if !valid {
+            return None;
+        }
+
+        let bound = self
+            .statements
+            .iter()
+            .enumerate()
+            .map(|(index, (sql, params))| {
+                if params.is_empty() {
+                    return (sql.clone(), QueryParams::None);
+                }
+                let named = params
+                    .iter()
+                    .filter_map(|param| {
+                        let statement = param.positional.then_some(index);
+                        self.inputs
+                            .iter()
+                            .position(|input| {
+                                input.statement == statement && input.param.name == param.name
+                            })
+                            .map(|i| (param.name.clone(), values[i].clone()))
+                    })
+                    .collect();
+                (sql.clone(), QueryParams::Named(named))
+            })
+            .collect();
+        Some(bound)
+    }
+
+    /// 渲染对话框
+    pub fn show(app: &mut MyApp, ctx: &egui::Context) {
+        let Some(mut prompt) = app.param_prompt.take() else {
+            return;
+        };
+
+        let mut is_open = true;
+        let mut confirmed = false;
+        let mut cancelled = false;
+        let statement_count = prompt.statements.len();
+        egui::Window::new(tr("params.title"))
+            .id(egui::Id::new("param_prompt"))
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("param_grid")
+                    .num_columns(3)
+                    .spacing([12.0, 6.0])
+                    .show(ui, |ui| {
+                        for (i, input) in prompt.inputs.iter_mut().enumerate() {
+                            let label = ui.label(input.label(statement_count));
+                            if let Some((sql, _)) = input
+                                .statement
+                                .and_then(|index| prompt.statements.get(index))
+                            {
+                                label.on_hover_text(sql);
+                            }
+
+                            egui::ComboBox::from_id_salt(("param_type", i))
+                                .selected_text(tr(input.kind.label()))
+                                .show_ui(ui, |ui| {
+                                    for kind in ParamType::ALL {
+                                        ui.selectable_value(
+                                            &mut input.kind,
+                                            kind,
+                                            tr(kind.label()),
+                                        );
+                                    }
+                                });
+
+                            ui.vertical(|ui| {
+                                let response = ui.add_enabled(
+                                    input.kind != ParamType::Null,
+                                    egui::TextEdit::singleline(&mut input.text)
+                                        .hint_text(type_hint(input.kind)),
+                                );
+                                if i == 0 && ui.memory(|m| m.focused().is_none()) {
+                                    response.request_focus();
+                                }
+                                if response.lost_focus()
+                                    && ui.input(|input| input.key_pressed(egui::Key::Enter))
+                                {
+                                    confirmed = true;
+                                }
+                                if let Some(error) = &input.error {
+                                    ui.colored_label(ui.visuals().error_fg_color, error);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("params.run")).clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button(tr("params.cancel")).clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+
+        if confirmed && let Some(statements) = prompt.bind() {
+            for input in prompt
+                .inputs
+                .iter()
+                .filter(|input| input.statement.is_none())
+            {
+                app.param_values
+                    .insert(input.param.name.clone(), (input.kind, input.text.clone()));
+            }
+            app.run_tab_statements(prompt.tab, statements);
+        } else if is_open && !cancelled {
+            app.param_prompt = Some(prompt);
+        }
+    }
+}
+
+/// 输入框中的格式提示
+fn type_hint(kind: ParamType) -> &'static str {
+    match kind {
+        ParamType::Integer => "42",
+        ParamType::Double => "3.14",
+        ParamType::Boolean => "true / false",
+        ParamType::Date => "2024-01-31",
+        ParamType::Timestamp => "2024-01-31 12:00:00",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(sql: &str) -> Vec<(String, bool, ParamType)> {
+        statement_params(sql)
+            .into_iter()
+            .map(|p| (p.name, p.positional, p.hint))
+            .collect()
+    }
+
+    #[test]
+    fn detects_parameters_outside_strings() {
+        assert_eq!(
+            names("SELECT * FROM t WHERE a = ? AND b = ?::DATE AND c = '?' -- ?"),
+            [
+                ("1".to_string(), true, ParamType::Auto),
+                ("2".to_string(), true, ParamType::Date),
+            ]
+        );
+        assert_eq!(
+            names("SELECT $name, CAST($n AS INTEGER), $name, $1, a$b"),
+            [
+                ("name".to_string(), false, ParamType::Auto),
+                ("n".to_string(), false, ParamType::Integer),
+                ("1".to_string(), true, ParamType::Auto),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_values_by_type() {
+        assert_eq!(ParamType::Auto.parse("42"), Ok(Value::BigInt(42)));
+        assert_eq!(ParamType::Auto.parse("1.5"), Ok(Value::Double(1.5)));
+        assert_eq!(ParamType::Auto.parse("TRUE"), Ok(Value::Boolean(true)));
+        assert_eq!(ParamType::Auto.parse("abc"), Ok(Value::Text("abc".into())));
+        assert_eq!(ParamType::Text.parse("42"), Ok(Value::Text("42".into())));
+        assert_eq!(ParamType::Date.parse("1970-01-02"), Ok(Value::Date32(1)));
+        assert_eq!(
+            ParamType::Timestamp.parse("1970-01-01 00:00:01"),
+            Ok(Value::Timestamp(TimeUnit::Microsecond, 1_000_000))
+        );
+        assert!(ParamType::Integer.parse("1.5").is_err());
+        assert_eq!(ParamType::Null.parse("x"), Ok(Value::Null));
+    }
+
+    #[test]
+    fn binds_each_statement_separately() {
+        let last = HashMap::from([("id".to_string(), (ParamType::Integer, "7".to_string()))]);
+        let mut prompt = ParamPrompt::new(
+            0,
+            &["SELECT $id, ?", "SELECT 1", "DELETE FROM t WHERE id = $id"],
+            &last,
+        )
+        .unwrap();
+        assert_eq!(prompt.inputs.len(), 2);
+        assert_eq!(prompt.inputs[0].text, "7");
+
+        prompt.inputs[1].text = "x".to_string();
+        let bound = prompt.bind().unwrap();
+        assert_eq!(
+            bound[0].1,
+            QueryParams::Named(vec![
+                ("id".to_string(), Value::BigInt(7)),
+                ("1".to_string(), Value::Text("x".into())),
+            ])
+        );
+        assert_eq!(bound[1].1, QueryParams::None);
+        assert_eq!(
+            bound[2].1,
+            QueryParams::Named(vec![("id".to_string(), Value::BigInt(7))])
+        );
+
+        prompt.inputs[0].kind = ParamType::Date;
+        assert!(prompt.bind().is_none());
+        assert!(prompt.inputs[0].error.is_some());
+    }
+}
@@ -0,0 +1,136 @@
+//! 性能分析模块
+//! 每次执行的耗时、行数和内存信息，来自 DuckDB 的 JSON 性能分析输出
+
+use crate::explain::ExplainPlan;
+use crate::i18n::{tr, tr_args};
+use crate::utils::StringUtils;
+use eframe::egui;
+use serde_json::Value as Json;
+use std::time::Duration;
+
+/// 一次执行的性能信息
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryProfile {
+    pub wall_time: Duration,           // 客户端测得的耗时（包括读取结果）
+    pub rows: usize,                   // 读取到的行数
+    pub latency: Option<f64>,          // DuckDB 统计的查询耗时（秒）
+    pub cpu_time: Option<f64>,         // CPU 时间（秒）
+    pub peak_memory: Option<u64>,      // 缓冲区内存峰值（字节）
+    pub memory_allocated: Option<u64>, // 分配的内存（字节）
+    pub rows_scanned: Option<u64>,     // 扫描的行数
+    pub plan: Option<ExplainPlan>,     // 各算子的耗时和行数
+}
+
+impl QueryProfile {
+    /// 由客户端测得的耗时和行数创建，DuckDB 的统计信息为空
+    pub fn new(wall_time: Duration, rows: usize) -> Self {
+        Self {
+            wall_time,
+            rows,
+            ..Self::default()
+        }
+    }
+
+    /// 读取 `enable_profiling = 'json'` 输出的统计信息
+    pub fn with_profiling_output(mut self, text: &str) -> Self {
+        let Ok(json) = serde_json::from_str::<Json>(text) else {
+            return self;
+        };
+        self.latency = json.get("latency").and_then(Json::as_f64);
+        self.cpu_time = json.get("cpu_time").and_then(Json::as_f64);
+        self.peak_memory = json.get("system_peak_buffer_memory").and_then(Json::as_u64);
+        self.memory_allocated = json.get("total_memory_allocated").and_then(Json::as_u64);
+        self.rows_scanned = json.get("cumulative_rows_scanned").and_then(Json::as_u64);
+        self.plan = ExplainPlan::parse(text)
+            .ok()
+            .filter(|plan| !plan.roots.is_empty());
+        self
+    }
+
+    /// 客户端耗时（毫秒）
+    pub fn wall_ms(&self) -> f64 {
+        self.wall_time.as_secs_f64() * 1000.0
+    }
+}
+
+/// 渲染性能信息，点击“查看算子”时返回 true
+pub fn show(ui: &mut egui::Ui, profile: &QueryProfile) -> bool {
+    let mut show_plan = false;
+    let title = tr_args(
+        "profile.summary",
+        &[
+            ("ms", &format!("{:.1}", profile.wall_ms())),
+            ("rows", &profile.rows),
+        ],
+    );
+
+    egui::CollapsingHeader::new(title)
+        .id_salt("query_profile")
+        .show(ui, |ui| {
+            let seconds = |value: Option<f64>| {
+                value.map_or_else(|| "-".to_string(), |s| format!("{:.2} ms", s * 1000.0))
+            };
+            let bytes = |value: Option<u64>| {
+                value.map_or_else(|| "-".to_string(), StringUtils::format_bytes)
+            };
+            let count =
+                |value: Option<u64>| value.map_or_else(|| "-".to_string(), |n| n.to_string());
+
+            let items = [
+                ("profile.wall_time", format!("{:.2} ms", profile.wall_ms())),
+                ("profile.latency", seconds(profile.latency)),
+                ("profile.cpu_time", seconds(profile.cpu_time)),
+                ("profile.rows", profile.rows.to_string()),
+                ("profile.rows_scanned", count(profile.rows_scanned)),
+                ("profile.peak_memory", bytes(profile.peak_memory)),
+                ("profile.memory_allocated", bytes(profile.memory_allocated)),
+            ];
+            egui::Grid::new("query_profile_grid")
+                .num_columns(2)
+                .spacing([16.0, 2.0])
+                .show(ui, |ui| {
+                    for (key, value) in items {
+                        ui.label(egui::RichText::new(tr(key)).weak());
+                        ui.label(value);
+                        ui.end_row();
+                    }
+                });
+
+            if profile.latency.is_none() {
+                ui.label(egui::RichText::new(tr("profile.disabled")).weak());
+            }
+            if profile.plan.is_some() && ui.button(tr("profile.show_plan")).clicked() {
+                show_plan = true;
+            }
+        });
+
+    show_plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_profiling_output() {
+        let profile = QueryProfile::new(Duration::from_millis(8), 7).with_profiling_output(
+            r#"{
+                "latency": 0.005,
+                "cpu_time": 0.002,
+                "system_peak_buffer_memory": 2433024,
+                "total_memory_allocated": 262144,
+                "cumulative_rows_scanned": 1000,
+                "children": [{ "operator_name": "HASH_GROUP_BY", "operator_timing": 0.002, "children": [] }]
+            }"#,
+        );
+
+        assert_eq!(profile.rows, 7);
+        assert_eq!(profile.latency, Some(0.005));
+        assert_eq!(profile.peak_memory, Some(2433024));
+        assert_eq!(profile.rows_scanned, Some(1000));
+        assert_eq!(profile.plan.unwrap().nodes()[0].name, "HASH_GROUP_BY");
+
+        let plain = QueryProfile::new(Duration::from_millis(8), 7).with_profiling_output("");
+        assert_eq!(plain, QueryProfile::new(Duration::from_millis(8), 7));
+    }
+}
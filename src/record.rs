@@ -0,0 +1,237 @@
+//! 记录视图模块
+//! 把结果中的一行转置为字段和值的列表，可以逐行浏览、搜索字段和比较两行
+
+use crate::config::GridConfig;
+use crate::database::{QueryResult, format_value};
+use crate::i18n::{tr, tr_args};
+use crate::results::{GridSelection, GridState};
+use duckdb::types::Value;
+use eframe::egui::{self, Color32};
+
+/// 记录视图的状态
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordState {
+    pub search: String,         // 按字段名或值筛选字段
+    pub pinned: Option<usize>,  // 固定用于比较的行，结果中的行号
+    pub differences_only: bool, // 比较时只显示不同的字段
+}
+
+/// 值的类型名称
+pub fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "NULL",
+        Value::Boolean(_) => "BOOLEAN",
+        Value::TinyInt(_) => "TINYINT",
+        Value::SmallInt(_) => "SMALLINT",
+        Value::Int(_) => "INTEGER",
+        Value::BigInt(_) => "BIGINT",
+        Value::HugeInt(_) => "HUGEINT",
+        Value::UTinyInt(_) => "UTINYINT",
+        Value::USmallInt(_) => "USMALLINT",
+        Value::UInt(_) => "UINTEGER",
+        Value::UBigInt(_) => "UBIGINT",
+        Value::UHugeInt(_) => "UHUGEINT",
+        Value::Float(_) => "FLOAT",
+        Value::Double(_) => "DOUBLE",
+        Value::Decimal(_) => "DECIMAL",
+        Value::Timestamp(..) => "TIMESTAMP",
+        Value::Text(_) => "VARCHAR",
+        Value::Blob(_) => "BLOB",
+        Value::Date32(_) => "DATE",
+        Value::Time64(..) => "TIME",
+        Value::Interval { .. } => "INTERVAL",
+        Value::List(_) => "LIST",
+        Value::Enum(_) => "ENUM",
+        Value::Struct(_) => "STRUCT",
+        Value::Array(_) => "ARRAY",
+        Value::Map(_) => "MAP",
+        Value::Union(_) => "UNION",
+        _ => "?",
+    }
+}
+
+/// 各列的类型，取第一个不是 NULL 的值的类型
+pub fn column_types(result: &QueryResult) -> Vec<&'static str> {
+    (0..result.columns.len())
+        .map(|col| {
+            result
+                .rows
+                .iter()
+                .map(|values| &values[col])
+                .find(|value| !matches!(value, Value::Null))
+                .map_or("NULL", type_name)
+        })
+        .collect()
+}
+
+/// 要显示的字段：名称或任一行的值包含搜索文本（不区分大小写）
+pub fn matching_fields(result: &QueryResult, rows: &[usize], search: &str) -> Vec<usize> {
+    let needle = search.trim().to_lowercase();
+    (0..result.columns.len())
+        .filter(|&col| {
+            needle.is_empty()
+                || result.columns[col].to_lowercase().contains(&needle)
+                || rows.iter().any(|&row| {
+                    format_value(&result.rows[row][col])
+                        .to_lowercase()
+                        .contains(&needle)
+                })
+        })
+        .collect()
+}
+
+/// 渲染记录视图
+///
+/// 选中两行时比较这两行，否则显示光标所在的行，固定了一行时与它比较。
+/// 翻页时移动表格的选区，切换回表格后仍然停在同一行。
+pub fn show(
+    ui: &mut egui::Ui,
+    result: &QueryResult,
+    grid_config: &GridConfig,
+    null_color: Color32,
+    grid: &mut GridState,
+    state: &mut RecordState,
+) {
+    grid.refresh(result);
+    let rows = result.rows.len();
+    let count = grid.visible_count(rows);
+    if count == 0 {
+        ui.label(tr("record.empty"));
+        return;
+    }
+
+    // 选区只包括已有的行，新增的行没有值
+    let selected: Vec<usize> = grid
+        .selection
+        .map(|selection| selection.row_range().filter(|&d| d < count).collect())
+        .unwrap_or_default();
+    let current = selected.last().copied().unwrap_or(0);
+    let row = grid.row_index(current, rows);
+    let compared: Vec<usize> = match (&selected[..], state.pinned) {
+        ([first, second], _) => vec![grid.row_index(*first, rows), grid.row_index(*second, rows)],
+        (_, Some(pinned)) if pinned < rows && pinned != row => vec![pinned, row],
+        _ => vec![row],
+    };
+
+    let mut target = None;
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(current > 0, egui::Button::new("◀"))
+            .on_hover_text(tr("record.previous"))
+            .clicked()
+        {
+            target = Some(current - 1);
+        }
+        ui.label(tr_args(
+            "record.position",
+            &[("row", &(current + 1)), ("count", &count)],
+        ));
+        if ui
+            .add_enabled(current + 1 < count, egui::Button::new("▶"))
+            .on_hover_text(tr("record.next"))
+            .clicked()
+        {
+            target = Some(current + 1);
+        }
+        ui.separator();
+
+        let mut pinned = state.pinned == Some(row);
+        if ui
+            .toggle_value(&mut pinned, tr("record.pin"))
+            .on_hover_text(tr("record.pin_hint"))
+            .changed()
+        {
+            state.pinned = pinned.then_some(row);
+        }
+        if compared.len() == 2 {
+            ui.checkbox(&mut state.differences_only, tr("record.differences_only"));
+        }
+        ui.separator();
+        ui.add(
+            egui::TextEdit::singleline(&mut state.search)
+                .hint_text(tr("record.search"))
+                .desired_width(180.0),
+        );
+    });
+    if let Some(target) = target {
+        let col = grid.selection.map_or(0, |s| s.cursor.1);
+        grid.selection = Some(GridSelection::cell(target, col));
+    }
+    ui.separator();
+
+    let types = column_types(result);
+    let mut fields = matching_fields(result, &compared, &state.search);
+    let differs = |col: usize| {
+        compared
+            .windows(2)
+            .any(|pair| result.rows[pair[0]][col] != result.rows[pair[1]][col])
+    };
+    if compared.len() == 2 && state.differences_only {
+        fields.retain(|&col| differs(col));
+    }
+    let highlight = ui.visuals().warn_fg_color.gamma_multiply(0.25);
+
+    egui::ScrollArea::both()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            egui::Grid::new("record_grid")
+                .striped(true)
+                .num_columns(2 + compared.len())
+                .spacing([16.0, 4.0])
+                .show(ui, |ui| {
+                    ui.strong(tr("record.field"));
+                    ui.strong(tr("record.type"));
+                    for &row in &compared {
+                        ui.strong(tr_args("record.row", &[("row", &(row + 1))]));
+                    }
+                    ui.end_row();
+
+                    for col in fields {
+                        ui.label(&result.columns[col]);
+                        ui.label(egui::RichText::new(types[col]).weak());
+                        let changed = compared.len() == 2 && differs(col);
+                        for &row in &compared {
+                            let value = &result.rows[row][col];
+                            let mut text = if matches!(value, Value::Null) {
+                                egui::RichText::new(&grid_config.null_display)
+                                    .italics()
+                                    .color(null_color)
+                            } else {
+                                egui::RichText::new(format_value(value))
+                            };
+                            if changed {
+                                text = text.background_color(highlight);
+                            }
+                            ui.add(egui::Label::new(text).wrap());
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infers_types_and_filters_fields() {
+        let result = QueryResult {
+            columns: vec!["id".into(), "name".into(), "note".into()],
+            rows: vec![
+                vec![Value::Int(1), Value::Text("Alice".into()), Value::Null],
+                vec![
+                    Value::Int(2),
+                    Value::Text("Bob".into()),
+                    Value::Text("id 7".into()),
+                ],
+            ],
+            ..QueryResult::default()
+        };
+        assert_eq!(column_types(&result), ["INTEGER", "VARCHAR", "VARCHAR"]);
+        assert_eq!(matching_fields(&result, &[0, 1], ""), [0, 1, 2]);
+        assert_eq!(matching_fields(&result, &[0], "ID"), [0]);
+        assert_eq!(matching_fields(&result, &[0, 1], "ID"), [0, 2]);
+        assert_eq!(matching_fields(&result, &[1], "bob"), [1]);
+    }
+}
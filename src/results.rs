@@ -0,0 +1,528 @@
+//! 查询结果模块
+//! 以表格形式显示查询结果
+
+use crate::config::GridConfig;
+use crate::database::{QueryResult, format_value};
+use crate::editing::{CellEdit, DataEditor};
+use crate::export::CopyFormat;
+use crate::filter::{ColumnFilter, FilterOp, ResultFilter};
+use crate::i18n::{tr, tr_args};
+use duckdb::types::Value;
+use eframe::egui::{self, Color32};
+use egui_extras::{Column, TableBuilder};
+use std::ops::RangeInclusive;
+
+/// 新增行的背景色
+const INSERTED_COLOR: Color32 = Color32::from_rgb(80, 170, 80);
+
+/// 结果面板显示的内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResultView {
+    #[default]
+    Grid,
+    Chart,
+    Record, // 单行的字段和值
+    Plan,   // 执行计划
+}
+
+/// 选中的单元格区域，`anchor` 为起点，`cursor` 为终点
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridSelection {
+    pub anchor: (usize, usize),
+    pub cursor: (usize, usize),
+    pub whole_rows: bool, // 通过行号选中整行
+}
+
+impl GridSelection {
+    /// 选中一个单元格
+    pub fn cell(row: usize, col: usize) -> Self {
+        Self {
+            anchor: (row, col),
+            cursor: (row, col),
+            whole_rows: false,
+        }
+    }
+
+    /// 选中从 `first` 到 `last` 的整行
+    pub fn rows_between(first: usize, last: usize) -> Self {
+        Self {
+            anchor: (first, 0),
+            cursor: (last, 0),
+            whole_rows: true,
+        }
+    }
+
+    /// 选中的行
+    pub fn row_range(&self) -> RangeInclusive<usize> {
+        self.anchor.0.min(self.cursor.0)..=self.anchor.0.max(self.cursor.0)
+    }
+
+    /// 选中的列，`count` 为结果的列数
+    pub fn column_range(&self, count: usize) -> RangeInclusive<usize> {
+        if self.whole_rows {
+            0..=count.saturating_sub(1)
+        } else {
+            self.anchor.1.min(self.cursor.1)..=self.anchor.1.max(self.cursor.1)
+        }
+    }
+
+    /// 单元格是否被选中
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        self.row_range().contains(&row)
+            && (self.whole_rows || self.column_range(usize::MAX).contains(&col))
+    }
+}
+
+/// 结果表格的界面状态
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GridState {
+    pub selection: Option<GridSelection>, // 选区使用显示的行号
+    pub filter: ResultFilter,
+    pub visible: Option<Vec<usize>>, // 筛选后显示的行，`None` 表示需要重新计算
+    pub search_matches: usize,       // 快速搜索匹配的单元格数
+    pub filter_error: Option<String>,
+}
+
+impl GridState {
+    /// 筛选条件改变后重新计算显示的行
+    pub fn invalidate(&mut self) {
+        self.visible = None;
+        self.selection = None;
+    }
+
+    /// 按筛选条件计算显示的行和快速搜索的匹配数
+    pub fn refresh(&mut self, result: &QueryResult) {
+        if self.visible.is_some() {
+            return;
+        }
+        let visible = match self.filter.apply(result) {
+            Ok(rows) => {
+                self.filter_error = None;
+                rows
+            }
+            Err(e) => {
+                self.filter_error = Some(e);
+                (0..result.rows.len()).collect()
+            }
+        };
+        let needle = self.filter.search.to_lowercase();
+        self.search_matches = visible
+            .iter()
+            .flat_map(|&row| &result.rows[row])
+            .filter(|value| ResultFilter::search_matches(&needle, value))
+            .count();
+        self.visible = Some(visible);
+    }
+
+    /// 显示的结果行数，不包括新增的行
+    pub fn visible_count(&self, rows: usize) -> usize {
+        self.visible.as_ref().map_or(rows, Vec::len)
+    }
+
+    /// 把显示的行号转换为结果中的行号，新增的行排在筛选后的行之后
+    pub fn row_index(&self, display: usize, rows: usize) -> usize {
+        match &self.visible {
+            Some(visible) if display < visible.len() => visible[display],
+            Some(visible) => rows + display - visible.len(),
+            None => display,
+        }
+    }
+
+    /// 选中的行在结果中的行号
+    pub fn selected_rows(&self, rows: usize) -> Vec<usize> {
+        self.selection.map_or_else(Vec::new, |selection| {
+            selection
+                .row_range()
+                .map(|display| self.row_index(display, rows))
+                .collect()
+        })
+    }
+
+    /// 点击单元格或行号时更新选区，按住 Shift 时扩展选区
+    fn select(&mut self, ui: &egui::Ui, row: usize, col: Option<usize>) {
+        let extend = ui.input(|i| i.modifiers.shift);
+        self.selection = Some(match (self.selection, col) {
+            (Some(selection), Some(col)) if extend => GridSelection {
+                cursor: (row, col),
+                ..selection
+            },
+            (Some(selection), None) if extend => {
+                GridSelection::rows_between(selection.anchor.0, row)
+            }
+            (_, Some(col)) => GridSelection::cell(row, col),
+            (_, None) => GridSelection::rows_between(row, row),
+        });
+    }
+}
+
+/// 渲染查询结果表格，返回右键菜单中选择的菜单动作
+///
+/// 传入 `editor` 时单元格可以双击编辑，暂存的修改以不同的背景色显示。
+pub fn show(
+    ui: &mut egui::Ui,
+    result: &QueryResult,
+    grid: &GridConfig,
+    null_color: Color32,
+    state: &mut GridState,
+    mut editor: Option<&mut DataEditor>,
+) -> Option<&'static str> {
+    let summary = if result.truncated {
+        tr_args("results.truncated", &[("count", &result.rows.len())])
+    } else {
+        tr_args("results.rows", &[("count", &result.rows.len())])
+    };
+    let mut action = None;
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(summary).weak());
+        if !result.columns.is_empty()
+            && ui
+                .small_button(tr("results.column_stats"))
+                .on_hover_text(tr("results.column_stats_hint"))
+                .clicked()
+        {
+            action = Some("column_stats");
+        }
+    });
+
+    if result.columns.is_empty() {
+        return None;
+    }
+    let rows = result.rows.len();
+    state.refresh(result);
+    let total_rows =
+        state.visible_count(rows) + editor.as_ref().map_or(0, |editor| editor.inserts.len());
+    let needle = state.filter.search.to_lowercase();
+
+    let row_height = ui.text_style_height(&egui::TextStyle::Body) + 4.0;
+    let mut table = TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto().at_least(32.0));
+    for _ in &result.columns {
+        table = table.column(
+            Column::auto()
+                .at_least(40.0)
+                .at_most(grid.column_max_width)
+                .clip(true),
+        );
+    }
+
+    table
+        .header(row_height, |mut header| {
+            header.col(|_ui| {});
+            for column in &result.columns {
+                header.col(|ui| {
+                    ui.strong(column);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(row_height, total_rows, |mut row| {
+                let display = row.index();
+                let index = state.row_index(display, rows);
+                let deleted = editor
+                    .as_ref()
+                    .is_some_and(|editor| editor.deletes.contains(&index));
+
+                row.col(|ui| {
+                    let mut number = egui::RichText::new(if index < rows {
+                        (index + 1).to_string()
+                    } else {
+                        "+".to_string()
+                    });
+                    if deleted {
+                        number = number.strikethrough().color(ui.visuals().error_fg_color);
+                    }
+                    let selected = state
+                        .selection
+                        .is_some_and(|s| s.whole_rows && s.row_range().contains(&display));
+                    let response = ui.selectable_label(selected, number);
+                    if response.clicked() {
+                        state.select(ui, display, None);
+                    }
+                    response.context_menu(|ui| {
+                        if let Some(chosen) =
+                            context_menu(ui, state, editor.as_deref_mut(), result, index, None)
+                        {
+                            action = Some(chosen);
+                        }
+                    });
+                });
+
+                for col in 0..result.columns.len() {
+                    row.col(|ui| {
+                        if state.selection.is_some_and(|s| s.contains(display, col)) {
+                            let color = ui.visuals().selection.bg_fill.gamma_multiply(0.4);
+                            ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+                        } else if result.rows.get(index).is_some_and(|values| {
+                            ResultFilter::search_matches(&needle, &values[col])
+                        }) {
+                            let color = ui.visuals().warn_fg_color.gamma_multiply(0.3);
+                            ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+                        }
+                        let response = match editor.as_deref_mut() {
+                            Some(editor) => {
+                                editable_cell(ui, editor, result, index, col, grid, null_color)
+                            }
+                            None => Some(cell(ui, &result.rows[index][col], grid, null_color)),
+                        };
+                        let Some(response) = response else {
+                            return;
+                        };
+
+                        let response = response.interact(egui::Sense::click());
+                        let outside = !state.selection.is_some_and(|s| s.contains(display, col));
+                        if response.clicked() || (response.secondary_clicked() && outside) {
+                            state.select(ui, display, Some(col));
+                        }
+                        // 不能编辑的单元格双击时在窗口中查看
+                        if response.double_clicked() {
+                            match editor.as_deref_mut() {
+                                Some(editor) if editor.can_edit(index, col) => {
+                                    editor.start_editing(result, index, col);
+                                }
+                                _ if index < rows => action = Some("view_cell"),
+                                _ => {}
+                            }
+                        }
+                        response.context_menu(|ui| {
+                            if let Some(chosen) = context_menu(
+                                ui,
+                                state,
+                                editor.as_deref_mut(),
+                                result,
+                                index,
+                                Some(col),
+                            ) {
+                                action = Some(chosen);
+                            }
+                        });
+                    });
+                }
+            });
+        });
+
+    action
+}
+
+/// 单元格和行号的右键菜单，返回选择的菜单动作，`row` 为结果中的行号
+fn context_menu(
+    ui: &mut egui::Ui,
+    state: &mut GridState,
+    editor: Option<&mut DataEditor>,
+    result: &QueryResult,
+    row: usize,
+    col: Option<usize>,
+) -> Option<&'static str> {
+    let mut action = None;
+    if ui.button(tr("menu.copy")).clicked() {
+        action = Some("copy");
+    }
+    ui.menu_button(tr("results.copy_as"), |ui| {
+        for format in CopyFormat::ALL {
+            if ui.button(tr(format.label())).clicked() {
+                action = Some(format.action());
+            }
+        }
+    });
+    if ui.button(tr("results.copy_advanced")).clicked() {
+        action = Some("copy_advanced");
+    }
+    if ui.button(tr("results.select_all")).clicked() {
+        let total =
+            state.visible_count(result.rows.len()) + editor.as_ref().map_or(0, |e| e.inserts.len());
+        state.selection = Some(GridSelection::rows_between(0, total.saturating_sub(1)));
+        ui.close();
+    }
+    if let Some(col) = col {
+        let value = result.rows.get(row).map(|values| &values[col]);
+        if ui
+            .add_enabled(value.is_some(), egui::Button::new(tr("results.view_value")))
+            .clicked()
+        {
+            action = Some("view_cell");
+        }
+        if ui
+            .add_enabled(
+                value.is_some(),
+                egui::Button::new(tr("results.filter_by_value")),
+            )
+            .clicked()
+            && let Some(value) = value
+        {
+            let op = if matches!(value, Value::Null) {
+                FilterOp::IsNull
+            } else {
+                FilterOp::Equals
+            };
+            state.filter.columns.push(ColumnFilter {
+                column: col,
+                op,
+                value: format_value(value),
+                upper: String::new(),
+            });
+            state.invalidate();
+            ui.close();
+        }
+    }
+
+    if let Some(editor) = editor {
+        ui.separator();
+        let rows = result.rows.len();
+        if let Some(col) = col {
+            let editable = editor.can_edit(row, col);
+            if ui
+                .add_enabled(editable, egui::Button::new(tr("editing.edit_cell")))
+                .clicked()
+            {
+                editor.start_editing(result, row, col);
+                ui.close();
+            }
+            if ui
+                .add_enabled(editable, egui::Button::new(tr("editing.set_null")))
+                .clicked()
+            {
+                editor.set_cell(result, row, col, CellEdit::Null);
+                ui.close();
+            }
+            let staged = editor.staged(rows, row, col).is_some();
+            if ui
+                .add_enabled(staged, egui::Button::new(tr("editing.revert")))
+                .clicked()
+            {
+                editor.revert_cell(rows, row, col);
+                ui.close();
+            }
+        }
+        let delete = if editor.deletes.contains(&row) {
+            "editing.undelete_row"
+        } else {
+            "editing.delete_row"
+        };
+        if ui.button(tr(delete)).clicked() {
+            crate::editing::toggle_delete_selection(editor, state, rows);
+            ui.close();
+        }
+    }
+
+    if action.is_some() {
+        ui.close();
+    }
+    action
+}
+
+/// 渲染单元格，NULL 使用主题颜色显示
+fn cell(
+    ui: &mut egui::Ui,
+    value: &Value,
+    grid: &GridConfig,
+    null_color: Color32,
+) -> egui::Response {
+    if matches!(value, Value::Null) {
+        null_label(ui, grid, null_color)
+    } else {
+        text_label(ui, &format_value(value))
+    }
+}
+
+/// 渲染文本，过长时截断并在悬停时显示全文
+fn text_label(ui: &mut egui::Ui, text: &str) -> egui::Response {
+    ui.add(egui::Label::new(text).truncate().selectable(false))
+        .on_hover_text(text)
+}
+
+/// 渲染 NULL
+fn null_label(ui: &mut egui::Ui, grid: &GridConfig, null_color: Color32) -> egui::Response {
+    ui.add(
+        egui::Label::new(
+            egui::RichText::new(&grid.null_display)
+                .italics()
+                .color(null_color),
+        )
+        .selectable(false),
+    )
+}
+
+/// 渲染可以编辑的单元格，正在编辑时返回 `None`
+///
+/// 修改过的单元格使用警告色背景，新增的行使用绿色，删除的行使用错误色。
+fn editable_cell(
+    ui: &mut egui::Ui,
+    editor: &mut DataEditor,
+    result: &QueryResult,
+    row: usize,
+    col: usize,
+    grid: &GridConfig,
+    null_color: Color32,
+) -> Option<egui::Response> {
+    if let Some(editing) = editor
+        .editing
+        .as_mut()
+        .filter(|editing| editing.row == row && editing.col == col)
+    {
+        let response =
+            ui.add(egui::TextEdit::singleline(&mut editing.text).desired_width(f32::INFINITY));
+        if editing.focus {
+            response.request_focus();
+            editing.focus = false;
+        }
+        if response.lost_focus() {
+            let text = std::mem::take(&mut editing.text);
+            editor.editing = None;
+            if !ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                editor.set_cell(result, row, col, CellEdit::Text(text));
+            }
+        }
+        return None;
+    }
+
+    let rows = result.rows.len();
+    let staged = editor.staged(rows, row, col);
+    let tint = if editor.deletes.contains(&row) {
+        Some(ui.visuals().error_fg_color)
+    } else if row >= rows {
+        Some(INSERTED_COLOR)
+    } else if staged.is_some() {
+        Some(ui.visuals().warn_fg_color)
+    } else {
+        None
+    };
+    if let Some(color) = tint {
+        ui.painter()
+            .rect_filled(ui.max_rect(), 0.0, color.gamma_multiply(0.25));
+    }
+
+    let response = match (staged, result.rows.get(row)) {
+        (Some(CellEdit::Null), _) => null_label(ui, grid, null_color),
+        (Some(CellEdit::Text(text)), _) => text_label(ui, text),
+        (None, Some(values)) => cell(ui, &values[col], grid, null_color),
+        (None, None) => ui.add(
+            egui::Label::new(egui::RichText::new("DEFAULT").italics().weak()).selectable(false),
+        ),
+    };
+    Some(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_covers_rectangle_or_whole_rows() {
+        let selection = GridSelection {
+            anchor: (4, 2),
+            cursor: (1, 0),
+            whole_rows: false,
+        };
+        assert_eq!(selection.row_range(), 1..=4);
+        assert_eq!(selection.column_range(5), 0..=2);
+        assert!(selection.contains(3, 1));
+        assert!(!selection.contains(3, 3));
+
+        let rows = GridSelection::rows_between(2, 0);
+        assert_eq!(rows.column_range(5), 0..=4);
+        assert!(rows.contains(1, 4));
+        assert!(!rows.contains(3, 0));
+    }
+}
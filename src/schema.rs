@@ -0,0 +1,790 @@
+//! 结构对比模块
+//! 比较两个数据库或模式中的表、列、约束、视图和宏，生成把目标改为与参照一致的迁移脚本
+
+use crate::app::MyApp;
+use crate::database::{DatabaseError, DatabaseManager, QueryParams, format_value, identifier};
+use crate::i18n::{tr, tr_args};
+use duckdb::types::Value;
+use eframe::egui::{self, Color32};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+/// 新建对象的颜色
+const CREATE_COLOR: Color32 = Color32::from_rgb(80, 170, 80);
+
+/// 从 `CREATE VIEW` 语句中取出查询
+static VIEW_BODY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)^\s*CREATE\s+(?:OR\s+REPLACE\s+)?VIEW\s+.+?\s+AS\s+(.*?);?\s*$").unwrap()
+});
+
+/// 表中的列
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnDef {
+    pub name: String,
+    pub data_type: String,
+    pub nullable: bool,
+    pub default: Option<String>, // 默认值表达式
+}
+
+impl ColumnDef {
+    /// 建表语句中的列定义
+    fn to_sql(&self) -> String {
+        let mut sql = format!("{} {}", identifier(&self.name), self.data_type);
+        if let Some(default) = &self.default {
+            sql.push_str(&format!(" DEFAULT {}", default));
+        }
+        if !self.nullable {
+            sql.push_str(" NOT NULL");
+        }
+        sql
+    }
+}
+
+/// 表的结构
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableDef {
+    pub name: String,
+    pub columns: Vec<ColumnDef>,
+    pub constraints: Vec<String>, // 主键、唯一、检查和外键约束的定义，非空约束记录在列中
+}
+
+/// 一个模式中的对象
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaSnapshot {
+    pub tables: Vec<TableDef>,
+    pub views: BTreeMap<String, String>,  // 视图名和查询
+    pub macros: BTreeMap<String, String>, // 宏名和宏名之后的定义，如 `(a) AS a + 1`
+}
+
+impl SchemaSnapshot {
+    /// 读取 `database.schema` 中的对象
+    pub fn read(
+        manager: &DatabaseManager,
+        database: &str,
+        schema: &str,
+    ) -> Result<Self, DatabaseError> {
+        let params = QueryParams::Positional(vec![
+            Value::Text(database.to_string()),
+            Value::Text(schema.to_string()),
+        ]);
+        let text = |value: &Value| match value {
+            Value::Null => None,
+            value => Some(format_value(value)),
+        };
+        let mut snapshot = Self::default();
+
+        let columns = manager.fetch(
+            "SELECT table_name, column_name, data_type, is_nullable, column_default
+             FROM duckdb_columns()
+             WHERE database_name = $1 AND schema_name = $2 AND NOT internal
+               AND table_name IN (
+                   SELECT table_name FROM duckdb_tables()
+                   WHERE database_name = $1 AND schema_name = $2
+               )
+             ORDER BY table_name, column_index",
+            &params,
+            usize::MAX,
+        )?;
+        for values in columns.rows {
+            let table = format_value(&values[0]);
+            if snapshot.tables.last().is_none_or(|t| t.name != table) {
+                snapshot.tables.push(TableDef {
+                    name: table,
+                    ..TableDef::default()
+                });
+            }
+            if let Some(table) = snapshot.tables.last_mut() {
+                table.columns.push(ColumnDef {
+                    name: format_value(&values[1]),
+                    data_type: format_value(&values[2]),
+                    nullable: matches!(values[3], Value::Boolean(true)),
+                    default: text(&values[4]),
+                });
+            }
+        }
+
+        let constraints = manager.fetch(
+            "SELECT table_name, constraint_text
+             FROM duckdb_constraints()
+             WHERE database_name = $1 AND schema_name = $2 AND constraint_type <> 'NOT NULL'
+             ORDER BY table_name, constraint_index",
+            &params,
+            usize::MAX,
+        )?;
+        for values in constraints.rows {
+            let name = format_value(&values[0]);
+            if let Some(table) = snapshot.tables.iter_mut().find(|t| t.name == name) {
+                table.constraints.push(format_value(&values[1]));
+            }
+        }
+
+        let views = manager.fetch(
+            "SELECT view_name, sql
+             FROM duckdb_views()
+             WHERE database_name = $1 AND schema_name = $2 AND NOT internal",
+            &params,
+            usize::MAX,
+        )?;
+        for values in views.rows {
+            let sql = format_value(&values[1]);
+            let body = VIEW_BODY
+                .captures(&sql)
+                .map_or(sql.clone(), |c| c[1].to_string());
+            snapshot.views.insert(format_value(&values[0]), body);
+        }
+
+        // 同名的宏可以有多个重载
+        let macros = manager.fetch(
+            "SELECT function_name, function_type, parameters, macro_definition
+             FROM duckdb_functions()
+             WHERE database_name = $1 AND schema_name = $2 AND NOT internal
+               AND function_type IN ('macro', 'table_macro')
+             ORDER BY function_name, function_oid",
+            &params,
+            usize::MAX,
+        )?;
+        for values in macros.rows {
+            let definition = macro_definition(&values[1], &values[2], &values[3]);
+            snapshot
+                .macros
+                .entry(format_value(&values[0]))
+                .and_modify(|existing| {
+                    existing.push_str(", ");
+                    existing.push_str(&definition);
+                })
+                .or_insert(definition);
+        }
+
+        Ok(snapshot)
+    }
+}
+
+/// 宏名之后的定义，如 `(a) AS a + 1`
+///
+/// 参数为 `duckdb_functions()` 中的 `function_type`、`parameters` 和 `macro_definition`。
+pub fn macro_definition(function_type: &Value, parameters: &Value, definition: &Value) -> String {
+    let parameters = match parameters {
+        Value::List(items) => items.iter().map(format_value).collect::<Vec<_>>(),
+        _ => Vec::new(),
+    };
+    let table = if format_value(function_type) == "table_macro" {
+        "TABLE "
+    } else {
+        ""
+    };
+    format!(
+        "({}) AS {}{}",
+        parameters.join(", "),
+        table,
+        format_value(definition)
+    )
+}
+
+/// 结构的差异，描述把目标改为与参照一致需要的修改
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaChange {
+    DropView(String),
+    DropMacro(String),
+    DropTable(String),
+    CreateTable(TableDef),
+    AddColumn(String, ColumnDef),
+    DropColumn(String, String),
+    AlterColumn {
+        table: String,
+        from: ColumnDef,
+        to: ColumnDef,
+    },
+    Constraints {
+        table: String,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    CreateView(String, String),
+    ReplaceView(String, String),
+    CreateMacro(String, String),
+    ReplaceMacro(String, String),
+}
+
+impl SchemaChange {
+    /// 差异的符号：`+` 为新建，`-` 为删除，`~` 为修改
+    fn kind(&self) -> char {
+        match self {
+            SchemaChange::DropView(_)
+            | SchemaChange::DropMacro(_)
+            | SchemaChange::DropTable(_)
+            | SchemaChange::DropColumn(..) => '-',
+            SchemaChange::CreateTable(_)
+            | SchemaChange::AddColumn(..)
+            | SchemaChange::CreateView(..)
+            | SchemaChange::CreateMacro(..) => '+',
+            _ => '~',
+        }
+    }
+
+    /// 差异的说明
+    pub fn describe(&self) -> String {
+        match self {
+            SchemaChange::DropView(name) => tr_args("schema.drop_view", &[("name", name)]),
+            SchemaChange::DropMacro(name) => tr_args("schema.drop_macro", &[("name", name)]),
+            SchemaChange::DropTable(name) => tr_args("schema.drop_table", &[("name", name)]),
+            SchemaChange::CreateTable(table) => {
+                tr_args("schema.create_table", &[("name", &table.name)])
+            }
+            SchemaChange::AddColumn(table, column) => tr_args(
+                "schema.add_column",
+                &[
+                    ("name", &format!("{}.{}", table, column.name)),
+                    ("type", &column.data_type),
+                ],
+            ),
+            SchemaChange::DropColumn(table, column) => tr_args(
+                "schema.drop_column",
+                &[("name", &format!("{}.{}", table, column))],
+            ),
+            SchemaChange::AlterColumn { table, from, to } => {
+                let mut details = Vec::new();
+                if from.data_type != to.data_type {
+                    details.push(format!("{} → {}", from.data_type, to.data_type));
+                }
+                if from.nullable != to.nullable {
+                    details.push(if to.nullable { "NULL" } else { "NOT NULL" }.to_string());
+                }
+                if from.default != to.default {
+                    let default = |d: &Option<String>| d.clone().unwrap_or_else(|| "-".into());
+                    details.push(format!(
+                        "DEFAULT {} → {}",
+                        default(&from.default),
+                        default(&to.default)
+                    ));
+                }
+                tr_args(
+                    "schema.alter_column",
+                    &[
+                        ("name", &format!("{}.{}", table, to.name)),
+                        ("details", &details.join(", ")),
+                    ],
+                )
+            }
+            SchemaChange::Constraints {
+                table,
+                added,
+                removed,
+            } => {
+                let changes: Vec<String> = added
+                    .iter()
+                    .map(|c| format!("+ {}", c))
+                    .chain(removed.iter().map(|c| format!("- {}", c)))
+                    .collect();
+                tr_args(
+                    "schema.constraints",
+                    &[("name", table), ("details", &changes.join(", "))],
+                )
+            }
+            SchemaChange::CreateView(name, _) => tr_args("schema.create_view", &[("name", name)]),
+            SchemaChange::ReplaceView(name, _) => tr_args("schema.replace_view", &[("name", name)]),
+            SchemaChange::CreateMacro(name, _) => tr_args("schema.create_macro", &[("name", name)]),
+            SchemaChange::ReplaceMacro(name, _) => {
+                tr_args("schema.replace_macro", &[("name", name)])
+            }
+        }
+    }
+
+    /// 迁移语句，`prefix` 为目标模式的限定名，如 `main.`
+    ///
+    /// DuckDB 不能修改已有表的约束，约束的差异写成注释。
+    fn statements(&self, prefix: &str) -> Vec<String> {
+        let name = |name: &str| format!("{}{}", prefix, identifier(name));
+        let alter = |table: &str, action: String| format!("ALTER TABLE {} {}", name(table), action);
+        match self {
+            SchemaChange::DropView(view) => vec![format!("DROP VIEW {}", name(view))],
+            SchemaChange::DropMacro(function) => vec![format!("DROP MACRO {}", name(function))],
+            SchemaChange::DropTable(table) => vec![format!("DROP TABLE {}", name(table))],
+            SchemaChange::CreateTable(table) => {
+                let definitions: Vec<String> = table
+                    .columns
+                    .iter()
+                    .map(ColumnDef::to_sql)
+                    .chain(table.constraints.iter().cloned())
+                    .map(|definition| format!("    {}", definition))
+                    .collect();
+                vec![format!(
+                    "CREATE TABLE {} (\n{}\n)",
+                    name(&table.name),
+                    definitions.join(",\n")
+                )]
+            }
+            // 新增列时不能带约束，非空约束单独设置
+            SchemaChange::AddColumn(table, column) => {
+                let nullable = ColumnDef {
+                    nullable: true,
+                    ..column.clone()
+                };
+                let mut statements =
+                    vec![alter(table, format!("ADD COLUMN {}", nullable.to_sql()))];
+                if !column.nullable {
+                    statements.push(alter(
+                        table,
+                        format!("ALTER COLUMN {} SET NOT NULL", identifier(&column.name)),
+                    ));
+                }
+                statements
+            }
+            SchemaChange::DropColumn(table, column) => {
+                vec![alter(table, format!("DROP COLUMN {}", identifier(column)))]
+            }
+            SchemaChange::AlterColumn { table, from, to } => {
+                let column = identifier(&to.name);
+                let mut statements = Vec::new();
+                if from.data_type != to.data_type {
+                    statements.push(alter(
+                        table,
+                        format!("ALTER COLUMN {} SET DATA TYPE {}", column, to.data_type),
+                    ));
+                }
+                if from.default != to.default {
+                    let action = match &to.default {
+                        Some(default) => format!("SET DEFAULT {}", default),
+                        None => "DROP DEFAULT".to_string(),
+                    };
+                    statements.push(alter(table, format!("ALTER COLUMN {} {}", column, action)));
+                }
+                if from.nullable != to.nullable {
+                    let action = if to.nullable { "DROP" } else { "SET" };
+                    statements.push(alter(
+                        table,
+                        format!("ALTER COLUMN {} {} NOT NULL", column, action),
+                    ));
+                }
+                statements
+            }
+            SchemaChange::Constraints {
+                table,
+                added,
+                removed,
+            } => {
+                let mut lines = vec![format!(
+                    "-- {}",
+                    tr_args("schema.rebuild_required", &[("name", &name(table))])
+                )];
+                lines.extend(added.iter().map(|c| format!("--   + {}", c)));
+                lines.extend(removed.iter().map(|c| format!("--   - {}", c)));
+                vec![lines.join("\n")]
+            }
+            SchemaChange::CreateView(view, body) | SchemaChange::ReplaceView(view, body) => {
+                vec![format!("CREATE OR REPLACE VIEW {} AS {}", name(view), body)]
+            }
+            SchemaChange::CreateMacro(function, definition)
+            | SchemaChange::ReplaceMacro(function, definition) => {
+                vec![format!(
+                    "CREATE OR REPLACE MACRO {}{}",
+                    name(function),
+                    definition
+                )]
+            }
+        }
+    }
+}
+
+/// 比较目标和参照，按迁移脚本的执行顺序列出差异
+///
+/// 先删除视图和宏，再修改表，最后重新创建视图和宏，避免依赖关系阻止修改。
+pub fn compare(target: &SchemaSnapshot, reference: &SchemaSnapshot) -> Vec<SchemaChange> {
+    let mut drops = Vec::new();
+    let mut tables = Vec::new();
+    let mut objects = Vec::new();
+
+    for (name, body) in &target.views {
+        match reference.views.get(name) {
+            None => drops.push(SchemaChange::DropView(name.clone())),
+            Some(new) if new != body => {
+                objects.push(SchemaChange::ReplaceView(name.clone(), new.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, definition) in &target.macros {
+        match reference.macros.get(name) {
+            None => drops.push(SchemaChange::DropMacro(name.clone())),
+            Some(new) if new != definition => {
+                objects.push(SchemaChange::ReplaceMacro(name.clone(), new.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    for table in &target.tables {
+        if !reference.tables.iter().any(|t| t.name == table.name) {
+            drops.push(SchemaChange::DropTable(table.name.clone()));
+        }
+    }
+
+    for table in &reference.tables {
+        let Some(old) = target.tables.iter().find(|t| t.name == table.name) else {
+            tables.push(SchemaChange::CreateTable(table.clone()));
+            continue;
+        };
+        for column in &old.columns {
+            if !table.columns.iter().any(|c| c.name == column.name) {
+                tables.push(SchemaChange::DropColumn(
+                    table.name.clone(),
+                    column.name.clone(),
+                ));
+            }
+        }
+        for column in &table.columns {
+            match old.columns.iter().find(|c| c.name == column.name) {
+                None => tables.push(SchemaChange::AddColumn(table.name.clone(), column.clone())),
+                Some(from) if from != column => tables.push(SchemaChange::AlterColumn {
+                    table: table.name.clone(),
+                    from: from.clone(),
+                    to: column.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        let added: Vec<String> = table
+            .constraints
+            .iter()
+            .filter(|c| !old.constraints.contains(c))
+            .cloned()
+            .collect();
+        let removed: Vec<String> = old
+            .constraints
+            .iter()
+            .filter(|c| !table.constraints.contains(c))
+            .cloned()
+            .collect();
+        if !added.is_empty() || !removed.is_empty() {
+            tables.push(SchemaChange::Constraints {
+                table: table.name.clone(),
+                added,
+                removed,
+            });
+        }
+    }
+
+    for (name, body) in &reference.views {
+        if !target.views.contains_key(name) {
+            objects.push(SchemaChange::CreateView(name.clone(), body.clone()));
+        }
+    }
+    for (name, definition) in &reference.macros {
+        if !target.macros.contains_key(name) {
+            objects.push(SchemaChange::CreateMacro(name.clone(), definition.clone()));
+        }
+    }
+
+    drops.into_iter().chain(tables).chain(objects).collect()
+}
+
+/// 迁移脚本，在目标所在的连接中执行
+pub fn migration_script(changes: &[SchemaChange], database: &str, schema: &str) -> String {
+    let prefix = format!("{}.{}.", identifier(database), identifier(schema));
+    let mut lines = vec![format!(
+        "-- {}",
+        tr_args(
+            "schema.script_header",
+            &[("name", &format!("{}.{}", database, schema))]
+        )
+    )];
+    for change in changes {
+        lines.push(String::new());
+        lines.push(format!("-- {}", change.describe()));
+        for statement in change.statements(&prefix) {
+            if statement.starts_with("--") {
+                lines.push(statement);
+            } else {
+                lines.push(format!("{};", statement));
+            }
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// 对比的一侧
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaSide {
+    pub connection: Option<String>,             // 数据库路径
+    pub schema: Option<(String, String)>,       // 数据库名和模式名
+    pub schemas: Option<Vec<(String, String)>>, // 连接中的模式，`None` 表示需要重新读取
+}
+
+impl SchemaSide {
+    /// 读取连接中的模式，默认选中第一个 `main`
+    fn load_schemas(&mut self, app: &MyApp) {
+        let Some(connection) = app
+            .connections
+            .iter()
+            .find(|c| Some(&c.path) == self.connection.as_ref())
+        else {
+            self.schemas = Some(Vec::new());
+            return;
+        };
+        let schemas: Vec<(String, String)> = connection
+            .manager
+            .fetch(
+                "SELECT database_name, schema_name FROM duckdb_schemas()
+                 WHERE NOT internal ORDER BY database_name, schema_name",
+                &QueryParams::None,
+                usize::MAX,
+            )
+            .map(|result| {
+                result
+                    .rows
+                    .iter()
+                    .map(|values| (format_value(&values[0]), format_value(&values[1])))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if self.schema.as_ref().is_none_or(|s| !schemas.contains(s)) {
+            self.schema = schemas
+                .iter()
+                .find(|(_, schema)| schema == "main")
+                .or(schemas.first())
+                .cloned();
+        }
+        self.schemas = Some(schemas);
+    }
+
+    /// 读取选中的模式
+    fn snapshot(&self, app: &MyApp) -> Result<SchemaSnapshot, String> {
+        let connection = app
+            .connections
+            .iter()
+            .find(|c| Some(&c.path) == self.connection.as_ref())
+            .ok_or_else(|| tr("diff.no_connection").to_string())?;
+        let (database, schema) = self
+            .schema
+            .as_ref()
+            .ok_or_else(|| tr("schema.no_schema").to_string())?;
+        SchemaSnapshot::read(&connection.manager, database, schema).map_err(|e| e.to_string())
+    }
+}
+
+/// 结构对比窗口
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaCompare {
+    pub target: SchemaSide,    // 要修改的一侧，迁移脚本在这里执行
+    pub reference: SchemaSide, // 期望的结构
+    pub changes: Option<Vec<SchemaChange>>,
+    pub error: Option<String>,
+}
+
+impl SchemaCompare {
+    /// 两侧都使用当前的连接
+    pub fn new(connection: Option<String>) -> Self {
+        let side = SchemaSide {
+            connection,
+            ..SchemaSide::default()
+        };
+        Self {
+            target: side.clone(),
+            reference: side,
+            ..Self::default()
+        }
+    }
+
+    /// 读取两侧的结构并比较
+    fn run(&mut self, app: &MyApp) {
+        let snapshots = self
+            .target
+            .snapshot(app)
+            .and_then(|target| Ok((target, self.reference.snapshot(app)?)));
+        match snapshots {
+            Ok((target, reference)) => {
+                self.changes = Some(compare(&target, &reference));
+                self.error = None;
+            }
+            Err(e) => {
+                self.changes = None;
+                self.error = Some(e);
+            }
+        }
+    }
+
+    /// 渲染窗口
+    pub fn show(app: &mut MyApp, ctx: &egui::Context) {
+        let Some(mut compare) = app.schema_compare.take() else {
+            return;
+        };
+        for side in [&mut compare.target, &mut compare.reference] {
+            if side.schemas.is_none() {
+                side.load_schemas(app);
+            }
+        }
+
+        let mut is_open = true;
+        let mut run = false;
+        let mut open_script = false;
+        let connections: Vec<(String, String)> = app
+            .connections
+            .iter()
+            .map(|c| (c.path.clone(), c.name.clone()))
+            .collect();
+        egui::Window::new(tr("schema.title"))
+            .id(egui::Id::new("schema_compare"))
+            .open(&mut is_open)
+            .default_size([720.0, 520.0])
+            .show(ctx, |ui| {
+                egui::Grid::new("schema_compare_sides")
+                    .num_columns(3)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        for (side, label, hint) in [
+                            (&mut compare.target, "schema.target", "schema.target_hint"),
+                            (
+                                &mut compare.reference,
+                                "schema.reference",
+                                "schema.reference_hint",
+                            ),
+                        ] {
+                            ui.strong(tr(label)).on_hover_text(tr(hint));
+                            side_selector(ui, side, label, &connections);
+                            ui.end_row();
+                        }
+                    });
+                ui.horizontal(|ui| {
+                    if ui.button(tr("schema.compare")).clicked() {
+                        run = true;
+                    }
+                    let has_changes = compare.changes.as_ref().is_some_and(|c| !c.is_empty());
+                    if ui
+                        .add_enabled(has_changes, egui::Button::new(tr("schema.open_script")))
+                        .clicked()
+                    {
+                        open_script = true;
+                    }
+                });
+                if let Some(error) = &compare.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                if let Some(changes) = &compare.changes {
+                    ui.separator();
+                    if changes.is_empty() {
+                        ui.label(tr("schema.identical"));
+                    }
+                    egui::ScrollArea::both()
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| {
+                            for change in changes {
+                                let color = match change.kind() {
+                                    '+' => CREATE_COLOR,
+                                    '-' => ui.visuals().error_fg_color,
+                                    _ => ui.visuals().warn_fg_color,
+                                };
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} {}",
+                                        change.kind(),
+                                        change.describe()
+                                    ))
+                                    .monospace()
+                                    .color(color),
+                                );
+                            }
+                        });
+                }
+            });
+
+        if run {
+            compare.run(app);
+        }
+        if open_script
+            && let (Some(changes), Some((database, schema))) =
+                (&compare.changes, &compare.target.schema)
+        {
+            let script = migration_script(changes, database, schema);
+            app.open_query_tab(script, compare.target.connection.clone());
+        }
+        if is_open {
+            app.schema_compare = Some(compare);
+        }
+    }
+}
+
+/// 一侧的连接和模式
+fn side_selector(
+    ui: &mut egui::Ui,
+    side: &mut SchemaSide,
+    label: &str,
+    connections: &[(String, String)],
+) {
+    let selected = connections
+        .iter()
+        .find(|(path, _)| Some(path) == side.connection.as_ref())
+        .map_or_else(
+            || tr("diff.no_connection").to_string(),
+            |(_, name)| name.clone(),
+        );
+    egui::ComboBox::from_id_salt(("schema_connection", label))
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            for (path, name) in connections {
+                let response = ui.selectable_value(&mut side.connection, Some(path.clone()), name);
+                if response.changed() {
+                    side.schemas = None;
+                }
+            }
+        });
+
+    let selected = side.schema.as_ref().map_or_else(
+        || tr("schema.no_schema").to_string(),
+        |(d, s)| format!("{}.{}", d, s),
+    );
+    egui::ComboBox::from_id_salt(("schema_name", label))
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            for schema in side.schemas.iter().flatten() {
+                let text = format!("{}.{}", schema.0, schema.1);
+                ui.selectable_value(&mut side.schema, Some(schema.clone()), text);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migration_script_makes_schemas_identical() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        for sql in [
+            "CREATE SCHEMA old",
+            "CREATE SCHEMA new",
+            "CREATE TABLE old.t (id INTEGER, name VARCHAR)",
+            "CREATE TABLE old.gone (x INTEGER)",
+            "CREATE VIEW old.v AS SELECT 1 AS one",
+            "CREATE MACRO old.m(a) AS a + 1",
+            "CREATE TABLE new.t (id BIGINT, name VARCHAR NOT NULL DEFAULT 'n', extra DOUBLE)",
+            "CREATE TABLE new.\"Items\" (k INTEGER PRIMARY KEY, CHECK (k > 0))",
+            "CREATE VIEW new.v AS SELECT 2 AS two",
+            "CREATE VIEW new.w AS SELECT 3 AS three",
+            "CREATE MACRO new.m(a) AS a + 2",
+            "CREATE MACRO new.tm(x) AS TABLE SELECT x AS y",
+        ] {
+            manager.execute_query(sql).unwrap();
+        }
+
+        let read = |schema: &str| SchemaSnapshot::read(&manager, "memory", schema).unwrap();
+        let changes = compare(&read("old"), &read("new"));
+        assert_eq!(changes[0], SchemaChange::DropTable("gone".to_string()));
+        assert!(changes.iter().any(|c| matches!(
+            c,
+            SchemaChange::AlterColumn { to, .. } if to.name == "name" && !to.nullable
+        )));
+
+        let script = migration_script(&changes, "memory", "old");
+        assert!(script.contains("CREATE TABLE memory.old.\"Items\" (\n    k INTEGER NOT NULL,"));
+        for statement in script.split(";\n") {
+            let statement: Vec<&str> = statement
+                .lines()
+                .filter(|line| !line.starts_with("--"))
+                .collect();
+            if !statement.is_empty() {
+                manager.execute_query(&statement.join("\n")).unwrap();
+            }
+        }
+        assert_eq!(compare(&read("old"), &read("new")), []);
+    }
+}
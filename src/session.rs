@@ -0,0 +1,149 @@
+//! 会话模块
+//! 保存和恢复工作区会话（资源管理器、编辑器标签页、连接、面板布局）
+
+use crate::config::AppConfig;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 编辑器标签页快照
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EditorTabState {
+    pub title: String,
+    pub file_path: Option<String>,  // 关联的文件路径
+    pub content: String,            // 编辑器内容（包括未保存的修改）
+    pub is_dirty: bool,             // 是否有未保存的修改
+    pub connection: Option<String>, // 关联的数据库路径
+}
+
+/// 面板布局
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    pub sidebar_visible: bool,
+    pub sidebar_width: f32,
+    pub status_bar_visible: bool,
+    pub history_visible: bool,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            sidebar_visible: true,
+            sidebar_width: 240.0,
+            status_bar_visible: true,
+            history_visible: false,
+        }
+    }
+}
+
+/// 工作区会话状态
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    pub explorer_roots: Vec<String>,        // 打开的资源管理器根目录
+    pub active_explorer_tab: Option<usize>, // 活动的资源管理器标签页
+    pub editor_tabs: Vec<EditorTabState>,   // 打开的编辑器标签页
+    pub active_editor_tab: Option<usize>,   // 活动的编辑器标签页
+    pub connections: Vec<String>,           // 活动的数据库连接
+    pub active_connection: Option<usize>,   // 当前选中的连接
+    pub panel_layout: PanelLayout,          // 面板布局
+}
+
+impl SessionState {
+    /// 会话文件路径（与配置文件位于同一目录）
+    pub fn session_path() -> PathBuf {
+        AppConfig::config_dir().join("session.json")
+    }
+
+    /// 从文件加载会话
+    pub fn load() -> Self {
+        Self::load_from(&Self::session_path())
+    }
+
+    /// 从指定路径加载会话，文件不存在或无法解析时返回空会话
+    pub fn load_from(session_path: &Path) -> Self {
+        if session_path.exists() {
+            match fs::read_to_string(session_path) {
+                Ok(content) => match serde_json::from_str(&content) {
+                    Ok(session) => {
+                        println!("会话恢复成功");
+                        return session;
+                    }
+                    Err(e) => {
+                        eprintln!("会话文件解析错误: {}", e);
+                    }
+                },
+                Err(e) => {
+                    eprintln!("读取会话文件失败: {}", e);
+                }
+            }
+        }
+
+        Self::default()
+    }
+
+    /// 保存会话到文件
+    ///
+    /// 先写入临时文件再重命名，避免崩溃时留下写了一半的会话文件。
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.save_to(&Self::session_path())
+    }
+
+    /// 保存会话到指定路径
+    pub fn save_to(&self, session_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(dir) = session_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        let tmp_path = session_path.with_extension("json.tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, session_path)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_round_trips_through_file() {
+        let dir = std::env::temp_dir().join(format!("e-session-test-{}", std::process::id()));
+        let path = dir.join("session.json");
+        let session = SessionState {
+            explorer_roots: vec!["/tmp".to_string()],
+            active_explorer_tab: Some(0),
+            editor_tabs: vec![
+                EditorTabState {
+                    title: "a.sql".to_string(),
+                    file_path: Some("/tmp/a.sql".to_string()),
+                    content: "SELECT 1;".to_string(),
+                    is_dirty: false,
+                    connection: Some(":memory:".to_string()),
+                },
+                EditorTabState {
+                    title: "查询 2".to_string(),
+                    content: "SELECT 2;".to_string(),
+                    is_dirty: true,
+                    ..EditorTabState::default()
+                },
+            ],
+            active_editor_tab: Some(1),
+            connections: vec![":memory:".to_string()],
+            active_connection: Some(0),
+            panel_layout: PanelLayout {
+                sidebar_width: 300.0,
+                ..PanelLayout::default()
+            },
+        };
+        session.save_to(&path).unwrap();
+        assert_eq!(SessionState::load_from(&path), session);
+
+        fs::write(&path, "{ not json").unwrap();
+        assert_eq!(SessionState::load_from(&path), SessionState::default());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,462 @@
+//! 设置模块
+//! 编辑 AppConfig 的设置面板
+
+use crate::app::{MenuType, MyApp};
+use crate::config::{AppConfig, FONT_SIZE_RANGE, MIN_AUTOSAVE_INTERVAL, SUPPORTED_LANGUAGES};
+use crate::i18n::tr;
+use crate::theme::Theme;
+use eframe::egui;
+
+/// 设置页面
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SettingsPage {
+    #[default]
+    Appearance,
+    Editor,
+    Grid,
+    Connections,
+    Files,
+    Keymap,
+}
+
+impl SettingsPage {
+    pub const ALL: [SettingsPage; 6] = [
+        SettingsPage::Appearance,
+        SettingsPage::Editor,
+        SettingsPage::Grid,
+        SettingsPage::Connections,
+        SettingsPage::Files,
+        SettingsPage::Keymap,
+    ];
+
+    /// 页面名称的文本键
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingsPage::Appearance => "settings.page.appearance",
+            SettingsPage::Editor => "settings.page.editor",
+            SettingsPage::Grid => "settings.page.grid",
+            SettingsPage::Connections => "settings.page.connections",
+            SettingsPage::Files => "settings.page.files",
+            SettingsPage::Keymap => "settings.page.keymap",
+        }
+    }
+}
+
+/// 设置面板状态
+///
+/// 面板编辑的是配置的工作副本，只有点击“应用”后才会整体替换当前配置。
+#[derive(Default)]
+pub struct SettingsState {
+    pub is_open: bool,
+    pub page: SettingsPage,
+    pub draft: AppConfig, // 配置的工作副本
+}
+
+impl SettingsState {
+    /// 打开设置面板，并以当前配置作为工作副本
+    pub fn open(&mut self, config: &AppConfig) {
+        self.is_open = true;
+        self.draft = config.clone();
+    }
+
+    /// 渲染设置窗口
+    pub fn show(app: &mut MyApp, ctx: &egui::Context) {
+        if !app.settings.is_open {
+            return;
+        }
+
+        let mut settings = std::mem::take(&mut app.settings);
+        // 窗口尺寸不在设置中编辑，调整窗口大小时跟随当前值，不算作修改
+        settings.draft.window_width = app.config.window_width;
+        settings.draft.window_height = app.config.window_height;
+        settings.draft.is_maximized = app.config.is_maximized;
+        let mut is_open = true;
+        let defaults = AppConfig::default();
+        let issues = settings.draft.validate();
+        let has_changes = settings.draft != app.config;
+        let mut apply = false;
+        let mut revert = false;
+
+        egui::Window::new(tr("settings.title"))
+            .id(egui::Id::new("settings_window"))
+            .open(&mut is_open)
+            .default_size([640.0, 420.0])
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.horizontal_top(|ui| {
+                    // 左侧分类列表
+                    ui.vertical(|ui| {
+                        ui.set_width(110.0);
+                        for page in SettingsPage::ALL {
+                            if ui
+                                .selectable_label(settings.page == page, tr(page.label()))
+                                .clicked()
+                            {
+                                settings.page = page;
+                            }
+                        }
+                    });
+                    ui.separator();
+
+                    // 右侧设置内容
+                    ui.vertical(|ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(340.0)
+                            .show(ui, |ui| {
+                                egui::Grid::new("settings_grid")
+                                    .num_columns(3)
+                                    .spacing([12.0, 6.0])
+                                    .show(ui, |ui| {
+                                        Self::render_page(ui, &mut settings, &defaults, app)
+                                    });
+                            });
+                    });
+                });
+
+                ui.separator();
+                for issue in &issues {
+                    ui.colored_label(ui.visuals().error_fg_color, issue.to_string());
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            has_changes && issues.is_empty(),
+                            egui::Button::new(tr("settings.apply")),
+                        )
+                        .clicked()
+                    {
+                        apply = true;
+                    }
+                    if ui
+                        .add_enabled(has_changes, egui::Button::new(tr("settings.revert")))
+                        .clicked()
+                    {
+                        revert = true;
+                    }
+                    if ui.button(tr("settings.reset_all")).clicked() {
+                        settings.draft = AppConfig {
+                            recent_files: settings.draft.recent_files.clone(),
+                            ..defaults.clone()
+                        };
+                    }
+                });
+            });
+
+        if apply {
+            app.apply_config(settings.draft.clone());
+            app.save_workspace();
+            app.set_status_message(tr("status.settings_applied").to_owned());
+        }
+        if revert {
+            settings.draft = app.config.clone();
+        }
+
+        settings.is_open = is_open;
+        app.settings = settings;
+    }
+
+    /// 渲染当前分类的设置项
+    fn render_page(
+        ui: &mut egui::Ui,
+        settings: &mut SettingsState,
+        defaults: &AppConfig,
+        app: &MyApp,
+    ) {
+        let draft = &mut settings.draft;
+        match settings.page {
+            SettingsPage::Appearance => {
+                // 跟随系统时明暗由系统决定
+                let follow_system = draft.theme.follow_system;
+                setting_row(
+                    ui,
+                    "settings.dark_mode",
+                    &mut draft.is_dark_mode,
+                    &defaults.is_dark_mode,
+                    |ui, v| {
+                        ui.add_enabled(!follow_system, egui::Checkbox::new(v, ""))
+                            .on_disabled_hover_text(tr("settings.dark_mode_follows_system"));
+                    },
+                );
+                setting_row(
+                    ui,
+                    "settings.follow_system_theme",
+                    &mut draft.theme.follow_system,
+                    &defaults.theme.follow_system,
+                    |ui, v| {
+                        ui.checkbox(v, "");
+                    },
+                );
+                setting_row(
+                    ui,
+                    "settings.light_theme",
+                    &mut draft.theme.light,
+                    &defaults.theme.light,
+                    |ui, v| theme_picker(ui, "settings_light_theme", v, &app.custom_themes),
+                );
+                setting_row(
+                    ui,
+                    "settings.dark_theme",
+                    &mut draft.theme.dark,
+                    &defaults.theme.dark,
+                    |ui, v| theme_picker(ui, "settings_dark_theme", v, &app.custom_themes),
+                );
+                ui.label(tr("settings.themes_dir"));
+                ui.label(
+                    egui::RichText::new(crate::theme::themes_dir().display().to_string()).weak(),
+                );
+                ui.end_row();
+                setting_row(
+                    ui,
+                    "settings.font_size",
+                    &mut draft.font_size,
+                    &defaults.font_size,
+                    |ui, v| {
+                        ui.add(egui::DragValue::new(v).range(FONT_SIZE_RANGE).speed(0.5));
+                    },
+                );
+                setting_row(
+                    ui,
+                    "settings.ui_font",
+                    &mut draft.ui_font,
+                    &defaults.ui_font,
+                    |ui, v| font_picker(ui, "settings_ui_font", v),
+                );
+                setting_row(
+                    ui,
+                    "settings.mono_font",
+                    &mut draft.mono_font,
+                    &defaults.mono_font,
+                    |ui, v| font_picker(ui, "settings_mono_font", v),
+                );
+                setting_row(
+                    ui,
+                    "settings.language",
+                    &mut draft.language,
+                    &defaults.language,
+                    |ui, v| {
+                        egui::ComboBox::from_id_salt("settings_language")
+                            .selected_text(v.as_str())
+                            .show_ui(ui, |ui| {
+                                for language in SUPPORTED_LANGUAGES {
+                                    ui.selectable_value(v, language.to_string(), *language);
+                                }
+                            });
+                    },
+                );
+            }
+            SettingsPage::Editor => {
+                setting_row(
+                    ui,
+                    "settings.word_wrap",
+                    &mut draft.editor.word_wrap,
+                    &defaults.editor.word_wrap,
+                    |ui, v| {
+                        ui.checkbox(v, "");
+                    },
+                );
+                setting_row(
+                    ui,
+                    "settings.autosave_interval",
+                    &mut draft.editor.autosave_interval_secs,
+                    &defaults.editor.autosave_interval_secs,
+                    |ui, v| {
+                        ui.add(egui::DragValue::new(v).range(MIN_AUTOSAVE_INTERVAL..=3600));
+                    },
+                );
+            }
+            SettingsPage::Grid => {
+                setting_row(
+                    ui,
+                    "settings.max_rows",
+                    &mut draft.grid.max_rows,
+                    &defaults.grid.max_rows,
+                    |ui, v| {
+                        ui.add(egui::DragValue::new(v).range(1..=1_000_000).speed(100));
+                    },
+                );
+                setting_row(
+                    ui,
+                    "settings.null_display",
+                    &mut draft.grid.null_display,
+                    &defaults.grid.null_display,
+                    |ui, v| {
+                        ui.text_edit_singleline(v);
+                    },
+                );
+                setting_row(
+                    ui,
+                    "settings.column_max_width",
+                    &mut draft.grid.column_max_width,
+                    &defaults.grid.column_max_width,
+                    |ui, v| {
+                        ui.add(egui::DragValue::new(v).range(40.0..=2000.0));
+                    },
+                );
+            }
+            SettingsPage::Connections => {
+                setting_row(
+                    ui,
+                    "settings.restore_connections",
+                    &mut draft.connections.restore_on_startup,
+                    &defaults.connections.restore_on_startup,
+                    |ui, v| {
+                        ui.checkbox(v, "");
+                    },
+                );
+                setting_row(
+                    ui,
+                    "settings.read_only",
+                    &mut draft.connections.read_only,
+                    &defaults.connections.read_only,
+                    |ui, v| {
+                        ui.checkbox(v, "");
+                    },
+                );
+                setting_row(
+                    ui,
+                    "settings.profiling",
+                    &mut draft.connections.profiling,
+                    &defaults.connections.profiling,
+                    |ui, v| {
+                        ui.checkbox(v, "");
+                    },
+                );
+            }
+            SettingsPage::Files => {
+                setting_row(
+                    ui,
+                    "settings.show_hidden_files",
+                    &mut draft.files.show_hidden_files,
+                    &defaults.files.show_hidden_files,
+                    |ui, v| {
+                        ui.checkbox(v, "");
+                    },
+                );
+                setting_row(
+                    ui,
+                    "settings.max_recent_files",
+                    &mut draft.files.max_recent_files,
+                    &defaults.files.max_recent_files,
+                    |ui, v| {
+                        ui.add(egui::DragValue::new(v).range(0..=50));
+                    },
+                );
+                setting_row(
+                    ui,
+                    "settings.library_dir",
+                    &mut draft.files.library_dir,
+                    &defaults.files.library_dir,
+                    |ui, v| {
+                        ui.add(
+                            egui::TextEdit::singleline(v)
+                                .hint_text(crate::library::Library::dir("").display().to_string()),
+                        );
+                    },
+                );
+            }
+            SettingsPage::Keymap => {
+                let items = MenuType::ALL
+                    .iter()
+                    .filter_map(|menu_type| app.menu_data.get(menu_type))
+                    .flatten();
+                for item in items {
+                    if item.action == "separator" {
+                        continue;
+                    }
+
+                    let default = item.default_shortcut.clone().unwrap_or_default();
+                    let mut shortcut = draft
+                        .keybindings
+                        .get(&item.action)
+                        .cloned()
+                        .unwrap_or_else(|| default.clone());
+
+                    setting_row(ui, &item.label, &mut shortcut, &default, |ui, v| {
+                        ui.add(egui::TextEdit::singleline(v).desired_width(140.0));
+                    });
+
+                    // 与默认值相同的快捷键不写入配置
+                    if shortcut == default {
+                        draft.keybindings.remove(&item.action);
+                    } else {
+                        draft.keybindings.insert(item.action.clone(), shortcut);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 主题选择下拉框，空字符串表示内置主题
+fn theme_picker(ui: &mut egui::Ui, id: &str, value: &mut String, themes: &[Theme]) {
+    let selected = if value.is_empty() {
+        tr("settings.theme_builtin").to_string()
+    } else {
+        value.clone()
+    };
+
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(selected)
+        .width(200.0)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(value, String::new(), tr("settings.theme_builtin"));
+            for theme in themes {
+                ui.selectable_value(value, theme.name.clone(), &theme.name);
+            }
+        });
+}
+
+/// 字体选择下拉框，空字符串表示自动选择
+fn font_picker(ui: &mut egui::Ui, id: &str, value: &mut String) {
+    let selected = crate::fonts::available_fonts()
+        .iter()
+        .find(|font| font.path.to_string_lossy() == value.as_str())
+        .map(|font| font.name.clone())
+        .unwrap_or_else(|| {
+            if value.is_empty() {
+                tr("settings.font_auto").to_string()
+            } else {
+                value.clone()
+            }
+        });
+
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(selected)
+        .width(200.0)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(value, String::new(), tr("settings.font_auto"));
+            for font in crate::fonts::available_fonts() {
+                let path = font.path.to_string_lossy().to_string();
+                ui.selectable_value(value, path.clone(), &font.name)
+                    .on_hover_text(path);
+            }
+        });
+}
+
+/// 渲染一行设置：名称（文本键）、编辑控件和“恢复默认”按钮
+///
+/// 与默认值不同的设置项名称前会显示 ● 标记。
+fn setting_row<T: PartialEq + Clone>(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: &mut T,
+    default: &T,
+    editor: impl FnOnce(&mut egui::Ui, &mut T),
+) {
+    let modified = value != default;
+    if modified {
+        ui.label(egui::RichText::new(format!("● {}", tr(label))).strong());
+    } else {
+        ui.label(tr(label));
+    }
+
+    editor(ui, value);
+
+    if ui
+        .add_enabled(modified, egui::Button::new(tr("settings.reset")).small())
+        .clicked()
+    {
+        *value = default.clone();
+    }
+    ui.end_row();
+}
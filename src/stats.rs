@@ -0,0 +1,393 @@
+//! 列统计模块
+//! 使用 DuckDB 的 SUMMARIZE 计算结果或表中各列的统计信息，并绘制小型直方图
+
+use crate::app::MyApp;
+use crate::database::{
+    DatabaseError, DatabaseManager, QueryParams, QueryResult, format_value, quote_identifier,
+};
+use crate::i18n::{tr, tr_args};
+use duckdb::types::Value;
+use eframe::egui;
+
+/// 数值列直方图的分组数
+const BINS: usize = 12;
+
+/// 非数值列显示的最常见值个数
+const TOP_VALUES: usize = 8;
+
+/// 数值类型的前缀，DECIMAL 带有精度
+const NUMERIC_TYPES: [&str; 13] = [
+    "TINYINT",
+    "SMALLINT",
+    "INTEGER",
+    "BIGINT",
+    "HUGEINT",
+    "UTINYINT",
+    "USMALLINT",
+    "UINTEGER",
+    "UBIGINT",
+    "UHUGEINT",
+    "FLOAT",
+    "DOUBLE",
+    "DECIMAL",
+];
+
+/// 列的值分布
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Histogram {
+    #[default]
+    None,
+    /// 数值列按等宽区间分组，`start` 为第一组的下限
+    Bins {
+        start: f64,
+        width: f64,
+        counts: Vec<i64>,
+    },
+    /// 其他列最常见的值和出现次数
+    TopValues(Vec<(String, i64)>),
+}
+
+/// 一列的统计信息，值都是 SUMMARIZE 输出的文本
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnStats {
+    pub name: String,
+    pub data_type: String,
+    pub min: Option<String>,
+    pub max: Option<String>,
+    pub approx_unique: Option<i64>, // 近似的不同值个数
+    pub avg: Option<String>,
+    pub std: Option<String>,
+    pub quantiles: [Option<String>; 3], // 25%、50%、75% 分位数
+    pub count: i64,
+    pub null_percentage: f64,
+    pub histogram: Histogram,
+}
+
+impl ColumnStats {
+    /// 是否是数值列
+    fn is_numeric(&self) -> bool {
+        let data_type = self.data_type.to_uppercase();
+        NUMERIC_TYPES.iter().any(|t| data_type.starts_with(t))
+    }
+}
+
+/// 结果或表的列统计
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsReport {
+    pub title: String,
+    pub columns: Vec<ColumnStats>,
+}
+
+impl StatsReport {
+    /// 计算 `relation` 中各列的统计信息
+    ///
+    /// `relation` 是可以放在 FROM 后面的表名或带别名的子查询，`params` 为子查询的绑定参数。
+    /// 直方图查询失败时该列不显示分布。
+    pub fn compute(
+        manager: &DatabaseManager,
+        title: String,
+        relation: &str,
+        params: &QueryParams,
+    ) -> Result<Self, DatabaseError> {
+        let summary = manager.fetch(
+            &format!("SUMMARIZE SELECT * FROM {}", relation),
+            params,
+            usize::MAX,
+        )?;
+        let mut columns = read_summary(&summary);
+        for column in &mut columns {
+            column.histogram = histogram(manager, relation, params, column).unwrap_or_default();
+        }
+        Ok(Self { title, columns })
+    }
+
+    /// 子查询作为统计的数据来源
+    pub fn subquery(sql: &str) -> String {
+        // 原查询单独成行，末尾的行注释不会注释掉右括号
+        format!("(\n{}\n) AS source", sql.trim().trim_end_matches(';'))
+    }
+
+    /// 渲染统计窗口
+    pub fn show(app: &mut MyApp, ctx: &egui::Context) {
+        let Some(report) = &app.column_stats else {
+            return;
+        };
+
+        let mut is_open = true;
+        egui::Window::new(tr_args("stats.title", &[("name", &report.title)]))
+            .id(egui::Id::new("column_stats"))
+            .open(&mut is_open)
+            .default_size([900.0, 420.0])
+            .show(ctx, |ui| {
+                egui::ScrollArea::both().show(ui, |ui| {
+                    egui::Grid::new("column_stats_grid")
+                        .striped(true)
+                        .spacing([16.0, 4.0])
+                        .show(ui, |ui| stats_grid(ui, report));
+                });
+            });
+
+        if !is_open {
+            app.column_stats = None;
+        }
+    }
+}
+
+/// 读取 SUMMARIZE 的输出，每行是一列的统计信息
+fn read_summary(summary: &QueryResult) -> Vec<ColumnStats> {
+    let index = |name: &str| summary.columns.iter().position(|c| c == name);
+    let text = |values: &[Value], name: &str| {
+        index(name)
+            .map(|i| &values[i])
+            .filter(|value| !matches!(value, Value::Null))
+            .map(format_value)
+    };
+    let number = |values: &[Value], name: &str| text(values, name)?.parse::<f64>().ok();
+
+    summary
+        .rows
+        .iter()
+        .map(|values| ColumnStats {
+            name: text(values, "column_name").unwrap_or_default(),
+            data_type: text(values, "column_type").unwrap_or_default(),
+            min: text(values, "min"),
+            max: text(values, "max"),
+            approx_unique: number(values, "approx_unique").map(|n| n as i64),
+            avg: text(values, "avg"),
+            std: text(values, "std"),
+            quantiles: [
+                text(values, "q25"),
+                text(values, "q50"),
+                text(values, "q75"),
+            ],
+            count: number(values, "count").map_or(0, |n| n as i64),
+            null_percentage: number(values, "null_percentage").unwrap_or_default(),
+            histogram: Histogram::None,
+        })
+        .collect()
+}
+
+/// 查询一列的值分布
+fn histogram(
+    manager: &DatabaseManager,
+    relation: &str,
+    params: &QueryParams,
+    column: &ColumnStats,
+) -> Result<Histogram, DatabaseError> {
+    let name = quote_identifier(&column.name);
+    let bounds = column
+        .min
+        .as_ref()
+        .zip(column.max.as_ref())
+        .and_then(|(min, max)| Some((min.parse::<f64>().ok()?, max.parse::<f64>().ok()?)));
+
+    match bounds {
+        Some((min, max)) if column.is_numeric() => {
+            // 所有值相同时只有一组
+            let width = if max > min {
+                (max - min) / BINS as f64
+            } else {
+                1.0
+            };
+            let sql = format!(
+                "SELECT least(floor((CAST({name} AS DOUBLE) - ({min})) / {width}), {last})::INTEGER AS bin,
+                        count(*) AS n
+                 FROM {relation}
+                 WHERE {name} IS NOT NULL
+                 GROUP BY 1",
+                last = BINS - 1,
+            );
+            let mut counts = vec![0; BINS];
+            for values in manager.fetch(&sql, params, BINS)?.rows {
+                if let (Some(bin), Some(n)) = (integer(&values[0]), integer(&values[1])) {
+                    counts[(bin.max(0) as usize).min(BINS - 1)] += n;
+                }
+            }
+            Ok(Histogram::Bins {
+                start: min,
+                width,
+                counts,
+            })
+        }
+        _ => {
+            let sql = format!(
+                "SELECT CAST({name} AS VARCHAR) AS value, count(*) AS n
+                 FROM {relation}
+                 GROUP BY 1
+                 ORDER BY 2 DESC, 1
+                 LIMIT {TOP_VALUES}"
+            );
+            let values = manager
+                .fetch(&sql, params, TOP_VALUES)?
+                .rows
+                .into_iter()
+                .map(|values| (format_value(&values[0]), integer(&values[1]).unwrap_or(0)))
+                .collect();
+            Ok(Histogram::TopValues(values))
+        }
+    }
+}
+
+/// 读取整数值
+fn integer(value: &Value) -> Option<i64> {
+    format_value(value).parse().ok()
+}
+
+/// 渲染统计表格
+fn stats_grid(ui: &mut egui::Ui, report: &StatsReport) {
+    for key in [
+        "stats.column",
+        "stats.type",
+        "stats.nulls",
+        "stats.distinct",
+        "stats.min",
+        "stats.max",
+        "stats.avg",
+        "stats.std",
+        "stats.quantiles",
+        "stats.distribution",
+    ] {
+        ui.strong(tr(key));
+    }
+    ui.end_row();
+
+    let optional = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    for column in &report.columns {
+        ui.label(&column.name);
+        ui.label(egui::RichText::new(&column.data_type).weak());
+        ui.label(format!("{:.1}%", column.null_percentage))
+            .on_hover_text(tr_args("stats.count", &[("count", &column.count)]));
+        ui.label(
+            column
+                .approx_unique
+                .map_or_else(|| "-".to_string(), |n| n.to_string()),
+        );
+        ui.label(optional(&column.min));
+        ui.label(optional(&column.max));
+        ui.label(optional(&column.avg));
+        ui.label(optional(&column.std));
+        ui.label(
+            column
+                .quantiles
+                .iter()
+                .map(optional)
+                .collect::<Vec<_>>()
+                .join(" / "),
+        );
+        mini_histogram(ui, &column.histogram);
+        ui.end_row();
+    }
+}
+
+/// 绘制小型柱状图，悬停时显示每组的范围和数量
+fn mini_histogram(ui: &mut egui::Ui, histogram: &Histogram) {
+    let (counts, labels): (Vec<i64>, Vec<String>) = match histogram {
+        Histogram::None => {
+            ui.label("-");
+            return;
+        }
+        Histogram::Bins {
+            start,
+            width,
+            counts,
+        } => counts
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| {
+                let from = start + width * i as f64;
+                (n, format!("[{}, {}): {}", from, from + width, n))
+            })
+            .unzip(),
+        Histogram::TopValues(values) => values
+            .iter()
+            .map(|(value, n)| (*n, format!("{}: {}", value, n)))
+            .unzip(),
+    };
+
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(120.0, 28.0), egui::Sense::hover());
+    let highest = counts.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let bar_width = rect.width() / counts.len().max(1) as f32;
+    let color = ui.visuals().selection.bg_fill;
+    for (i, &n) in counts.iter().enumerate() {
+        let height = rect.height() * n as f32 / highest;
+        let left = rect.left() + bar_width * i as f32;
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(left + 1.0, rect.bottom() - height),
+            egui::pos2(left + bar_width - 1.0, rect.bottom()),
+        );
+        ui.painter().rect_filled(bar, 0.0, color);
+    }
+    response.on_hover_text(labels.join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_columns_with_histograms() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        manager
+            .execute_query(
+                "CREATE TABLE t AS SELECT i AS n, CASE WHEN i % 4 = 0 THEN NULL ELSE 'v' || (i % 2) END AS s
+                 FROM range(0, 100) r(i)",
+            )
+            .unwrap();
+
+        let relation = StatsReport::subquery("SELECT * FROM t WHERE n < $1 -- 注释");
+        let report = StatsReport::compute(
+            &manager,
+            "t".to_string(),
+            &relation,
+            &QueryParams::Positional(vec![Value::Int(40)]),
+        )
+        .unwrap();
+
+        let [n, s] = &report.columns[..] else {
+            panic!("应该有两列");
+        };
+        assert_eq!((n.name.as_str(), n.count), ("n", 40));
+        assert_eq!(n.min.as_deref(), Some("0"));
+        assert_eq!(n.max.as_deref(), Some("39"));
+        let Histogram::Bins { counts, .. } = &n.histogram else {
+            panic!("数值列应该按区间分组");
+        };
+        assert_eq!(counts.len(), BINS);
+        assert_eq!(counts.iter().sum::<i64>(), 40);
+
+        assert_eq!(s.null_percentage, 25.0);
+        assert_eq!(
+            s.histogram,
+            Histogram::TopValues(vec![
+                ("v1".to_string(), 20),
+                ("v0".to_string(), 10),
+                ("NULL".to_string(), 10),
+            ])
+        );
+    }
+
+    #[test]
+    fn columns_named_like_aliases() {
+        let mut manager = DatabaseManager::new();
+        manager.connect(":memory:").unwrap();
+        manager
+            .execute_query(
+                "CREATE TABLE t AS SELECT i AS x, 'v' || (i % 2) AS s, 0 AS bin, '' AS value, 0 AS n
+                 FROM range(0, 10) r(i)",
+            )
+            .unwrap();
+
+        let relation = StatsReport::subquery("SELECT * FROM t");
+        let report =
+            StatsReport::compute(&manager, "t".to_string(), &relation, &QueryParams::None).unwrap();
+        let Histogram::Bins { counts, .. } = &report.columns[0].histogram else {
+            panic!("数值列应该按区间分组");
+        };
+        assert_eq!(counts.iter().sum::<i64>(), 10);
+        assert_eq!(
+            report.columns[1].histogram,
+            Histogram::TopValues(vec![("v0".to_string(), 5), ("v1".to_string(), 5)])
+        );
+    }
+}
@@ -0,0 +1,380 @@
+//! 主题模块
+//! 内置浅色/深色主题，以及从配置目录加载的自定义主题
+
+use crate::config::{AppConfig, ThemeConfig};
+use crate::i18n::tr_args;
+use eframe::egui::{self, Color32};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::path::PathBuf;
+
+/// 自定义主题所在的子目录
+const THEMES_DIR_NAME: &str = "themes";
+
+/// 主题文件中的颜色，使用 `#RRGGBB` 或 `#RRGGBBAA` 格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HexColor(pub Color32);
+
+impl Serialize for HexColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for HexColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Color32::from_hex(text.trim()).map(HexColor).map_err(|_| {
+            serde::de::Error::custom(tr_args("config.invalid_color", &[("value", &text)]))
+        })
+    }
+}
+
+/// 主题文件中的界面颜色，未填写的项沿用基础主题
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiColorSpec {
+    pub background: Option<HexColor>, // 面板和窗口背景
+    pub text: Option<HexColor>,       // 文字
+    pub accent: Option<HexColor>,     // 选中和链接
+    pub faint_bg: Option<HexColor>,   // 表格交替行等浅背景
+    pub input_bg: Option<HexColor>,   // 输入框背景
+    pub code_bg: Option<HexColor>,    // 代码背景
+    pub border: Option<HexColor>,     // 窗口边框
+}
+
+/// 主题文件中的 SQL 语法颜色
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyntaxColorSpec {
+    pub keyword: Option<HexColor>,
+    pub identifier: Option<HexColor>,
+    pub string: Option<HexColor>,
+    pub number: Option<HexColor>,
+    pub comment: Option<HexColor>,
+    pub operator: Option<HexColor>,
+}
+
+/// 主题文件中的结果表格颜色
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridColorSpec {
+    pub null: Option<HexColor>, // NULL 值
+}
+
+/// 主题文件内容
+///
+/// ```json
+/// {
+///   "name": "Solarized Dark",
+///   "dark": true,
+///   "ui": { "background": "#002b36", "text": "#839496" },
+///   "syntax": { "keyword": "#859900", "string": "#2aa198" },
+///   "grid": { "null": "#586e75" },
+///   "error": "#dc322f"
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeSpec {
+    pub name: String,
+    pub dark: bool, // 基础主题是否为深色
+    pub ui: UiColorSpec,
+    pub syntax: SyntaxColorSpec,
+    pub grid: GridColorSpec,
+    pub error: Option<HexColor>,
+    pub warning: Option<HexColor>,
+}
+
+/// SQL 语法高亮颜色
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxColors {
+    pub keyword: Color32,
+    pub identifier: Color32,
+    pub string: Color32,
+    pub number: Color32,
+    pub comment: Color32,
+    pub operator: Color32,
+}
+
+/// 应用到界面上的主题
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub visuals: egui::Visuals,
+    pub syntax: SyntaxColors,
+    pub null_color: Color32,
+}
+
+impl Theme {
+    /// 内置浅色主题
+    pub fn light() -> Self {
+        let visuals = egui::Visuals::light();
+        Self {
+            name: String::new(),
+            syntax: SyntaxColors {
+                keyword: Color32::from_rgb(0, 0, 192),
+                identifier: visuals.text_color(),
+                string: Color32::from_rgb(163, 21, 21),
+                number: Color32::from_rgb(9, 134, 88),
+                comment: Color32::from_rgb(0, 128, 0),
+                operator: Color32::from_rgb(100, 100, 100),
+            },
+            null_color: Color32::from_gray(150),
+            visuals,
+        }
+    }
+
+    /// 内置深色主题
+    pub fn dark() -> Self {
+        let visuals = egui::Visuals::dark();
+        Self {
+            name: String::new(),
+            syntax: SyntaxColors {
+                keyword: Color32::from_rgb(86, 156, 214),
+                identifier: visuals.text_color(),
+                string: Color32::from_rgb(206, 145, 120),
+                number: Color32::from_rgb(181, 206, 168),
+                comment: Color32::from_rgb(106, 153, 85),
+                operator: Color32::from_rgb(180, 180, 180),
+            },
+            null_color: Color32::from_gray(110),
+            visuals,
+        }
+    }
+
+    /// 以内置主题为基础，应用主题文件中的颜色
+    pub fn from_spec(spec: &ThemeSpec) -> Self {
+        let mut theme = if spec.dark {
+            Self::dark()
+        } else {
+            Self::light()
+        };
+        theme.name = spec.name.clone();
+
+        let visuals = &mut theme.visuals;
+        let ui = &spec.ui;
+        if let Some(HexColor(color)) = ui.background {
+            visuals.panel_fill = color;
+            visuals.window_fill = color;
+        }
+        if let Some(HexColor(color)) = ui.text {
+            visuals.override_text_color = Some(color);
+            theme.syntax.identifier = color;
+        }
+        if let Some(HexColor(color)) = ui.accent {
+            visuals.selection.bg_fill = color;
+            visuals.hyperlink_color = color;
+        }
+        if let Some(HexColor(color)) = ui.faint_bg {
+            visuals.faint_bg_color = color;
+        }
+        if let Some(HexColor(color)) = ui.input_bg {
+            visuals.extreme_bg_color = color;
+            visuals.text_edit_bg_color = Some(color);
+        }
+        if let Some(HexColor(color)) = ui.code_bg {
+            visuals.code_bg_color = color;
+        }
+        if let Some(HexColor(color)) = ui.border {
+            visuals.window_stroke.color = color;
+        }
+        if let Some(HexColor(color)) = spec.error {
+            visuals.error_fg_color = color;
+        }
+        if let Some(HexColor(color)) = spec.warning {
+            visuals.warn_fg_color = color;
+        }
+
+        let syntax = &mut theme.syntax;
+        let overrides = [
+            (&mut syntax.keyword, spec.syntax.keyword),
+            (&mut syntax.identifier, spec.syntax.identifier),
+            (&mut syntax.string, spec.syntax.string),
+            (&mut syntax.number, spec.syntax.number),
+            (&mut syntax.comment, spec.syntax.comment),
+            (&mut syntax.operator, spec.syntax.operator),
+        ];
+        for (target, color) in overrides {
+            if let Some(HexColor(color)) = color {
+                *target = color;
+            }
+        }
+
+        if let Some(HexColor(color)) = spec.grid.null {
+            theme.null_color = color;
+        }
+
+        theme
+    }
+
+    /// 错误文字颜色
+    pub fn error_color(&self) -> Color32 {
+        self.visuals.error_fg_color
+    }
+}
+
+/// 当前使用的浅色和深色主题
+///
+/// 两套主题都会交给 egui，跟随系统时由 egui 根据系统设置切换。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeSet {
+    pub light: Theme,
+    pub dark: Theme,
+}
+
+impl Default for ThemeSet {
+    fn default() -> Self {
+        Self {
+            light: Theme::light(),
+            dark: Theme::dark(),
+        }
+    }
+}
+
+impl ThemeSet {
+    /// 按配置中的主题名称选择主题，名称为空或找不到时使用内置主题
+    ///
+    /// 返回主题集合和找不到的主题名称。
+    pub fn resolve(config: &ThemeConfig, custom: &[Theme]) -> (Self, Vec<String>) {
+        let mut missing = Vec::new();
+        let mut find = |name: &str, builtin: Theme| {
+            if name.is_empty() {
+                return builtin;
+            }
+            match custom.iter().find(|theme| theme.name == name) {
+                Some(theme) => theme.clone(),
+                None => {
+                    missing.push(name.to_string());
+                    builtin
+                }
+            }
+        };
+
+        let themes = Self {
+            light: find(&config.light, Theme::light()),
+            dark: find(&config.dark, Theme::dark()),
+        };
+        (themes, missing)
+    }
+
+    /// 与界面当前明暗一致的主题
+    pub fn current(&self, visuals: &egui::Visuals) -> &Theme {
+        if visuals.dark_mode {
+            &self.dark
+        } else {
+            &self.light
+        }
+    }
+
+    /// 把主题交给 egui，`preference` 决定使用哪一套
+    pub fn apply(&self, ctx: &egui::Context, preference: egui::ThemePreference) {
+        ctx.set_visuals_of(egui::Theme::Light, self.light.visuals.clone());
+        ctx.set_visuals_of(egui::Theme::Dark, self.dark.visuals.clone());
+        ctx.set_theme(preference);
+    }
+}
+
+/// 自定义主题目录
+pub fn themes_dir() -> PathBuf {
+    AppConfig::config_dir().join(THEMES_DIR_NAME)
+}
+
+/// 解析主题文件内容，没有名称时使用 `fallback_name`
+pub fn parse_theme(content: &str, fallback_name: &str) -> Result<Theme, String> {
+    let mut spec: ThemeSpec = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    if spec.name.trim().is_empty() {
+        spec.name = fallback_name.to_string();
+    }
+    Ok(Theme::from_spec(&spec))
+}
+
+/// 加载主题目录中的所有 `.json` 主题
+///
+/// 返回成功加载的主题（按名称排序）和加载失败的错误信息。
+pub fn load_custom_themes() -> (Vec<Theme>, Vec<String>) {
+    let mut themes = Vec::new();
+    let mut errors = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(themes_dir()) else {
+        return (themes, errors);
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| parse_theme(&content, &stem))
+        {
+            Ok(theme) => themes.push(theme),
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    themes.sort_by_key(|theme| theme.name.to_lowercase());
+    (themes, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_file_overrides_base_colors() {
+        let theme = parse_theme(
+            r##"{
+                "dark": true,
+                "ui": { "background": "#102030" },
+                "syntax": { "keyword": "#ff0000" },
+                "grid": { "null": "#808080" },
+                "error": "#ff00ff80"
+            }"##,
+            "my-theme",
+        )
+        .unwrap();
+
+        assert_eq!(theme.name, "my-theme");
+        assert!(theme.visuals.dark_mode);
+        assert_eq!(
+            theme.visuals.panel_fill,
+            Color32::from_rgb(0x10, 0x20, 0x30)
+        );
+        assert_eq!(theme.syntax.keyword, Color32::RED);
+        assert_eq!(theme.syntax.string, Theme::dark().syntax.string);
+        assert_eq!(theme.null_color, Color32::from_gray(0x80));
+        assert_eq!(
+            theme.error_color(),
+            Color32::from_rgba_unmultiplied(255, 0, 255, 128)
+        );
+    }
+
+    #[test]
+    fn invalid_color_is_rejected() {
+        let error = parse_theme(r#"{ "syntax": { "keyword": "blue" } }"#, "bad").unwrap_err();
+        assert!(error.contains("blue"));
+    }
+
+    #[test]
+    fn missing_custom_theme_falls_back_to_builtin() {
+        let custom = Theme {
+            name: "paper".to_string(),
+            ..Theme::light()
+        };
+        let config = ThemeConfig {
+            light: "paper".to_string(),
+            dark: "night".to_string(),
+            ..ThemeConfig::default()
+        };
+
+        let (themes, missing) = ThemeSet::resolve(&config, &[custom]);
+        assert_eq!(themes.light.name, "paper");
+        assert_eq!(themes.dark, Theme::dark());
+        assert_eq!(missing, ["night"]);
+    }
+}
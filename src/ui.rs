@@ -1,7 +1,11 @@
 //! UI 模块
 //! 处理界面渲染和用户交互
 
-use crate::app::{MenuType, MyApp};
+use crate::app::{MenuType, MyApp, NOTIFICATION_TIMEOUT, NotificationLevel};
+use crate::database::ObjectKind;
+use crate::ddl::SqlTemplate;
+use crate::i18n::{tr, tr_args};
+use crate::results::ResultView;
 use eframe::egui;
 
 /// UI 渲染器
@@ -13,14 +17,14 @@ impl UIRenderer {
         ui.horizontal(|ui| {
             ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
                 let menu_items = [
-                    (MenuType::File, "文件(F)"),
-                    (MenuType::Edit, "编辑(E)"),
-                    (MenuType::Navigate, "导航(N)"),
-                    (MenuType::Search, "搜索(A)"),
-                    (MenuType::SqlEditor, "SQL编辑器"),
-                    (MenuType::Database, "数据库(D)"),
-                    (MenuType::Window, "窗口(W)"),
-                    (MenuType::Help, "帮助(H)"),
+                    (MenuType::File, tr("menubar.file")),
+                    (MenuType::Edit, tr("menubar.edit")),
+                    (MenuType::Navigate, tr("menubar.navigate")),
+                    (MenuType::Search, tr("menubar.search")),
+                    (MenuType::SqlEditor, tr("menubar.sql_editor")),
+                    (MenuType::Database, tr("menubar.database")),
+                    (MenuType::Window, tr("menubar.window")),
+                    (MenuType::Help, tr("menubar.help")),
                 ];
 
                 for (i, (menu_type, label)) in menu_items.iter().enumerate() {
@@ -49,92 +53,93 @@ impl UIRenderer {
 
     /// 渲染下拉菜单
     pub fn render_dropdown_menu(app: &mut MyApp, ctx: &egui::Context) {
-        if let (Some(active_menu), Some(click_pos)) = (&app.active_menu, app.menu_click_pos) {
-            if let Some(menu_items) = app.menu_data.get(active_menu) {
-                let menu_height = (menu_items.len() as f32 * 25.0) + 10.0;
-
-                // 克隆菜单项数据以避免借用冲突
-                let menu_items_clone = menu_items.clone();
-                let mut action_taken = None;
-
-                // 使用 Area 而不是 Window，更简单可靠
-                egui::Area::new(egui::Id::new("dropdown_menu"))
-                    .fixed_pos(click_pos)
-                    .show(ctx, |ui| {
-                        egui::Frame::menu(ui.style())
-                            .inner_margin(egui::Margin::same(5))
-                            .show(ui, |ui| {
-                                ui.set_width(200.0);
-                                ui.set_max_height(menu_height);
+        if let (Some(active_menu), Some(click_pos)) = (&app.active_menu, app.menu_click_pos)
+            && let Some(menu_items) = app.menu_data.get(active_menu)
+        {
+            let menu_height = (menu_items.len() as f32 * 25.0) + 10.0;
 
-                                for item in &menu_items_clone {
-                                    if item.label == "---" {
-                                        ui.separator();
-                                    } else {
-                                        // 为每个菜单项创建可点击区域
-                                        let response = ui.horizontal(|ui| {
-                                            // 菜单项标签
-                                            ui.with_layout(
-                                                egui::Layout::left_to_right(
-                                                    egui::Align::Center,
-                                                ),
-                                                |ui| {
+            // 克隆菜单项数据以避免借用冲突
+            let menu_items_clone = menu_items.clone();
+            let mut action_taken = None;
+
+            // 使用 Area 而不是 Window，更简单可靠
+            egui::Area::new(egui::Id::new("dropdown_menu"))
+                .fixed_pos(click_pos)
+                .show(ctx, |ui| {
+                    egui::Frame::menu(ui.style())
+                        .inner_margin(egui::Margin::same(5))
+                        .show(ui, |ui| {
+                            ui.set_width(200.0);
+                            ui.set_max_height(menu_height);
+
+                            for item in &menu_items_clone {
+                                if item.label == "---" {
+                                    ui.separator();
+                                } else {
+                                    // 为每个菜单项创建可点击区域
+                                    let response = ui.horizontal(|ui| {
+                                        // 菜单项标签
+                                        ui.with_layout(
+                                            egui::Layout::left_to_right(egui::Align::Center),
+                                            |ui| {
+                                                if item.enabled {
+                                                    ui.add(egui::Label::new(tr(&item.label)));
+                                                } else {
+                                                    ui.label(
+                                                        egui::RichText::new(tr(&item.label))
+                                                            .color(egui::Color32::GRAY),
+                                                    );
+                                                }
+                                            },
+                                        );
+
+                                        // 快捷键
+                                        ui.with_layout(
+                                            egui::Layout::right_to_left(egui::Align::Center),
+                                            |ui| {
+                                                if let Some(shortcut) = &item.shortcut {
                                                     if item.enabled {
-                                                        ui.add(egui::Label::new(&item.label));
+                                                        ui.label(shortcut);
                                                     } else {
                                                         ui.label(
-                                                            egui::RichText::new(&item.label)
+                                                            egui::RichText::new(shortcut)
                                                                 .color(egui::Color32::GRAY),
                                                         );
                                                     }
-                                                },
-                                            );
-
-                                            // 快捷键
-                                            ui.with_layout(
-                                                egui::Layout::right_to_left(
-                                                    egui::Align::Center,
-                                                ),
-                                                |ui| {
-                                                    if let Some(shortcut) = &item.shortcut {
-                                                        if item.enabled {
-                                                            ui.label(shortcut);
-                                                        } else {
-                                                            ui.label(
-                                                                egui::RichText::new(shortcut)
-                                                                    .color(egui::Color32::GRAY),
-                                                            );
-                                                        }
-                                                    }
-                                                },
-                                            );
-                                        });
+                                                }
+                                            },
+                                        );
+                                    });
 
-                                        // 为整个菜单项区域添加点击感应
-                                        let response = ui.interact(response.response.rect, egui::Id::new(&item.action), egui::Sense::click());
-                                        
-                                        // 记录点击的菜单项
-                                        if response.clicked() && item.enabled {
-                                            action_taken = Some(item.action.clone());
-                                        }
+                                    // 为整个菜单项区域添加点击感应
+                                    let response = ui.interact(
+                                        response.response.rect,
+                                        egui::Id::new(&item.action),
+                                        egui::Sense::click(),
+                                    );
+
+                                    // 记录点击的菜单项
+                                    if response.clicked() && item.enabled {
+                                        action_taken = Some(item.action.clone());
                                     }
                                 }
-                            });
-                    });
+                            }
+                        });
+                });
 
-                // 在闭包外部处理菜单动作
-                if let Some(action) = action_taken {
-                    app.handle_menu_action(&action);
-                    // 点击菜单项后关闭菜单
-                    app.active_menu = None;
-                    app.menu_open_time = None;
-                    app.menu_click_pos = None;
-                }
+            // 在闭包外部处理菜单动作
+            if let Some(action) = action_taken {
+                app.handle_menu_action(&action);
+                // 点击菜单项后关闭菜单
+                app.active_menu = None;
+                app.menu_open_time = None;
+                app.menu_click_pos = None;
             }
         }
     }
 
     /// 渲染菜单项
+    #[allow(dead_code)]
     fn render_menu_items(ui: &mut egui::Ui, app: &mut MyApp, menu_items: &[crate::app::MenuItem]) {
         ui.vertical(|ui| {
             for item in menu_items {
@@ -148,13 +153,14 @@ impl UIRenderer {
     }
 
     /// 渲染单个菜单项
+    #[allow(dead_code)]
     fn render_menu_item(ui: &mut egui::Ui, app: &mut MyApp, item: &crate::app::MenuItem) {
         if item.enabled {
             let response = ui
                 .horizontal(|ui| {
                     // 菜单项标签 - 添加点击感应
                     ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
-                        ui.add(egui::Label::new(&item.label).sense(egui::Sense::click()));
+                        ui.add(egui::Label::new(tr(&item.label)).sense(egui::Sense::click()));
                     });
 
                     // 快捷键
@@ -176,7 +182,7 @@ impl UIRenderer {
             // 禁用的菜单项
             ui.horizontal(|ui| {
                 ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
-                    ui.label(egui::RichText::new(&item.label).color(egui::Color32::GRAY));
+                    ui.label(egui::RichText::new(tr(&item.label)).color(egui::Color32::GRAY));
                 });
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -190,47 +196,274 @@ impl UIRenderer {
 
     /// 渲染主内容区域
     pub fn render_main_content(ui: &mut egui::Ui, app: &mut MyApp) {
+        if !app.editor_tabs.is_empty() {
+            Self::render_editor_tabs(ui, app);
+            return;
+        }
+
         ui.horizontal(|ui| {
-            ui.label("您的姓名: ");
+            ui.label(tr("welcome.name"));
             ui.text_edit_singleline(&mut app.name);
         });
 
-        ui.add(egui::Slider::new(&mut app.age, 0..=120).text("年龄"));
+        ui.add(egui::Slider::new(&mut app.age, 0..=120).text(tr("welcome.age")));
 
-        if ui.button("每年点击").clicked() {
+        if ui.button(tr("welcome.click_each_year")).clicked() {
             app.age += 1;
         }
 
-        ui.label(format!("你好 '{}', 年龄 {}", app.name, app.age));
+        ui.label(tr_args(
+            "welcome.greeting",
+            &[("name", &app.name), ("age", &app.age)],
+        ));
 
         // 显示当前文件信息
         if let Some(file) = &app.current_file {
-            ui.label(format!("当前文件: {}", file));
+            ui.label(tr_args("welcome.current_file", &[("path", file)]));
         }
 
         // 深色模式切换
-        if ui.button("切换深色模式").clicked() {
+        if ui.button(tr("welcome.toggle_dark_mode")).clicked() {
             app.toggle_dark_mode();
         }
     }
 
+    /// 渲染编辑器标签页
+    fn render_editor_tabs(ui: &mut egui::Ui, app: &mut MyApp) {
+        let mut close_index = None;
+
+        ui.horizontal_wrapped(|ui| {
+            for (i, tab) in app.editor_tabs.iter().enumerate() {
+                let title = if tab.is_dirty {
+                    format!("● {}", tab.title)
+                } else {
+                    tab.title.clone()
+                };
+
+                if ui
+                    .selectable_label(app.active_editor_tab == Some(i), title)
+                    .clicked()
+                {
+                    app.active_editor_tab = Some(i);
+                }
+                if ui.small_button("×").clicked() {
+                    close_index = Some(i);
+                }
+                ui.add_space(4.0);
+            }
+        });
+        ui.separator();
+
+        if let Some(index) = close_index {
+            app.close_editor_tab(index);
+        }
+
+        let word_wrap = app.config.editor.word_wrap;
+        let theme = app.themes.current(ui.visuals());
+        let syntax = theme.syntax.clone();
+        let null_color = theme.null_color;
+        let mut run = false;
+        let mut toolbar_action = None;
+        if let Some(tab) = app
+            .active_editor_tab
+            .and_then(|i| app.editor_tabs.get_mut(i))
+        {
+            ui.horizontal(|ui| {
+                if ui.button(tr("editor.run")).clicked() {
+                    run = true;
+                }
+                if ui.button(tr("editor.explain")).clicked() {
+                    toolbar_action = Some("explain");
+                }
+                if ui.button(tr("editor.explain_analyze")).clicked() {
+                    toolbar_action = Some("explain_analyze");
+                }
+                if let Some(connection) = &tab.connection {
+                    let text = tr_args("editor.connection", &[("path", connection)]);
+                    ui.label(egui::RichText::new(text).weak());
+                }
+
+                // 事务控制
+                let manager = tab
+                    .connection
+                    .as_ref()
+                    .and_then(|path| app.connections.iter().find(|c| &c.path == path))
+                    .map(|c| &c.manager);
+                if let Some(manager) = manager {
+                    ui.separator();
+                    let pending = manager.in_transaction();
+                    let mut auto_commit = manager.auto_commit();
+                    let response = ui
+                        .add_enabled(
+                            !pending,
+                            egui::Checkbox::new(&mut auto_commit, tr("editor.auto_commit")),
+                        )
+                        .on_disabled_hover_text(tr("editor.auto_commit_pending"));
+                    if response.changed() {
+                        toolbar_action = Some("toggle_auto_commit");
+                    }
+                    if ui
+                        .add_enabled(pending, egui::Button::new(tr("editor.commit")))
+                        .clicked()
+                    {
+                        toolbar_action = Some("commit");
+                    }
+                    if ui
+                        .add_enabled(pending, egui::Button::new(tr("editor.rollback")))
+                        .clicked()
+                    {
+                        toolbar_action = Some("rollback");
+                    }
+                }
+            });
+
+            // 结果面板
+            if tab.result.is_some() || tab.error.is_some() || tab.plan.is_some() {
+                egui::TopBottomPanel::bottom("results_panel")
+                    .resizable(true)
+                    .default_height(240.0)
+                    .show_inside(ui, |ui| {
+                        // 性能信息显示在结果下方，可以折叠
+                        if let Some(profile) = tab.result.as_ref().and_then(|r| r.profile.as_ref())
+                        {
+                            let mut show_plan = false;
+                            egui::TopBottomPanel::bottom("profile_panel").show_inside(ui, |ui| {
+                                show_plan = crate::profile::show(ui, profile);
+                            });
+                            if show_plan {
+                                tab.plan = profile.plan.clone();
+                                tab.view = ResultView::Plan;
+                            }
+                        }
+                        if tab.result.is_some() || tab.plan.is_some() {
+                            ui.horizontal(|ui| {
+                                ui.selectable_value(
+                                    &mut tab.view,
+                                    ResultView::Grid,
+                                    tr("results.grid"),
+                                );
+                                if tab.result.is_some() {
+                                    ui.selectable_value(
+                                        &mut tab.view,
+                                        ResultView::Chart,
+                                        tr("results.chart"),
+                                    );
+                                    ui.selectable_value(
+                                        &mut tab.view,
+                                        ResultView::Record,
+                                        tr("results.record"),
+                                    );
+                                }
+                                if tab.plan.is_some() {
+                                    ui.selectable_value(
+                                        &mut tab.view,
+                                        ResultView::Plan,
+                                        tr("results.plan"),
+                                    );
+                                }
+                            });
+                        }
+                        if let Some(error) = &tab.error {
+                            ui.colored_label(ui.visuals().error_fg_color, error);
+                        }
+                        match (&tab.plan, &tab.result) {
+                            (Some(plan), _) if tab.view == ResultView::Plan => {
+                                crate::explain::show(ui, plan)
+                            }
+                            (_, Some(result)) if tab.view == ResultView::Chart => {
+                                if let Some(action) = crate::chart::show(ui, result, &mut tab.chart)
+                                {
+                                    toolbar_action = Some(action);
+                                }
+                            }
+                            (_, Some(result)) if tab.view == ResultView::Record => {
+                                crate::record::show(
+                                    ui,
+                                    result,
+                                    &app.config.grid,
+                                    null_color,
+                                    &mut tab.grid,
+                                    &mut tab.record,
+                                );
+                            }
+                            (_, Some(result)) => {
+                                if let Some(edits) = &mut tab.edits
+                                    && crate::editing::toolbar(ui, edits, result, &mut tab.grid)
+                                {
+                                    toolbar_action = Some("apply_edits");
+                                }
+                                if crate::filter::filter_bar(ui, result, &mut tab.grid) {
+                                    toolbar_action = Some("push_down_filter");
+                                }
+                                let action = crate::results::show(
+                                    ui,
+                                    result,
+                                    &app.config.grid,
+                                    null_color,
+                                    &mut tab.grid,
+                                    tab.edits.as_mut(),
+                                );
+                                if action.is_some() {
+                                    toolbar_action = action;
+                                }
+                            }
+                            _ => {}
+                        }
+                    });
+            }
+
+            let mut layouter = |ui: &egui::Ui, text: &dyn egui::TextBuffer, wrap_width: f32| {
+                let job = crate::highlight::sql_layout_job(
+                    text.as_str(),
+                    egui::TextStyle::Monospace.resolve(ui.style()),
+                    &syntax,
+                    if word_wrap { wrap_width } else { f32::INFINITY },
+                );
+                ui.fonts_mut(|fonts| fonts.layout_job(job))
+            };
+
+            egui::ScrollArea::both().show(ui, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::multiline(&mut tab.content)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(20)
+                        .layouter(&mut layouter),
+                );
+                if response.changed() {
+                    tab.is_dirty = true;
+                }
+            });
+        }
+
+        if run {
+            app.run_query();
+        }
+        if let Some(action) = toolbar_action {
+            app.handle_menu_action(action);
+        }
+    }
+
     /// 渲染侧边栏
     pub fn render_sidebar(ui: &mut egui::Ui, app: &mut MyApp) {
         ui.vertical(|ui| {
             // 标签页标签区域
             ui.horizontal(|ui| {
                 // 可以在这里添加更多标签页标签
-                if ui.button("文件管理器").clicked() {
+                if ui.button(tr("sidebar.explorer")).clicked() {
                     app.handle_menu_action("open_folder");
                 }
 
-                if ui.button("数据库").clicked() {
-                    app.set_status_message("正在打开数据库连接管理器...".to_owned());
-                    println!("打开数据库连接");
+                if ui.button(tr("sidebar.database")).clicked() {
+                    app.handle_menu_action("connect_db");
                 }
 
-                if ui.button("设置").clicked() {
-                    app.set_status_message("正在打开设置面板...".to_owned());
+                if ui.button(tr("sidebar.history")).clicked() {
+                    app.handle_menu_action("toggle_history");
+                }
+
+                if ui.button(tr("sidebar.settings")).clicked() {
+                    app.settings.open(&app.config);
                     println!("打开设置");
                 }
             });
@@ -242,42 +475,166 @@ impl UIRenderer {
                 if !app.explorer_tabs.is_empty() {
                     Self::render_explorer_tabs_content(ui, app);
                 }
+
+                // 数据库连接列表
+                if !app.connections.is_empty() {
+                    ui.separator();
+                    Self::render_connections(ui, app);
+                }
+
+                // 查询库
+                ui.separator();
+                crate::library::LibraryPanel::show(app, ui);
             });
         });
     }
 
+    /// 渲染数据库连接列表
+    fn render_connections(ui: &mut egui::Ui, app: &mut MyApp) {
+        ui.label(egui::RichText::new(tr("sidebar.connections")).strong());
+
+        for i in 0..app.connections.len() {
+            let connection = &app.connections[i];
+            let response = ui
+                .selectable_label(
+                    app.active_connection == Some(i),
+                    format!("🗄 {}", connection.name),
+                )
+                .on_hover_text(&connection.path);
+
+            if response.clicked() {
+                app.active_connection = Some(i);
+                let message = tr_args("status.connection_switched", &[("name", &connection.name)]);
+                app.set_status_message(message);
+            }
+
+            let mut stats_table = None;
+            let mut generate = None;
+            let mut design = None;
+            egui::CollapsingHeader::new(tr("sidebar.objects"))
+                .id_salt(("connection_tables", i))
+                .show(ui, |ui| {
+                    let connection = &mut app.connections[i];
+                    ui.horizontal(|ui| {
+                        if ui.small_button(tr("sidebar.refresh_tables")).clicked() {
+                            connection.objects = None;
+                        }
+                        if ui.small_button(tr("sidebar.new_table")).clicked() {
+                            design = Some(None);
+                        }
+                    });
+                    let objects = connection.objects.get_or_insert_with(|| {
+                        connection.manager.list_objects().unwrap_or_else(|e| {
+                            eprintln!("读取数据库对象失败: {}", e);
+                            Vec::new()
+                        })
+                    });
+                    for kind in ObjectKind::ALL {
+                        let count = objects.iter().filter(|o| o.kind == kind).count();
+                        if count == 0 {
+                            continue;
+                        }
+                        let (icon, title) = Self::object_kind_label(kind);
+                        egui::CollapsingHeader::new(format!("{} ({})", tr(title), count))
+                            .id_salt(("connection_objects", i, kind.name()))
+                            .default_open(kind == ObjectKind::Table)
+                            .show(ui, |ui| {
+                                for object in objects.iter().filter(|o| o.kind == kind) {
+                                    let table = &object.name;
+                                    let label = match table.schema.as_deref() {
+                                        Some(schema) if schema != "main" => {
+                                            format!("{}.{}", schema, table.name)
+                                        }
+                                        _ => table.name.clone(),
+                                    };
+                                    ui.label(format!("{} {}", icon, label))
+                                        .on_hover_text(table.to_string())
+                                        .context_menu(|ui| {
+                                            if matches!(kind, ObjectKind::Table | ObjectKind::View)
+                                                && ui.button(tr("sidebar.column_stats")).clicked()
+                                            {
+                                                stats_table = Some(table.clone());
+                                                ui.close();
+                                            }
+                                            if kind == ObjectKind::Table
+                                                && ui.button(tr("sidebar.design_table")).clicked()
+                                            {
+                                                design = Some(Some(table.clone()));
+                                                ui.close();
+                                            }
+                                            ui.menu_button(tr("sidebar.generate_sql"), |ui| {
+                                                for template in SqlTemplate::ALL
+                                                    .into_iter()
+                                                    .filter(|t| t.applies(kind))
+                                                {
+                                                    if ui.button(tr(template.label())).clicked() {
+                                                        generate = Some((object.clone(), template));
+                                                        ui.close();
+                                                    }
+                                                }
+                                            });
+                                        });
+                                }
+                            });
+                    }
+                });
+            if let Some(table) = stats_table {
+                app.show_table_stats(i, &table);
+            }
+            if let Some((object, template)) = generate {
+                app.generate_sql(i, &object, template);
+            }
+            if let Some(table) = design {
+                app.open_table_designer(i, table.as_ref());
+            }
+        }
+    }
+
+    /// 对象类型的图标和翻译键
+    fn object_kind_label(kind: ObjectKind) -> (&'static str, &'static str) {
+        match kind {
+            ObjectKind::Table => ("▦", "sidebar.kind_tables"),
+            ObjectKind::View => ("◫", "sidebar.kind_views"),
+            ObjectKind::Index => ("↯", "sidebar.kind_indexes"),
+            ObjectKind::Sequence => ("#", "sidebar.kind_sequences"),
+            ObjectKind::Macro => ("ƒ", "sidebar.kind_macros"),
+        }
+    }
+
     /// 渲染文件资源管理器标签页内容
     fn render_explorer_tabs_content(ui: &mut egui::Ui, app: &mut MyApp) {
         // 渲染当前活动标签页的内容
-        if let Some(active_index) = app.active_explorer_tab {
-            if let Some(tab) = app.explorer_tabs.get_mut(active_index) {
-                ui.label(egui::RichText::new(&format!("{} - {}", tab.name, tab.path)).strong());
+        if let Some(active_index) = app.active_explorer_tab
+            && let Some(tab) = app.explorer_tabs.get_mut(active_index)
+        {
+            ui.label(egui::RichText::new(format!("{} - {}", tab.name, tab.path)).strong());
 
-                // 保存状态消息到队列，稍后处理
-                let mut status_messages = Vec::new();
+            // 保存状态消息到队列，稍后处理
+            let mut status_messages = Vec::new();
 
-                // 渲染文件树
-                Self::render_file_tree(
-                    ui,
-                    &mut tab.file_tree,
-                    &mut tab.expanded_paths,
-                    |message| {
-                        status_messages.push(message);
-                    },
-                );
+            // 渲染文件树
+            Self::render_file_tree(
+                ui,
+                &mut tab.file_tree,
+                &mut tab.expanded_paths,
+                app.config.files.show_hidden_files,
+                |message| {
+                    status_messages.push(message);
+                },
+            );
 
-                // 处理状态消息（在闭包外）
-                for message in status_messages {
-                    app.set_status_message(message);
-                }
+            // 处理状态消息（在闭包外）
+            for message in status_messages {
+                app.set_status_message(message);
             }
         }
     }
 
     /// 渲染文件资源管理器标签页
+    #[allow(dead_code)]
     fn render_explorer_tabs(ui: &mut egui::Ui, app: &mut MyApp) {
         ui.horizontal(|ui| {
-            ui.label("资源管理器:");
+            ui.label(tr("explorer.label"));
 
             // 显示所有标签页
             let tab_indices: Vec<usize> = (0..app.explorer_tabs.len()).collect();
@@ -300,7 +657,10 @@ impl UIRenderer {
                             tab.is_active = j == i;
                         }
                     }
-                    app.set_status_message(format!("切换到资源管理器: {}", tab_name));
+                    app.set_status_message(tr_args(
+                        "status.explorer_switched",
+                        &[("name", &tab_name)],
+                    ));
                 }
             }
         });
@@ -309,13 +669,21 @@ impl UIRenderer {
     /// 渲染文件树
     fn render_file_tree(
         ui: &mut egui::Ui,
-        file_tree: &mut Vec<crate::app::FileItem>,
+        file_tree: &mut [crate::app::FileItem],
         expanded_paths: &mut std::collections::HashSet<String>,
+        show_hidden: bool,
         mut set_status_message: impl FnMut(String),
     ) {
         ui.vertical(|ui| {
             for item in file_tree.iter_mut() {
-                Self::render_file_item(ui, item, expanded_paths, 0, &mut set_status_message);
+                Self::render_file_item(
+                    ui,
+                    item,
+                    expanded_paths,
+                    show_hidden,
+                    0,
+                    &mut set_status_message,
+                );
             }
         });
     }
@@ -325,6 +693,7 @@ impl UIRenderer {
         ui: &mut egui::Ui,
         item: &mut crate::app::FileItem,
         expanded_paths: &mut std::collections::HashSet<String>,
+        show_hidden: bool,
         depth: usize,
         set_status_message: &mut impl FnMut(String),
     ) {
@@ -342,62 +711,67 @@ impl UIRenderer {
                     // 切换展开状态
                     if is_expanded {
                         expanded_paths.remove(&item.path);
-                        set_status_message(format!("折叠 {}", item.name));
+                        set_status_message(tr_args(
+                            "status.folder_collapsed",
+                            &[("name", &item.name)],
+                        ));
                     } else {
                         expanded_paths.insert(item.path.clone());
-                        set_status_message(format!("展开 {}", item.name));
+                        set_status_message(tr_args(
+                            "status.folder_expanded",
+                            &[("name", &item.name)],
+                        ));
 
                         // 如果文件夹还没有加载子项，现在加载
-                        if item.children.len() == 1 && item.children[0].name == "..." {
-                            if let Ok(entries) = std::fs::read_dir(&item.path) {
-                                let mut children = Vec::new();
-                                for entry in entries {
-                                    if let Ok(entry) = entry {
-                                        let path = entry.path();
-                                        let is_directory = path.is_dir();
-
-                                        // 获取文件名
-                                        let name = match path.file_name() {
-                                            Some(os_str) => os_str.to_string_lossy().to_string(),
-                                            None => continue,
-                                        };
-
-                                        // 跳过隐藏文件
-                                        if name.starts_with('.') {
-                                            continue;
-                                        }
+                        if item.children.len() == 1
+                            && item.children[0].name == "..."
+                            && let Ok(entries) = std::fs::read_dir(&item.path)
+                        {
+                            let mut children = Vec::new();
+                            for entry in entries.flatten() {
+                                let path = entry.path();
+                                let is_directory = path.is_dir();
 
-                                        children.push(crate::app::FileItem {
-                                            name,
-                                            path: path.to_string_lossy().to_string(),
-                                            is_directory,
-                                            is_expanded: false,
-                                            children: if is_directory {
-                                                vec![crate::app::FileItem {
-                                                    name: "...".to_string(),
-                                                    path: "".to_string(),
-                                                    is_directory: false,
-                                                    is_expanded: false,
-                                                    children: Vec::new(),
-                                                }]
-                                            } else {
-                                                Vec::new()
-                                            },
-                                        });
-                                    }
+                                // 获取文件名
+                                let name = match path.file_name() {
+                                    Some(os_str) => os_str.to_string_lossy().to_string(),
+                                    None => continue,
+                                };
+
+                                // 跳过隐藏文件
+                                if name.starts_with('.') && !show_hidden {
+                                    continue;
                                 }
 
-                                // 按文件夹在前，文件在后排序
-                                children.sort_by(|a, b| {
-                                    if a.is_directory != b.is_directory {
-                                        b.is_directory.cmp(&a.is_directory)
+                                children.push(crate::app::FileItem {
+                                    name,
+                                    path: path.to_string_lossy().to_string(),
+                                    is_directory,
+                                    is_expanded: false,
+                                    children: if is_directory {
+                                        vec![crate::app::FileItem {
+                                            name: "...".to_string(),
+                                            path: "".to_string(),
+                                            is_directory: false,
+                                            is_expanded: false,
+                                            children: Vec::new(),
+                                        }]
                                     } else {
-                                        a.name.to_lowercase().cmp(&b.name.to_lowercase())
-                                    }
+                                        Vec::new()
+                                    },
                                 });
-
-                                item.children = children;
                             }
+
+                            // 按文件夹在前，文件在后排序
+                            children.sort_by(|a, b| {
+                                if a.is_directory != b.is_directory {
+                                    b.is_directory.cmp(&a.is_directory)
+                                } else {
+                                    a.name.to_lowercase().cmp(&b.name.to_lowercase())
+                                }
+                            });
+
+                            item.children = children;
                         }
                     }
                 }
@@ -411,9 +785,9 @@ impl UIRenderer {
 
             if response.clicked() {
                 if item.is_directory {
-                    set_status_message(format!("打开文件夹: {}", item.name));
+                    set_status_message(tr_args("status.folder_clicked", &[("name", &item.name)]));
                 } else {
-                    set_status_message(format!("打开文件: {}", item.name));
+                    set_status_message(tr_args("status.file_clicked", &[("name", &item.name)]));
                     // 注意：这里无法直接设置 current_file，需要其他方式处理
                 }
             }
@@ -422,7 +796,14 @@ impl UIRenderer {
         // 渲染子项（如果文件夹是展开的）
         if item.is_directory && is_expanded {
             for child in &mut item.children {
-                Self::render_file_item(ui, child, expanded_paths, depth + 1, set_status_message);
+                Self::render_file_item(
+                    ui,
+                    child,
+                    expanded_paths,
+                    show_hidden,
+                    depth + 1,
+                    set_status_message,
+                );
             }
         }
     }
@@ -435,8 +816,218 @@ impl UIRenderer {
 
             // 右侧显示应用信息
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                ui.label("egui 应用");
+                ui.label(tr("status.app_name"));
+
+                // 未提交的事务
+                let pending = app.pending_transactions();
+                if !pending.is_empty() {
+                    let text = tr_args("status.pending_transactions", &[("count", &pending.len())]);
+                    ui.separator();
+                    ui.colored_label(ui.visuals().warn_fg_color, format!("● {}", text))
+                        .on_hover_text(pending.join("\n"));
+                }
+            });
+        });
+    }
+
+    /// 渲染退出确认对话框（有未提交的事务时）
+    pub fn render_close_prompt(app: &mut MyApp, ctx: &egui::Context) {
+        if !app.close_prompt {
+            return;
+        }
+
+        let mut choice = None;
+        let mut is_open = true;
+        egui::Window::new(tr("dialog.pending_transactions_title"))
+            .id(egui::Id::new("close_prompt"))
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(tr("dialog.pending_transactions"));
+                for name in app.pending_transactions() {
+                    ui.label(format!("• {}", name));
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("dialog.commit_and_exit")).clicked() {
+                        choice = Some(true);
+                    }
+                    if ui.button(tr("dialog.rollback_and_exit")).clicked() {
+                        choice = Some(false);
+                    }
+                    if ui.button(tr("dialog.cancel")).clicked() {
+                        app.close_prompt = false;
+                    }
+                });
+            });
+
+        if !is_open {
+            app.close_prompt = false;
+        }
+        if let Some(commit) = choice {
+            app.confirm_close(ctx, commit);
+        }
+    }
+
+    /// 渲染通知（右下角）
+    pub fn render_notifications(app: &mut MyApp, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        app.notifications.retain_mut(|n| {
+            let created_at = *n.created_at.get_or_insert(now);
+            n.level != NotificationLevel::Info || now - created_at < NOTIFICATION_TIMEOUT
+        });
+        if app.notifications.is_empty() {
+            return;
+        }
+
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("notifications"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -36.0))
+            .show(ctx, |ui| {
+                for (i, notification) in app.notifications.iter().enumerate() {
+                    let color = match notification.level {
+                        NotificationLevel::Info => ui.visuals().text_color(),
+                        NotificationLevel::Warning => ui.visuals().warn_fg_color,
+                        NotificationLevel::Error => ui.visuals().error_fg_color,
+                    };
+
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.set_max_width(360.0);
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new(&notification.title)
+                                    .strong()
+                                    .color(color),
+                            );
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    if ui.small_button("×").clicked() {
+                                        dismissed = Some(i);
+                                    }
+                                },
+                            );
+                        });
+                        if !notification.message.is_empty() {
+                            ui.label(&notification.message);
+                        }
+                    });
+                    ui.add_space(4.0);
+                }
+            });
+
+        if let Some(index) = dismissed {
+            app.notifications.remove(index);
+        }
+
+        // 普通通知需要在超时后自动消失
+        ctx.request_repaint_after(std::time::Duration::from_secs(1));
+    }
+}
+
+impl eframe::App for MyApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // 更新状态消息
+        self.update_status_message(ctx);
+
+        // 有未提交的事务时确认后再关闭
+        self.check_close_request(ctx);
+
+        // 热加载配置文件并应用外观
+        self.check_config_reload(ctx);
+        self.apply_appearance(ctx);
+
+        // 记录窗口状态并定期保存工作区
+        self.update_window_state(ctx);
+        self.auto_save_workspace(ctx);
+
+        // 简单的菜单关闭检测
+        // 只在用户点击外部区域或按 ESC 键时关闭菜单
+        if self.active_menu.is_some() {
+            // ESC 键关闭菜单
+            if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+                self.active_menu = None;
+                self.menu_open_time = None;
+                self.menu_click_pos = None;
+            }
+        }
+
+        // 快捷键
+        self.handle_shortcuts(ctx);
+
+        // 使用模块化的 UI 渲染
+        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            ui.vertical(|ui| {
+                UIRenderer::render_top_menu(ui, self, ctx);
+                // ui.separator();
+                // ui.horizontal(|ui| {
+                //     ui.label("状态栏");
+                // });
+            });
+        });
+
+        // 渲染下拉菜单
+        UIRenderer::render_dropdown_menu(self, ctx);
+
+        // 渲染底部状态栏
+        if self.panel_layout.status_bar_visible {
+            egui::TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
+                UIRenderer::render_status_bar(ui, self);
             });
+        }
+
+        // 渲染侧边栏
+        if self.panel_layout.sidebar_visible {
+            let response = egui::SidePanel::left("left_panel")
+                .default_width(self.panel_layout.sidebar_width)
+                .show(ctx, |ui| {
+                    UIRenderer::render_sidebar(ui, self);
+                });
+            self.panel_layout.sidebar_width = response.response.rect.width();
+        }
+
+        // 渲染查询历史面板
+        if self.panel_layout.history_visible {
+            egui::SidePanel::right("history_panel")
+                .default_width(320.0)
+                .show(ctx, |ui| {
+                    crate::history::HistoryPanel::show(self, ui);
+                });
+        }
+
+        // 渲染主内容区域
+        egui::CentralPanel::default().show(ctx, |ui| {
+            UIRenderer::render_main_content(ui, self);
         });
+
+        // 渲染设置窗口
+        crate::settings::SettingsState::show(self, ctx);
+        crate::library::LibraryPanel::show_save_dialog(self, ctx);
+        crate::library::PlaceholderPrompt::show(self, ctx);
+        crate::params::ParamPrompt::show(self, ctx);
+        crate::export::CopyDialog::show(self, ctx);
+        crate::stats::StatsReport::show(self, ctx);
+        crate::inspector::CellViewer::show(self, ctx);
+        crate::diff::ResultDiff::show(self, ctx);
+        crate::schema::SchemaCompare::show(self, ctx);
+        crate::designer::TableDesigner::show(self, ctx);
+
+        // 渲染通知
+        UIRenderer::render_close_prompt(self, ctx);
+        UIRenderer::render_notifications(self, ctx);
+
+        // 复制的内容交给系统剪贴板
+        if let Some(text) = self.clipboard.take() {
+            ctx.copy_text(text);
+        }
+
+        // 保持对配置文件修改的检查
+        ctx.request_repaint_after(std::time::Duration::from_secs(1));
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_workspace();
     }
 }
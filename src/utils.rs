@@ -65,6 +65,22 @@ impl StringUtils {
         format!("{}...", &s[..max_len - 3])
     }
 
+    /// 把字节数格式化为 KB、MB 等单位
+    pub fn format_bytes(bytes: u64) -> String {
+        const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+        let mut value = bytes as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            format!("{} B", bytes)
+        } else {
+            format!("{:.1} {}", value, UNITS[unit])
+        }
+    }
+
     /// 检查字符串是否为有效的文件名
     pub fn is_valid_filename(s: &str) -> bool {
         !s.is_empty()
@@ -145,6 +161,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(StringUtils::format_bytes(512), "512 B");
+        assert_eq!(StringUtils::format_bytes(1536), "1.5 KB");
+        assert_eq!(StringUtils::format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+
     #[test]
     fn test_filename_validation() {
         assert!(StringUtils::is_valid_filename("test.txt"));